@@ -1,26 +1,32 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::sync::Arc;
 
 use deno_ast::SourceMapOption;
 use deno_config::deno_json::CompilerOptionsParseError;
+use deno_config::deno_json::ConfigFile;
 use deno_config::deno_json::TsConfig;
 use deno_config::deno_json::TsConfigType;
 use deno_config::deno_json::TsConfigWithIgnoredOptions;
 use deno_config::deno_json::TsTypeLib;
 use deno_config::workspace::Workspace;
 use deno_config::workspace::WorkspaceDirectory;
+use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::serde_json;
 use deno_core::unsync::sync::AtomicFlag;
 use deno_core::url::Url;
 use deno_lib::util::hash::FastInsecureHasher;
 use deno_lint::linter::LintConfig as DenoLintConfig;
+use deno_path_util::url_to_file_path;
 use deno_semver::jsr::JsrDepPackageReq;
 use deno_semver::jsr::JsrPackageReqReference;
 use deno_semver::npm::NpmPackageReqReference;
 use once_cell::sync::OnceCell;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
 
 use crate::util::collections::FolderScopedMap;
 
@@ -118,6 +124,147 @@ fn value_to_dep_req(value: &str) -> Option<JsrDepPackageReq> {
   }
 }
 
+/// Minimum coverage percentages, whole numbers like the `--threshold-*`
+/// flags.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct CoverageThresholds {
+  pub lines: Option<u8>,
+  pub branches: Option<u8>,
+  pub functions: Option<u8>,
+}
+
+impl CoverageThresholds {
+  pub fn is_empty(&self) -> bool {
+    self.lines.is_none() && self.branches.is_none() && self.functions.is_none()
+  }
+
+  /// Takes the thresholds of `self` where they are set, and the ones of
+  /// `other` otherwise.
+  pub fn or(self, other: Self) -> Self {
+    Self {
+      lines: self.lines.or(other.lines),
+      branches: self.branches.or(other.branches),
+      functions: self.functions.or(other.functions),
+    }
+  }
+}
+
+/// The `coverage.thresholds` key of a config file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct CoverageThresholdsConfig {
+  #[serde(flatten)]
+  pub all_files: CoverageThresholds,
+  /// Thresholds for the files within a directory, relative to the config
+  /// file.
+  pub directories: BTreeMap<String, CoverageThresholds>,
+}
+
+/// The `coverage` key of a config file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct CoverageConfig {
+  pub thresholds: CoverageThresholdsConfig,
+}
+
+impl CoverageConfig {
+  pub fn from_config_file(config_file: &ConfigFile) -> Result<Self, AnyError> {
+    Ok(read_config_file_key(config_file, "coverage")?.unwrap_or_default())
+  }
+}
+
+/// The `lint.rules.import-boundaries` key of a config file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ImportBoundariesConfig {
+  pub boundaries: Vec<ImportBoundaryConfig>,
+}
+
+/// The imports that are denied in the modules matching `from`.
+///
+/// Paths are globs relative to the config file, and packages are matched by
+/// their name like `npm:express` or `jsr:@std/path`, without the version.
+/// A pattern starting with `!` excludes what it matches.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ImportBoundaryConfig {
+  /// The importing modules, or all of them when empty.
+  pub from: Vec<String>,
+  pub deny: Vec<String>,
+  /// Exceptions of `deny`.
+  pub allow: Vec<String>,
+  /// Replaces the message of the diagnostic.
+  pub message: Option<String>,
+}
+
+/// The `lint.rules.no-import-cycles` key of a config file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct NoImportCyclesConfig {
+  /// Leaves out `import type` and `export type` declarations, which are
+  /// erased at runtime.
+  pub ignore_type_imports: bool,
+  /// Cycles that are allowed, as the modules of each cycle relative to the
+  /// config file.
+  pub allow: Vec<Vec<String>>,
+}
+
+/// The options of the configurable lint rules, which are keyed by the rule
+/// code in `lint.rules` of a config file, next to `tags`, `include` and
+/// `exclude`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct LintRuleOptionsConfig {
+  #[serde(rename = "import-boundaries")]
+  pub import_boundaries: ImportBoundariesConfig,
+  #[serde(rename = "no-import-cycles")]
+  pub no_import_cycles: NoImportCyclesConfig,
+}
+
+impl LintRuleOptionsConfig {
+  pub fn from_config_file(config_file: &ConfigFile) -> Result<Self, AnyError> {
+    let Some(rules) = config_file
+      .json
+      .lint
+      .as_ref()
+      .and_then(|lint| lint.get("rules"))
+    else {
+      return Ok(Self::default());
+    };
+    Self::deserialize(rules).with_context(|| {
+      format!(
+        "Invalid \"lint.rules\" options in '{}'",
+        config_file.specifier
+      )
+    })
+  }
+}
+
+/// Reads a top-level key of a config file that `ConfigFile` doesn't keep,
+/// as `ConfigFileJson` drops the keys it doesn't know about.
+fn read_config_file_key<T: DeserializeOwned>(
+  config_file: &ConfigFile,
+  key: &str,
+) -> Result<Option<T>, AnyError> {
+  let path = url_to_file_path(&config_file.specifier)?;
+  let text = std::fs::read_to_string(&path)
+    .with_context(|| format!("Failed to read '{}'", path.display()))?;
+  parse_config_file_key(&text, key)
+    .with_context(|| format!("Invalid \"{key}\" in '{}'", path.display()))
+}
+
+fn parse_config_file_key<T: DeserializeOwned>(
+  text: &str,
+  key: &str,
+) -> Result<Option<T>, AnyError> {
+  let value = jsonc_parser::parse_to_serde_value(text, &Default::default())?;
+  match value.and_then(|mut value| value.get_mut(key).map(|v| v.take())) {
+    Some(value) => Ok(Some(serde_json::from_value(value)?)),
+    None => Ok(None),
+  }
+}
+
 fn check_warn_tsconfig(
   ts_config: &TsConfigWithIgnoredOptions,
   logged_warnings: &LoggedWarnings,
@@ -376,3 +523,98 @@ fn ts_config_to_transpile_and_emit_options(
     pre_computed_hash: transpile_and_emit_options_hash,
   })
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn parses_coverage_config() {
+    let config: CoverageConfig = parse_config_file_key(
+      r#"{
+        // comment
+        "coverage": {
+          "thresholds": {
+            "lines": 80,
+            "branches": 70,
+            "directories": { "src/core": { "functions": 90 } }
+          }
+        }
+      }"#,
+      "coverage",
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+      config.thresholds.all_files,
+      CoverageThresholds {
+        lines: Some(80),
+        branches: Some(70),
+        functions: None,
+      }
+    );
+    assert_eq!(
+      config.thresholds.directories.get("src/core"),
+      Some(&CoverageThresholds {
+        lines: None,
+        branches: None,
+        functions: Some(90),
+      })
+    );
+    let config: Option<CoverageConfig> =
+      parse_config_file_key(r#"{ "name": "@scope/pkg" }"#, "coverage").unwrap();
+    assert!(config.is_none());
+  }
+
+  #[test]
+  fn parses_lint_rule_options_config() {
+    let specifier = Url::parse("file:///project/deno.json").unwrap();
+    let config_file = ConfigFile::new(
+      r#"{
+        // comment
+        "lint": {
+          "rules": {
+            "tags": ["recommended"],
+            "no-import-cycles": {
+              "ignoreTypeImports": true,
+              "allow": [["./a.ts", "./b.ts"]]
+            },
+            "import-boundaries": {
+              "boundaries": [{ "from": ["src/domain/**"], "deny": ["npm:*"] }]
+            }
+          }
+        }
+      }"#,
+      specifier.clone(),
+    )
+    .unwrap();
+    let options =
+      LintRuleOptionsConfig::from_config_file(&config_file).unwrap();
+    assert_eq!(
+      options.no_import_cycles,
+      NoImportCyclesConfig {
+        ignore_type_imports: true,
+        allow: vec![vec!["./a.ts".to_string(), "./b.ts".to_string()]],
+      }
+    );
+    assert_eq!(options.import_boundaries.boundaries.len(), 1);
+    assert_eq!(
+      options.import_boundaries.boundaries[0].deny,
+      vec!["npm:*".to_string()]
+    );
+
+    let config_file = ConfigFile::new(
+      r#"{ "lint": { "rules": { "no-import-cycles": { "unknown": 1 } } } }"#,
+      specifier.clone(),
+    )
+    .unwrap();
+    assert!(LintRuleOptionsConfig::from_config_file(&config_file).is_err());
+
+    let config_file =
+      ConfigFile::new(r#"{ "name": "@scope/pkg" }"#, specifier).unwrap();
+    assert_eq!(
+      LintRuleOptionsConfig::from_config_file(&config_file).unwrap(),
+      LintRuleOptionsConfig::default()
+    );
+  }
+}
@@ -17,6 +17,7 @@ use clap::error::ErrorKind;
 use clap::value_parser;
 use clap::Arg;
 use clap::ArgAction;
+use clap::ArgGroup;
 use clap::ArgMatches;
 use clap::ColorChoice;
 use clap::Command;
@@ -100,10 +101,25 @@ pub struct RemoveFlags {
 pub struct BenchFlags {
   pub files: FileFlags,
   pub filter: Option<String>,
-  pub json: bool,
+  pub reporter: BenchReporterConfig,
+  /// Whether the JSON reporter includes every iteration sample.
+  pub raw_samples: bool,
   pub no_run: bool,
   pub permit_no_files: bool,
   pub watch: Option<WatchFlags>,
+  pub save_baseline: Option<String>,
+  pub baseline: Option<String>,
+  /// Percent by which a bench may get slower than the baseline.
+  pub fail_on_regression: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BenchReporterConfig {
+  #[default]
+  Pretty,
+  Json,
+  Markdown,
+  Csv,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -116,6 +132,9 @@ pub struct CheckFlags {
   pub files: Vec<String>,
   pub doc: bool,
   pub doc_only: bool,
+  /// Output the diagnostics in the SARIF format.
+  pub sarif: bool,
+  pub watch: Option<WatchFlags>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -150,6 +169,25 @@ pub enum CoverageType {
   Detailed,
   Lcov,
   Html,
+  Cobertura,
+  Istanbul,
+}
+
+/// Minimum coverage percentages, below which `deno coverage` fails.
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct CoverageThresholdFlags {
+  pub lines: Option<u8>,
+  pub branches: Option<u8>,
+  pub functions: Option<u8>,
+}
+
+/// The change that `deno coverage --diff` reports the coverage of.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CoverageDiff {
+  /// The changes since a git ref, including untracked files.
+  GitRef(String),
+  /// The path of a unified diff.
+  File(String),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
@@ -159,6 +197,8 @@ pub struct CoverageFlags {
   pub include: Vec<String>,
   pub exclude: Vec<String>,
   pub r#type: CoverageType,
+  pub thresholds: CoverageThresholdFlags,
+  pub diff: Option<CoverageDiff>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -199,6 +239,16 @@ pub struct EvalFlags {
   pub code: String,
 }
 
+/// How `deno fmt --check` reports the files that aren't formatted.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum FmtCheckOutput {
+  #[default]
+  Pretty,
+  /// A unified diff that `git apply` accepts.
+  Diff,
+  Json,
+}
+
 #[derive(Clone, Default, Debug, Eq, PartialEq)]
 pub struct FmtFlags {
   pub check: bool,
@@ -212,6 +262,10 @@ pub struct FmtFlags {
   pub watch: Option<WatchFlags>,
   pub unstable_component: bool,
   pub unstable_sql: bool,
+  pub unstable_toml: bool,
+  pub staged: bool,
+  pub changed_since: Option<String>,
+  pub output: FmtCheckOutput,
 }
 
 impl FmtFlags {
@@ -297,6 +351,11 @@ pub struct LintFlags {
   pub maybe_rules_exclude: Option<Vec<String>>,
   pub json: bool,
   pub compact: bool,
+  pub sarif: bool,
+  pub baseline: Option<String>,
+  pub update_baseline: bool,
+  pub staged: bool,
+  pub changed_since: Option<String>,
   pub watch: Option<WatchFlags>,
 }
 
@@ -390,6 +449,26 @@ pub enum TestReporterConfig {
   Dot,
   Junit,
   Tap,
+  Json,
+}
+
+/// Selects one of `count` deterministic buckets of test modules, as passed
+/// via `--shard=<index>/<count>`. The index is 1-based.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TestShardFlag {
+  pub index: NonZeroUsize,
+  pub count: NonZeroUsize,
+}
+
+/// Where each test module runs, as passed via `--isolate`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TestIsolation {
+  /// In a worker of this process.
+  #[default]
+  Worker,
+  /// In a child process, so that crashes and global state of native code
+  /// don't affect other modules.
+  Process,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -397,6 +476,7 @@ pub struct TestFlags {
   pub doc: bool,
   pub no_run: bool,
   pub coverage_dir: Option<String>,
+  pub coverage_per_test: bool,
   pub clean: bool,
   pub fail_fast: Option<NonZeroUsize>,
   pub files: FileFlags,
@@ -409,6 +489,16 @@ pub struct TestFlags {
   pub reporter: TestReporterConfig,
   pub junit_path: Option<String>,
   pub hide_stacktraces: bool,
+  pub shard: Option<TestShardFlag>,
+  pub shard_timings: Option<String>,
+  pub retry: usize,
+  pub update_snapshots: bool,
+  pub changed_since: Option<String>,
+  pub timeout: Option<u64>,
+  pub isolate: TestIsolation,
+  /// Set in the child processes of `--isolate=process` to the module they
+  /// run.
+  pub isolated_module: Option<String>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -1113,6 +1203,14 @@ impl Flags {
           ..
         }),
       ..
+    })
+    | DenoSubcommand::Check(CheckFlags {
+      watch:
+        Some(WatchFlags {
+          exclude: excluded_paths,
+          ..
+        }),
+      ..
     }) = &self.subcommand
     {
       let cwd = std::env::current_dir()?;
@@ -1726,8 +1824,22 @@ If you specify a directory instead of a file, the path is expanded to all contai
         Arg::new("json")
           .long("json")
           .action(ArgAction::SetTrue)
+          .conflicts_with("reporter")
           .help("UNSTABLE: Output benchmark result in JSON format"),
       )
+      .arg(
+        Arg::new("reporter")
+          .long("reporter")
+          .require_equals(true)
+          .value_parser(["pretty", "json", "markdown", "csv"])
+          .help("Select reporter to use. Default to 'pretty'"),
+      )
+      .arg(
+        Arg::new("raw-samples")
+          .long("raw-samples")
+          .action(ArgAction::SetTrue)
+          .help("Include every iteration sample in the JSON output"),
+      )
       .arg(
         Arg::new("ignore")
           .long("ignore")
@@ -1762,6 +1874,31 @@ If you specify a directory instead of a file, the path is expanded to all contai
           .help("Don't return an error code if no bench files were found")
           .action(ArgAction::SetTrue)
       )
+      .arg(
+        Arg::new("save-baseline")
+          .long("save-baseline")
+          .value_name("NAME")
+          .require_equals(true)
+          .help("Save the results as a baseline to compare later runs with")
+          .conflicts_with("no-run"),
+      )
+      .arg(
+        Arg::new("baseline")
+          .long("baseline")
+          .value_name("NAME")
+          .require_equals(true)
+          .help("Compare the results with a baseline saved with --save-baseline")
+          .conflicts_with("no-run"),
+      )
+      .arg(
+        Arg::new("fail-on-regression")
+          .long("fail-on-regression")
+          .value_name("PERCENT")
+          .require_equals(true)
+          .value_parser(parse_percent)
+          .requires("baseline")
+          .help("Exit with an error code if a bench is slower than the baseline by more than this percentage, e.g. 5%"),
+      )
       .arg(watch_arg(false))
       .arg(watch_exclude_arg())
       .arg(no_clear_screen_arg())
@@ -1771,6 +1908,17 @@ If you specify a directory instead of a file, the path is expanded to all contai
   })
 }
 
+fn parse_percent(s: &str) -> Result<u32, String> {
+  s.strip_suffix('%')
+    .unwrap_or(s)
+    .parse::<u32>()
+    .map_err(|_| {
+      format!(
+        "Invalid percentage '{s}': expected a whole number, for example '5%'"
+      )
+    })
+}
+
 fn bundle_subcommand() -> Command {
   command("bundle",  "`deno bundle` was removed in Deno 2.
 
@@ -1858,6 +2006,16 @@ Unless --reload is specified, this command will not re-download already cached d
             .action(ArgAction::SetTrue)
             .conflicts_with("doc")
         )
+        .arg(
+          Arg::new("sarif")
+            .long("sarif")
+            .help("Output diagnostics in the SARIF 2.1.0 format, for code scanning tools")
+            .action(ArgAction::SetTrue)
+            .conflicts_with("watch")
+        )
+        .arg(watch_arg(false))
+        .arg(watch_exclude_arg())
+        .arg(no_clear_screen_arg())
         .arg(
           Arg::new("file")
             .num_args(1..)
@@ -1992,9 +2150,18 @@ for an url to match it must match the include pattern and not match the exclude
 Write a report using the lcov format:
   <p(245)>deno coverage --lcov --output=cov.lcov cov_profile/</>
 
+Write a report using the Cobertura XML format:
+  <p(245)>deno coverage --cobertura --output=cobertura.xml cov_profile/</>
+
 Generate html reports from lcov:
   <p(245)>genhtml -o html_cov cov.lcov</>
 
+Fail when the line coverage is below 80%:
+  <p(245)>deno coverage --threshold-lines=80 cov_profile</>
+
+Only report the coverage of the lines changed since the main branch:
+  <p(245)>deno coverage --diff=main cov_profile</>
+
 <y>Read more:</> <c>https://docs.deno.com/go/coverage</>"),
     UnstableArgsConfig::None,
   )
@@ -2035,14 +2202,31 @@ Generate html reports from lcov:
           .help("Output coverage report in lcov format")
           .action(ArgAction::SetTrue),
       )
+      .arg(
+        Arg::new("cobertura")
+          .long("cobertura")
+          .help("Output coverage report in Cobertura XML format")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("istanbul")
+          .long("istanbul")
+          .requires("output")
+          .help(cstr!("Output coverage report in Istanbul JSON format
+  <p(245)>Writes coverage-final.json and coverage-summary.json to the directory given with --output.</>"))
+          .action(ArgAction::SetTrue),
+      )
+      .group(
+        ArgGroup::new("file-report").args(["lcov", "cobertura", "istanbul"]),
+      )
       .arg(
         Arg::new("output")
-          .requires("lcov")
+          .requires("file-report")
           .long("output")
           .value_parser(value_parser!(String))
           .help(
-            cstr!("Exports the coverage report in lcov format to the given file.
-  <p(245)>If no --output arg is specified then the report is written to stdout.</>",
+            cstr!("Exports the lcov or Cobertura coverage report to the given file, or the Istanbul reports to the given directory.
+  <p(245)>If no --output arg is specified then the lcov or Cobertura report is written to stdout.</>",
           ))
           .require_equals(true)
           .value_hint(ValueHint::FilePath),
@@ -2059,6 +2243,27 @@ Generate html reports from lcov:
           .help("Output coverage report in detailed format in the terminal")
           .action(ArgAction::SetTrue),
       )
+      .arg(coverage_threshold_arg("lines"))
+      .arg(coverage_threshold_arg("branches"))
+      .arg(coverage_threshold_arg("functions"))
+      .arg(
+        Arg::new("diff")
+          .long("diff")
+          .value_name("GIT_REF")
+          .require_equals(true)
+          .conflicts_with("diff-file")
+          .help(cstr!("Only report the coverage of the lines changed since the given git ref
+  <p(245)>Untracked files count as changed. Thresholds apply to the changed lines.</>")),
+      )
+      .arg(
+        Arg::new("diff-file")
+          .long("diff-file")
+          .value_name("PATH")
+          .require_equals(true)
+          .help(cstr!("Only report the coverage of the lines added or changed by the given unified diff
+  <p(245)>The paths in the diff are relative to the current directory. Thresholds apply to the changed lines.</>"))
+          .value_hint(ValueHint::FilePath),
+      )
       .arg(
         Arg::new("files")
           .num_args(0..)
@@ -2068,6 +2273,17 @@ Generate html reports from lcov:
   })
 }
 
+fn coverage_threshold_arg(kind: &'static str) -> Arg {
+  Arg::new(format!("threshold-{kind}"))
+    .long(format!("threshold-{kind}"))
+    .value_name("PERCENT")
+    .require_equals(true)
+    .value_parser(value_parser!(u8).range(0..=100))
+    .help(format!(
+      "Exit with an error if the {kind} coverage of all files is below the given percentage"
+    ))
+}
+
 fn doc_subcommand() -> Command {
   command("doc",
       cstr!("Show documentation for a module.
@@ -2246,7 +2462,7 @@ Supported file types are:
   <p(245)>JavaScript, TypeScript, Markdown, JSON(C) and Jupyter Notebooks</>
 
 Supported file types which are behind corresponding unstable flags (see formatting options):
-  <p(245)>HTML, CSS, SCSS, SASS, LESS, YAML, Svelte, Vue, Astro and Angular</>
+  <p(245)>HTML, CSS, SCSS, SASS, LESS, YAML, TOML, Svelte, Vue, Astro and Angular</>
 
 Format stdin and write to stdout:
   <p(245)>cat file.ts | deno fmt -</>
@@ -2274,6 +2490,17 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
           .num_args(0)
           .help_heading(FMT_HEADING),
       )
+      .arg(
+        Arg::new("output")
+          .long("output")
+          .require_equals(true)
+          .value_parser(["pretty", "diff", "json"])
+          .requires("check")
+          .help(cstr!("Select how --check reports the files that aren't formatted. Default to 'pretty'
+  <p(245)>'diff' prints a unified diff that git apply accepts, with paths relative to the git root,
+  and 'json' lists the changed lines</>"))
+          .help_heading(FMT_HEADING),
+      )
       .arg(
         Arg::new("ext")
           .long("ext")
@@ -2281,7 +2508,7 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
           .value_parser([
             "ts", "tsx", "js", "jsx", "md", "json", "jsonc", "css", "scss",
             "sass", "less", "html", "svelte", "vue", "astro", "yml", "yaml",
-            "ipynb", "sql"
+            "ipynb", "sql", "toml"
           ])
           .help_heading(FMT_HEADING).requires("files"),
       )
@@ -2295,6 +2522,25 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
           .value_hint(ValueHint::AnyPath)
           .help_heading(FMT_HEADING),
       )
+      .arg(
+        Arg::new("staged")
+          .long("staged")
+          .help(cstr!("Only format the files that are staged in git, formatting their staged contents and staging the result
+  <p(245)>Unstaged changes of partially staged files are kept.</>"))
+          .action(ArgAction::SetTrue)
+          .conflicts_with_all(["changed-since", "watch"])
+          .help_heading(FMT_HEADING),
+      )
+      .arg(
+        Arg::new("changed-since")
+          .long("changed-since")
+          .value_name("REF")
+          .require_equals(true)
+          .conflicts_with("watch")
+          .help(cstr!("Only format the files changed since the git REF, including untracked files
+  <p(245)>deno fmt --changed-since=main</>"))
+          .help_heading(FMT_HEADING),
+      )
       .arg(
         Arg::new("files")
           .num_args(1..)
@@ -2408,6 +2654,14 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
         .action(ArgAction::SetTrue)
         .help_heading(FMT_HEADING),
       )
+      .arg(
+        Arg::new("unstable-toml")
+        .long("unstable-toml")
+        .help("Enable formatting TOML files.")
+        .value_parser(FalseyValueParser::new())
+        .action(ArgAction::SetTrue)
+        .help_heading(FMT_HEADING),
+      )
   })
 }
 
@@ -2862,6 +3116,49 @@ To ignore linting on an entire file, you can add an ignore comment at the top of
           .conflicts_with("json")
           .help_heading(LINT_HEADING),
       )
+      .arg(
+        Arg::new("sarif")
+          .long("sarif")
+          .help("Output lint result in the SARIF 2.1.0 format, for code scanning tools")
+          .action(ArgAction::SetTrue)
+          .conflicts_with_all(["json", "compact"])
+          .help_heading(LINT_HEADING),
+      )
+      .arg(
+        Arg::new("baseline")
+          .long("baseline")
+          .value_name("FILE")
+          .help(cstr!("Only report diagnostics that are not recorded in the baseline file.
+  <p(245)>The baseline is created with the current diagnostics when the file doesn't exist.</>"))
+          .value_hint(ValueHint::FilePath)
+          .help_heading(LINT_HEADING),
+      )
+      .arg(
+        Arg::new("update-baseline")
+          .long("update-baseline")
+          .help("Remove the diagnostics that were fixed from the baseline file")
+          .action(ArgAction::SetTrue)
+          .requires("baseline")
+          .help_heading(LINT_HEADING),
+      )
+      .arg(
+        Arg::new("staged")
+          .long("staged")
+          .help("Only lint the files that are staged in git")
+          .action(ArgAction::SetTrue)
+          .conflicts_with_all(["changed-since", "watch"])
+          .help_heading(LINT_HEADING),
+      )
+      .arg(
+        Arg::new("changed-since")
+          .long("changed-since")
+          .value_name("REF")
+          .require_equals(true)
+          .conflicts_with("watch")
+          .help(cstr!("Only lint the files changed since the git REF, including untracked files
+  <p(245)>deno lint --changed-since=main</>"))
+          .help_heading(LINT_HEADING),
+      )
       .arg(
         Arg::new("files")
           .num_args(1..)
@@ -3153,6 +3450,14 @@ or <c>**/__tests__/**</>:
           .help("Collect coverage profile data into DIR. If DIR is not specified, it uses 'coverage/'")
           .help_heading(TEST_HEADING),
       )
+      .arg(
+        Arg::new("coverage-per-test")
+          .long("coverage-per-test")
+          .requires("coverage")
+          .help("Collect a coverage profile per test, so that coverage reports can show which tests covered each line")
+          .action(ArgAction::SetTrue)
+          .help_heading(TEST_HEADING),
+      )
       .arg(
         Arg::new("clean")
           .long("clean")
@@ -3191,7 +3496,7 @@ or <c>**/__tests__/**</>:
         Arg::new("reporter")
           .long("reporter")
           .help("Select reporter to use. Default to 'pretty'")
-          .value_parser(["pretty", "dot", "junit", "tap"])
+          .value_parser(["pretty", "dot", "junit", "tap", "json"])
           .help_heading(TEST_HEADING)
       )
       .arg(
@@ -3200,11 +3505,98 @@ or <c>**/__tests__/**</>:
           .help("Hide stack traces for errors in failure test results.")
           .action(ArgAction::SetTrue)
       )
+      .arg(
+        Arg::new("shard")
+          .long("shard")
+          .value_name("INDEX/COUNT")
+          .require_equals(true)
+          .value_parser(parse_test_shard)
+          .conflicts_with("watch")
+          .help(cstr!("Only run the INDEX-th of COUNT deterministic partitions of the test modules
+  <p(245)>deno test --shard=2/4</>"))
+          .help_heading(TEST_HEADING),
+      )
+      .arg(
+        Arg::new("retry")
+          .long("retry")
+          .value_name("N")
+          .require_equals(true)
+          .value_parser(value_parser!(usize))
+          .help("Re-run a failed test up to N times before reporting it as failed. Tests that pass after a retry are reported as flaky")
+          .help_heading(TEST_HEADING),
+      )
+      .arg(
+        Arg::new("timeout")
+          .long("timeout")
+          .value_name("MS")
+          .require_equals(true)
+          .value_parser(value_parser!(u64))
+          .help("Fail tests that run for longer than MS milliseconds. Tests can override this with the `timeout` option")
+          .help_heading(TEST_HEADING),
+      )
+      .arg(
+        Arg::new("isolate")
+          .long("isolate")
+          .value_name("MODE")
+          .require_equals(true)
+          .value_parser(["worker", "process"])
+          .conflicts_with("watch")
+          .help(cstr!("Run each test module in a worker of this process (default) or in its own child process,
+  so that a crash or leaked global state doesn't affect other modules
+  <p(245)>deno test --isolate=process</>"))
+          .help_heading(TEST_HEADING),
+      )
+      .arg(
+        Arg::new("isolated-module")
+          .long("isolated-module")
+          .require_equals(true)
+          .hide(true),
+      )
+      .arg(
+        Arg::new("update-snapshots")
+          .long("update-snapshots")
+          .help("Write the values of snapshot assertions to the snapshot files instead of comparing them, and remove obsolete snapshots")
+          .action(ArgAction::SetTrue)
+          .help_heading(TEST_HEADING),
+      )
+      .arg(
+        Arg::new("changed-since")
+          .long("changed-since")
+          .value_name("REF")
+          .require_equals(true)
+          .conflicts_with("watch")
+          .help(cstr!("Only run the test modules that depend on files or lockfile entries changed since the git REF
+  <p(245)>deno test --changed-since=main</>"))
+          .help_heading(TEST_HEADING),
+      )
+      .arg(
+        Arg::new("shard-timings")
+          .long("shard-timings")
+          .value_name("PATH")
+          .require_equals(true)
+          .value_hint(ValueHint::FilePath)
+          .help("Balance shards using per-file durations stored in PATH and update it with the durations of this run")
+          .help_heading(TEST_HEADING),
+      )
       .arg(env_file_arg())
       .arg(executable_ext_arg())
     )
 }
 
+fn parse_test_shard(s: &str) -> Result<TestShardFlag, String> {
+  let invalid =
+    || format!("Invalid shard '{s}': expected INDEX/COUNT, for example '1/3'");
+  let (index, count) = s.split_once('/').ok_or_else(invalid)?;
+  let index = index.parse::<NonZeroUsize>().map_err(|_| invalid())?;
+  let count = count.parse::<NonZeroUsize>().map_err(|_| invalid())?;
+  if index > count {
+    return Err(format!(
+      "Invalid shard '{s}': index {index} is greater than the shard count {count}"
+    ));
+  }
+  Ok(TestShardFlag { index, count })
+}
+
 fn parallel_arg(descr: &str) -> Arg {
   Arg::new("parallel")
     .long("parallel")
@@ -4501,7 +4893,24 @@ fn bench_parse(
   // interactive prompts, unless done by user code
   flags.permissions.no_prompt = true;
 
-  let json = matches.get_flag("json");
+  let reporter = if matches.get_flag("json") {
+    BenchReporterConfig::Json
+  } else {
+    match matches.remove_one::<String>("reporter").as_deref() {
+      Some("json") => BenchReporterConfig::Json,
+      Some("markdown") => BenchReporterConfig::Markdown,
+      Some("csv") => BenchReporterConfig::Csv,
+      Some("pretty") | None => BenchReporterConfig::Pretty,
+      _ => unreachable!(),
+    }
+  };
+  let raw_samples = matches.get_flag("raw-samples");
+  if raw_samples && reporter != BenchReporterConfig::Json {
+    return Err(clap::Error::raw(
+      clap::error::ErrorKind::ArgumentConflict,
+      "--raw-samples requires the JSON reporter, use it with --json or --reporter=json",
+    ));
+  }
   let permit_no_files = matches.get_flag("permit-no-files");
 
   let ignore = match matches.remove_many::<String>("ignore") {
@@ -4530,10 +4939,14 @@ fn bench_parse(
   flags.subcommand = DenoSubcommand::Bench(BenchFlags {
     files: FileFlags { include, ignore },
     filter,
-    json,
+    reporter,
+    raw_samples,
     no_run,
     permit_no_files,
     watch: watch_arg_parse(matches)?,
+    save_baseline: matches.remove_one::<String>("save-baseline"),
+    baseline: matches.remove_one::<String>("baseline"),
+    fail_on_regression: matches.remove_one::<u32>("fail-on-regression"),
   });
 
   Ok(())
@@ -4573,6 +4986,8 @@ fn check_parse(
     files,
     doc: matches.get_flag("doc"),
     doc_only: matches.get_flag("doc-only"),
+    sarif: matches.get_flag("sarif"),
+    watch: watch_arg_parse(matches)?,
   });
   flags.code_cache_enabled = !matches.get_flag("no-code-cache");
   allow_import_parse(flags, matches);
@@ -4671,6 +5086,10 @@ fn coverage_parse(
   };
   let r#type = if matches.get_flag("lcov") {
     CoverageType::Lcov
+  } else if matches.get_flag("cobertura") {
+    CoverageType::Cobertura
+  } else if matches.get_flag("istanbul") {
+    CoverageType::Istanbul
   } else if matches.get_flag("html") {
     CoverageType::Html
   } else if matches.get_flag("detailed") {
@@ -4679,6 +5098,18 @@ fn coverage_parse(
     CoverageType::Summary
   };
   let output = matches.remove_one::<String>("output");
+  let thresholds = CoverageThresholdFlags {
+    lines: matches.remove_one::<u8>("threshold-lines"),
+    branches: matches.remove_one::<u8>("threshold-branches"),
+    functions: matches.remove_one::<u8>("threshold-functions"),
+  };
+  let diff = if let Some(git_ref) = matches.remove_one::<String>("diff") {
+    Some(CoverageDiff::GitRef(git_ref))
+  } else {
+    matches
+      .remove_one::<String>("diff-file")
+      .map(CoverageDiff::File)
+  };
   flags.subcommand = DenoSubcommand::Coverage(CoverageFlags {
     files: FileFlags {
       include: files,
@@ -4688,6 +5119,8 @@ fn coverage_parse(
     include,
     exclude,
     r#type,
+    thresholds,
+    diff,
   });
   Ok(())
 }
@@ -4806,6 +5239,14 @@ fn fmt_parse(
   let no_semicolons = matches.remove_one::<bool>("no-semicolons");
   let unstable_component = matches.get_flag("unstable-component");
   let unstable_sql = matches.get_flag("unstable-sql");
+  let unstable_toml = matches.get_flag("unstable-toml");
+  let staged = matches.get_flag("staged");
+  let changed_since = matches.remove_one::<String>("changed-since");
+  let output = match matches.remove_one::<String>("output").as_deref() {
+    Some("diff") => FmtCheckOutput::Diff,
+    Some("json") => FmtCheckOutput::Json,
+    _ => FmtCheckOutput::Pretty,
+  };
 
   flags.subcommand = DenoSubcommand::Fmt(FmtFlags {
     check: matches.get_flag("check"),
@@ -4819,6 +5260,10 @@ fn fmt_parse(
     watch: watch_arg_parse(matches)?,
     unstable_component,
     unstable_sql,
+    unstable_toml,
+    staged,
+    changed_since,
+    output,
   });
   Ok(())
 }
@@ -5093,6 +5538,11 @@ fn lint_parse(
 
   let json = matches.get_flag("json");
   let compact = matches.get_flag("compact");
+  let sarif = matches.get_flag("sarif");
+  let baseline = matches.remove_one::<String>("baseline");
+  let update_baseline = matches.get_flag("update-baseline");
+  let staged = matches.get_flag("staged");
+  let changed_since = matches.remove_one::<String>("changed-since");
 
   flags.subcommand = DenoSubcommand::Lint(LintFlags {
     files: FileFlags {
@@ -5106,6 +5556,11 @@ fn lint_parse(
     maybe_rules_exclude,
     json,
     compact,
+    sarif,
+    baseline,
+    update_baseline,
+    staged,
+    changed_since,
     watch: watch_arg_parse(matches)?,
   });
   Ok(())
@@ -5381,22 +5836,41 @@ fn test_parse(
         "junit" => TestReporterConfig::Junit,
         "dot" => TestReporterConfig::Dot,
         "tap" => TestReporterConfig::Tap,
+        "json" => TestReporterConfig::Json,
         _ => unreachable!(),
       }
     } else {
       TestReporterConfig::Pretty
     };
 
-  if matches!(reporter, TestReporterConfig::Dot | TestReporterConfig::Tap) {
+  if matches!(
+    reporter,
+    TestReporterConfig::Dot
+      | TestReporterConfig::Tap
+      | TestReporterConfig::Json
+  ) {
     flags.log_level = Some(Level::Error);
   }
 
   let hide_stacktraces = matches.get_flag("hide-stacktraces");
+  let shard = matches.remove_one::<TestShardFlag>("shard");
+  let shard_timings = matches.remove_one::<String>("shard-timings");
+  let retry = matches.remove_one::<usize>("retry").unwrap_or(0);
+  let update_snapshots = matches.get_flag("update-snapshots");
+  let changed_since = matches.remove_one::<String>("changed-since");
+  let timeout = matches.remove_one::<u64>("timeout");
+  let isolate = match matches.remove_one::<String>("isolate").as_deref() {
+    Some("process") => TestIsolation::Process,
+    Some("worker") | None => TestIsolation::Worker,
+    _ => unreachable!(),
+  };
+  let isolated_module = matches.remove_one::<String>("isolated-module");
 
   flags.subcommand = DenoSubcommand::Test(TestFlags {
     no_run,
     doc,
     coverage_dir: matches.remove_one::<String>("coverage"),
+    coverage_per_test: matches.get_flag("coverage-per-test"),
     clean,
     fail_fast,
     files: FileFlags { include, ignore },
@@ -5409,6 +5883,14 @@ fn test_parse(
     reporter,
     junit_path,
     hide_stacktraces,
+    shard,
+    shard_timings,
+    retry,
+    update_snapshots,
+    changed_since,
+    timeout,
+    isolate,
+    isolated_module,
   });
   Ok(())
 }
@@ -6802,6 +7284,10 @@ mod tests {
           no_semicolons: None,
           unstable_component: false,
           unstable_sql: false,
+          unstable_toml: false,
+          staged: false,
+          changed_since: None,
+          output: FmtCheckOutput::Pretty,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -6826,6 +7312,10 @@ mod tests {
           no_semicolons: None,
           unstable_component: false,
           unstable_sql: false,
+          unstable_toml: false,
+          staged: false,
+          changed_since: None,
+          output: FmtCheckOutput::Pretty,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -6850,6 +7340,10 @@ mod tests {
           no_semicolons: None,
           unstable_component: false,
           unstable_sql: false,
+          unstable_toml: false,
+          staged: false,
+          changed_since: None,
+          output: FmtCheckOutput::Pretty,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -6874,6 +7368,10 @@ mod tests {
           no_semicolons: None,
           unstable_component: false,
           unstable_sql: false,
+          unstable_toml: false,
+          staged: false,
+          changed_since: None,
+          output: FmtCheckOutput::Pretty,
           watch: Some(Default::default()),
         }),
         ..Flags::default()
@@ -6889,7 +7387,8 @@ mod tests {
       "--unstable-html",
       "--unstable-component",
       "--unstable-yaml",
-      "--unstable-sql"
+      "--unstable-sql",
+      "--unstable-toml"
     ]);
     assert_eq!(
       r.unwrap(),
@@ -6908,6 +7407,10 @@ mod tests {
           no_semicolons: None,
           unstable_component: true,
           unstable_sql: true,
+          unstable_toml: true,
+          staged: false,
+          changed_since: None,
+          output: FmtCheckOutput::Pretty,
           watch: Some(WatchFlags {
             hmr: false,
             no_clear_screen: true,
@@ -6943,6 +7446,10 @@ mod tests {
           no_semicolons: None,
           unstable_component: false,
           unstable_sql: false,
+          unstable_toml: false,
+          staged: false,
+          changed_since: None,
+          output: FmtCheckOutput::Pretty,
           watch: Some(Default::default()),
         }),
         ..Flags::default()
@@ -6967,6 +7474,10 @@ mod tests {
           no_semicolons: None,
           unstable_component: false,
           unstable_sql: false,
+          unstable_toml: false,
+          staged: false,
+          changed_since: None,
+          output: FmtCheckOutput::Pretty,
           watch: Default::default(),
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
@@ -6999,6 +7510,10 @@ mod tests {
           no_semicolons: None,
           unstable_component: false,
           unstable_sql: false,
+          unstable_toml: false,
+          staged: false,
+          changed_since: None,
+          output: FmtCheckOutput::Pretty,
           watch: Some(Default::default()),
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
@@ -7036,6 +7551,10 @@ mod tests {
           no_semicolons: Some(true),
           unstable_component: false,
           unstable_sql: false,
+          unstable_toml: false,
+          staged: false,
+          changed_since: None,
+          output: FmtCheckOutput::Pretty,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -7067,6 +7586,10 @@ mod tests {
           no_semicolons: Some(false),
           unstable_component: false,
           unstable_sql: false,
+          unstable_toml: false,
+          staged: false,
+          changed_since: None,
+          output: FmtCheckOutput::Pretty,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -7093,6 +7616,10 @@ mod tests {
           no_semicolons: None,
           unstable_component: false,
           unstable_sql: false,
+          unstable_toml: false,
+          staged: false,
+          changed_since: None,
+          output: FmtCheckOutput::Pretty,
           watch: Default::default(),
         }),
         ext: Some("html".to_string()),
@@ -7101,6 +7628,75 @@ mod tests {
     );
   }
 
+  #[test]
+  fn fmt_staged_and_changed_since() {
+    let r = flags_from_vec(svec!["deno", "fmt", "--staged"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          staged: true,
+          ..FmtFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "fmt", "--check", "--changed-since=main"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: true,
+          changed_since: Some("main".to_string()),
+          ..FmtFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "fmt", "--staged", "--changed-since=main"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "fmt", "--staged", "--watch"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn fmt_check_output() {
+    let r = flags_from_vec(svec!["deno", "fmt", "--check", "--output=diff"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: true,
+          output: FmtCheckOutput::Diff,
+          ..FmtFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "fmt", "--check", "--output=json"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: true,
+          output: FmtCheckOutput::Json,
+          ..FmtFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "fmt", "--output=diff"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "fmt", "--check", "--output=xml"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn lint() {
     let r = flags_from_vec(svec!["deno", "lint", "script_1.ts", "script_2.ts"]);
@@ -7119,6 +7715,11 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          baseline: None,
+          update_baseline: false,
+          staged: false,
+          changed_since: None,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -7148,6 +7749,11 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          baseline: None,
+          update_baseline: false,
+          staged: false,
+          changed_since: None,
           watch: Some(Default::default()),
         }),
         permissions: PermissionFlags {
@@ -7181,6 +7787,11 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          baseline: None,
+          update_baseline: false,
+          staged: false,
+          changed_since: None,
           watch: Some(WatchFlags {
             hmr: false,
             no_clear_screen: true,
@@ -7212,6 +7823,11 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          baseline: None,
+          update_baseline: false,
+          staged: false,
+          changed_since: None,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -7234,6 +7850,11 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          baseline: None,
+          update_baseline: false,
+          staged: false,
+          changed_since: None,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -7261,6 +7882,11 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          baseline: None,
+          update_baseline: false,
+          staged: false,
+          changed_since: None,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -7289,6 +7915,11 @@ mod tests {
           maybe_rules_exclude: Some(svec!["no-const-assign"]),
           json: false,
           compact: false,
+          sarif: false,
+          baseline: None,
+          update_baseline: false,
+          staged: false,
+          changed_since: None,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -7311,6 +7942,11 @@ mod tests {
           maybe_rules_exclude: None,
           json: true,
           compact: false,
+          sarif: false,
+          baseline: None,
+          update_baseline: false,
+          staged: false,
+          changed_since: None,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -7340,6 +7976,11 @@ mod tests {
           maybe_rules_exclude: None,
           json: true,
           compact: false,
+          sarif: false,
+          baseline: None,
+          update_baseline: false,
+          staged: false,
+          changed_since: None,
           watch: Default::default(),
         }),
         config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
@@ -7370,29 +8011,133 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: true,
+          sarif: false,
+          baseline: None,
+          update_baseline: false,
+          staged: false,
+          changed_since: None,
           watch: Default::default(),
         }),
         config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn types() {
-    let r = flags_from_vec(svec!["deno", "types"]);
+    let r = flags_from_vec(svec!["deno", "lint", "--sarif", "script_1.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Types,
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec!["script_1.ts".to_string()],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          sarif: true,
+          baseline: None,
+          update_baseline: false,
+          staged: false,
+          changed_since: None,
+          watch: Default::default(),
+        }),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "lint", "--sarif", "--json"]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn cache() {
-    let r = flags_from_vec(svec!["deno", "cache", "script.ts"]);
+  fn lint_baseline() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "lint",
+      "--baseline=lint-baseline.json",
+      "--update-baseline",
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          sarif: false,
+          baseline: Some("lint-baseline.json".to_string()),
+          update_baseline: true,
+          staged: false,
+          changed_since: None,
+          watch: Default::default(),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "lint", "--update-baseline"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn lint_staged_and_changed_since() {
+    let r = flags_from_vec(svec!["deno", "lint", "--staged"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          staged: true,
+          ..LintFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "lint", "--changed-since=HEAD~1"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          changed_since: Some("HEAD~1".to_string()),
+          ..LintFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "lint", "--staged", "--changed-since=main"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn types() {
+    let r = flags_from_vec(svec!["deno", "types"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Types,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn cache() {
+    let r = flags_from_vec(svec!["deno", "cache", "script.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
@@ -7414,6 +8159,8 @@ mod tests {
           files: svec!["script.ts"],
           doc: false,
           doc_only: false,
+          sarif: false,
+          watch: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         code_cache_enabled: true,
@@ -7429,6 +8176,8 @@ mod tests {
           files: svec!["script.ts"],
           doc: true,
           doc_only: false,
+          sarif: false,
+          watch: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         code_cache_enabled: true,
@@ -7444,6 +8193,8 @@ mod tests {
           files: svec!["markdown.md"],
           doc: false,
           doc_only: true,
+          sarif: false,
+          watch: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         code_cache_enabled: true,
@@ -7451,6 +8202,57 @@ mod tests {
       }
     );
 
+    let r = flags_from_vec(svec!["deno", "check", "--sarif", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Check(CheckFlags {
+          files: svec!["script.ts"],
+          doc: false,
+          doc_only: false,
+          sarif: true,
+          watch: None,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "check",
+      "--watch",
+      "--no-clear-screen",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Check(CheckFlags {
+          files: svec!["script.ts"],
+          doc: false,
+          doc_only: false,
+          sarif: false,
+          watch: Some(WatchFlags {
+            hmr: false,
+            no_clear_screen: true,
+            exclude: vec![],
+          }),
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "check", "--watch", "--sarif", "script.ts"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+
     // `--doc` and `--doc-only` are mutually exclusive
     let r = flags_from_vec(svec![
       "deno",
@@ -7473,6 +8275,8 @@ mod tests {
             files: svec!["script.ts"],
             doc: false,
             doc_only: false,
+            sarif: false,
+            watch: None,
           }),
           type_check_mode: TypeCheckMode::All,
           code_cache_enabled: true,
@@ -9288,11 +10092,20 @@ mod tests {
           concurrent_jobs: None,
           trace_leaks: true,
           coverage_dir: Some("cov".to_string()),
+          coverage_per_test: false,
           clean: true,
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
           hide_stacktraces: false,
+          shard: None,
+          shard_timings: None,
+          retry: 0,
+          update_snapshots: false,
+          changed_since: None,
+          timeout: None,
+          isolate: TestIsolation::Worker,
+          isolated_module: None,
         }),
         no_npm: true,
         no_remote: true,
@@ -9372,11 +10185,20 @@ mod tests {
           concurrent_jobs: None,
           trace_leaks: false,
           coverage_dir: None,
+          coverage_per_test: false,
           clean: false,
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
           hide_stacktraces: false,
+          shard: None,
+          shard_timings: None,
+          retry: 0,
+          update_snapshots: false,
+          changed_since: None,
+          timeout: None,
+          isolate: TestIsolation::Worker,
+          isolated_module: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
@@ -9415,11 +10237,20 @@ mod tests {
           concurrent_jobs: None,
           trace_leaks: false,
           coverage_dir: None,
+          coverage_per_test: false,
           clean: false,
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
           hide_stacktraces: false,
+          shard: None,
+          shard_timings: None,
+          retry: 0,
+          update_snapshots: false,
+          changed_since: None,
+          timeout: None,
+          isolate: TestIsolation::Worker,
+          isolated_module: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -9504,6 +10335,24 @@ mod tests {
       }
     );
 
+    let r = flags_from_vec(svec!["deno", "test", "--reporter=json"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          reporter: TestReporterConfig::Json,
+          ..Default::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        log_level: Some(Level::Error),
+        ..Flags::default()
+      }
+    );
+
     let r = flags_from_vec(svec![
       "deno",
       "test",
@@ -9552,11 +10401,20 @@ mod tests {
           concurrent_jobs: None,
           trace_leaks: false,
           coverage_dir: None,
+          coverage_per_test: false,
           clean: false,
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
           hide_stacktraces: false,
+          shard: None,
+          shard_timings: None,
+          retry: 0,
+          update_snapshots: false,
+          changed_since: None,
+          timeout: None,
+          isolate: TestIsolation::Worker,
+          isolated_module: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -9588,11 +10446,20 @@ mod tests {
           concurrent_jobs: None,
           trace_leaks: false,
           coverage_dir: None,
+          coverage_per_test: false,
           clean: false,
           watch: Some(Default::default()),
           reporter: Default::default(),
           junit_path: None,
           hide_stacktraces: false,
+          shard: None,
+          shard_timings: None,
+          retry: 0,
+          update_snapshots: false,
+          changed_since: None,
+          timeout: None,
+          isolate: TestIsolation::Worker,
+          isolated_module: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -9623,11 +10490,20 @@ mod tests {
           concurrent_jobs: None,
           trace_leaks: false,
           coverage_dir: None,
+          coverage_per_test: false,
           clean: false,
           watch: Some(Default::default()),
           reporter: Default::default(),
           junit_path: None,
           hide_stacktraces: false,
+          shard: None,
+          shard_timings: None,
+          retry: 0,
+          update_snapshots: false,
+          changed_since: None,
+          timeout: None,
+          isolate: TestIsolation::Worker,
+          isolated_module: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -9660,6 +10536,7 @@ mod tests {
           concurrent_jobs: None,
           trace_leaks: false,
           coverage_dir: None,
+          coverage_per_test: false,
           clean: false,
           watch: Some(WatchFlagsWithPaths {
             hmr: false,
@@ -9670,6 +10547,14 @@ mod tests {
           reporter: Default::default(),
           junit_path: None,
           hide_stacktraces: false,
+          shard: None,
+          shard_timings: None,
+          retry: 0,
+          update_snapshots: false,
+          changed_since: None,
+          timeout: None,
+          isolate: TestIsolation::Worker,
+          isolated_module: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
@@ -9866,6 +10751,35 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_coverage_per_test() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "test",
+      "--coverage=cov",
+      "--coverage-per-test"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          coverage_dir: Some("cov".to_string()),
+          coverage_per_test: true,
+          ..TestFlags::default()
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--coverage-per-test"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn test_hide_stacktraces() {
     let r = flags_from_vec(svec!["deno", "test", "--hide-stacktraces"]);
@@ -9886,6 +10800,157 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_shard() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "test",
+      "--shard=2/3",
+      "--shard-timings=timings.json"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          shard: Some(TestShardFlag {
+            index: NonZeroUsize::new(2).unwrap(),
+            count: NonZeroUsize::new(3).unwrap(),
+          }),
+          shard_timings: Some("timings.json".to_string()),
+          ..TestFlags::default()
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--shard=4/3"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "test", "--shard=0/3"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "test", "--shard=1"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "test", "--shard=1/2", "--watch"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn test_changed_since() {
+    let r = flags_from_vec(svec!["deno", "test", "--changed-since=main"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          changed_since: Some("main".to_string()),
+          ..TestFlags::default()
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "test", "--changed-since=main", "--watch"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn test_timeout() {
+    let r = flags_from_vec(svec!["deno", "test", "--timeout=5000"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          timeout: Some(5000),
+          ..TestFlags::default()
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--timeout=-1"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn test_isolate() {
+    let r = flags_from_vec(svec!["deno", "test", "--isolate=process"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          isolate: TestIsolation::Process,
+          ..TestFlags::default()
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--isolate=thread"]);
+    assert!(r.is_err());
+    let r =
+      flags_from_vec(svec!["deno", "test", "--isolate=process", "--watch"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn test_retry() {
+    let r = flags_from_vec(svec!["deno", "test", "--retry=3"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          retry: 3,
+          ..TestFlags::default()
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn test_update_snapshots() {
+    let r = flags_from_vec(svec!["deno", "test", "--update-snapshots"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          update_snapshots: true,
+          ..TestFlags::default()
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn upgrade_with_ca_file() {
     let r = flags_from_vec(svec!["deno", "upgrade", "--cert", "example.crt"]);
@@ -10388,10 +11453,122 @@ mod tests {
           exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
           r#type: CoverageType::Lcov,
           output: Some(String::from("foo.lcov")),
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn coverage_with_cobertura_and_istanbul() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--cobertura",
+      "--output=cobertura.xml",
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["coverage".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          r#type: CoverageType::Cobertura,
+          output: Some(String::from("cobertura.xml")),
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--istanbul",
+      "--output=istanbul",
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["coverage".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          r#type: CoverageType::Istanbul,
+          output: Some(String::from("istanbul")),
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "coverage", "--istanbul"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "coverage", "--lcov", "--cobertura"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "coverage", "--output=out.xml"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn coverage_with_diff() {
+    let r = flags_from_vec(svec!["deno", "coverage", "--diff=main"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["coverage".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          diff: Some(CoverageDiff::GitRef("main".to_string())),
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--diff-file=pr.diff",
+      "--html"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["coverage".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          r#type: CoverageType::Html,
+          diff: Some(CoverageDiff::File("pr.diff".to_string())),
+          ..CoverageFlags::default()
         }),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--diff=main",
+      "--diff-file=pr.diff"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -10414,6 +11591,40 @@ mod tests {
     );
   }
 
+  #[test]
+  fn coverage_with_thresholds() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--threshold-lines=80",
+      "--threshold-branches=70",
+      "--threshold-functions=75",
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["coverage".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          thresholds: CoverageThresholdFlags {
+            lines: Some(80),
+            branches: Some(70),
+            functions: Some(75),
+          },
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "coverage", "--threshold-lines=101"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn location_with_bad_scheme() {
     #[rustfmt::skip]
@@ -10825,7 +12036,8 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Bench(BenchFlags {
           filter: Some("- foo".to_string()),
-          json: true,
+          reporter: BenchReporterConfig::Json,
+          raw_samples: false,
           no_run: true,
           files: FileFlags {
             include: vec!["dir1/".to_string(), "dir2/".to_string()],
@@ -10833,6 +12045,9 @@ mod tests {
           },
           watch: Default::default(),
           permit_no_files: false,
+          save_baseline: None,
+          baseline: None,
+          fail_on_regression: None,
         }),
         no_npm: true,
         no_remote: true,
@@ -10857,14 +12072,18 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Bench(BenchFlags {
           filter: None,
-          json: false,
+          reporter: Default::default(),
+          raw_samples: false,
           no_run: false,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
           },
           watch: Some(Default::default()),
-          permit_no_files: false
+          permit_no_files: false,
+          save_baseline: None,
+          baseline: None,
+          fail_on_regression: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -10884,14 +12103,90 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Bench(BenchFlags {
           filter: None,
-          json: false,
+          reporter: Default::default(),
+          raw_samples: false,
           no_run: false,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
           },
           watch: None,
-          permit_no_files: true
+          permit_no_files: true,
+          save_baseline: None,
+          baseline: None,
+          fail_on_regression: None,
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn bench_reporter() {
+    for (args, reporter) in [
+      (svec!["deno", "bench"], BenchReporterConfig::Pretty),
+      (svec!["deno", "bench", "--json"], BenchReporterConfig::Json),
+      (
+        svec!["deno", "bench", "--reporter=json"],
+        BenchReporterConfig::Json,
+      ),
+      (
+        svec!["deno", "bench", "--reporter=markdown"],
+        BenchReporterConfig::Markdown,
+      ),
+      (
+        svec!["deno", "bench", "--reporter=csv"],
+        BenchReporterConfig::Csv,
+      ),
+    ] {
+      let r = flags_from_vec(args);
+      assert_eq!(
+        r.unwrap().subcommand,
+        DenoSubcommand::Bench(BenchFlags {
+          reporter,
+          ..Default::default()
+        })
+      );
+    }
+
+    let r = flags_from_vec(svec!["deno", "bench", "--json", "--raw-samples"]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Bench(BenchFlags {
+        reporter: BenchReporterConfig::Json,
+        raw_samples: true,
+        ..Default::default()
+      })
+    );
+
+    let r = flags_from_vec(svec!["deno", "bench", "--raw-samples"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "bench", "--json", "--reporter=csv"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn bench_baseline() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "bench",
+      "--save-baseline=next",
+      "--baseline=main",
+      "--fail-on-regression=5%"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Bench(BenchFlags {
+          save_baseline: Some("next".to_string()),
+          baseline: Some("main".to_string()),
+          fail_on_regression: Some(5),
+          ..Default::default()
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -10901,6 +12196,31 @@ mod tests {
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "bench",
+      "--baseline=main",
+      "--fail-on-regression=10"
+    ]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Bench(BenchFlags {
+        baseline: Some("main".to_string()),
+        fail_on_regression: Some(10),
+        ..Default::default()
+      })
+    );
+
+    let r = flags_from_vec(svec!["deno", "bench", "--fail-on-regression=5%"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "bench",
+      "--baseline=main",
+      "--fail-on-regression=5.5%"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -40,6 +40,13 @@ use deno_core::resolve_url_or_path;
 use deno_core::serde_json;
 use deno_core::url::Url;
 use deno_graph::GraphKind;
+pub use deno_json::CoverageConfig;
+pub use deno_json::CoverageThresholds;
+pub use deno_json::CoverageThresholdsConfig;
+pub use deno_json::ImportBoundariesConfig;
+pub use deno_json::ImportBoundaryConfig;
+pub use deno_json::LintRuleOptionsConfig;
+pub use deno_json::NoImportCyclesConfig;
 use deno_lib::args::has_flag_env_var;
 use deno_lib::args::npm_pkg_req_ref_to_binary_command;
 use deno_lib::args::CaData;
@@ -157,18 +164,26 @@ impl WorkspaceExternalImportMapLoader {
 
 pub struct WorkspaceBenchOptions {
   pub filter: Option<String>,
-  pub json: bool,
+  pub reporter: BenchReporterConfig,
+  pub raw_samples: bool,
   pub no_run: bool,
   pub permit_no_files: bool,
+  pub save_baseline: Option<String>,
+  pub baseline: Option<String>,
+  pub fail_on_regression: Option<u32>,
 }
 
 impl WorkspaceBenchOptions {
   pub fn resolve(bench_flags: &BenchFlags) -> Self {
     Self {
       filter: bench_flags.filter.clone(),
-      json: bench_flags.json,
+      reporter: bench_flags.reporter,
+      raw_samples: bench_flags.raw_samples,
       no_run: bench_flags.no_run,
       permit_no_files: bench_flags.permit_no_files,
+      save_baseline: bench_flags.save_baseline.clone(),
+      baseline: bench_flags.baseline.clone(),
+      fail_on_regression: bench_flags.fail_on_regression,
     }
   }
 }
@@ -191,6 +206,7 @@ impl BenchOptions {
 pub struct UnstableFmtOptions {
   pub component: bool,
   pub sql: bool,
+  pub toml: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -225,6 +241,7 @@ impl FmtOptions {
       unstable: UnstableFmtOptions {
         component: unstable.component || fmt_flags.unstable_component,
         sql: unstable.sql || fmt_flags.unstable_sql,
+        toml: unstable.toml || fmt_flags.unstable_toml,
       },
       files: fmt_config.files,
     }
@@ -281,6 +298,15 @@ pub struct WorkspaceTestOptions {
   pub reporter: TestReporterConfig,
   pub junit_path: Option<String>,
   pub hide_stacktraces: bool,
+  pub shard: Option<TestShardFlag>,
+  pub shard_timings: Option<String>,
+  pub retry: usize,
+  pub update_snapshots: bool,
+  pub changed_since: Option<String>,
+  pub timeout: Option<u64>,
+  pub coverage_per_test: bool,
+  pub isolate: TestIsolation,
+  pub isolated_module: Option<String>,
 }
 
 impl WorkspaceTestOptions {
@@ -299,6 +325,15 @@ impl WorkspaceTestOptions {
       reporter: test_flags.reporter,
       junit_path: test_flags.junit_path.clone(),
       hide_stacktraces: test_flags.hide_stacktraces,
+      shard: test_flags.shard,
+      shard_timings: test_flags.shard_timings.clone(),
+      retry: test_flags.retry,
+      update_snapshots: test_flags.update_snapshots,
+      changed_since: test_flags.changed_since.clone(),
+      timeout: test_flags.timeout,
+      coverage_per_test: test_flags.coverage_per_test,
+      isolate: test_flags.isolate,
+      isolated_module: test_flags.isolated_module.clone(),
     }
   }
 }
@@ -317,28 +352,93 @@ impl TestOptions {
   }
 }
 
+/// Coverage thresholds for all files, or for the files within `dir`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageThresholdTarget {
+  pub dir: Option<PathBuf>,
+  /// How the target is shown, `All files` or the directory as configured.
+  pub name: String,
+  pub thresholds: CoverageThresholds,
+}
+
+#[derive(Debug, Clone)]
+pub struct CoverageOptions {
+  pub thresholds: Vec<CoverageThresholdTarget>,
+}
+
+impl CoverageOptions {
+  /// Takes the config of every config file with its directory, the
+  /// workspace root first. The thresholds for all files of a later config
+  /// take precedence, and the flags over all of them. Directories are
+  /// relative to the config file they're in.
+  pub fn resolve(
+    coverage_configs: Vec<(CoverageConfig, PathBuf)>,
+    coverage_flags: &CoverageFlags,
+  ) -> Self {
+    let mut all_files = CoverageThresholds::default();
+    let mut dir_targets = Vec::new();
+    for (coverage_config, config_dir) in coverage_configs {
+      let CoverageThresholdsConfig {
+        all_files: config_all_files,
+        directories,
+      } = coverage_config.thresholds;
+      all_files = config_all_files.or(all_files);
+      for (dir, dir_thresholds) in directories {
+        dir_targets.push(CoverageThresholdTarget {
+          dir: Some(deno_path_util::normalize_path(config_dir.join(&dir))),
+          name: dir,
+          thresholds: dir_thresholds,
+        });
+      }
+    }
+    let flags = &coverage_flags.thresholds;
+    let all_files = CoverageThresholds {
+      lines: flags.lines,
+      branches: flags.branches,
+      functions: flags.functions,
+    }
+    .or(all_files);
+    let mut thresholds = Vec::with_capacity(dir_targets.len() + 1);
+    if !all_files.is_empty() {
+      thresholds.push(CoverageThresholdTarget {
+        dir: None,
+        name: "All files".to_string(),
+        thresholds: all_files,
+      });
+    }
+    thresholds.extend(dir_targets);
+    Self { thresholds }
+  }
+}
+
 #[derive(Clone, Copy, Default, Debug)]
 pub enum LintReporterKind {
   #[default]
   Pretty,
   Json,
   Compact,
+  Sarif,
 }
 
 #[derive(Clone, Debug)]
 pub struct WorkspaceLintOptions {
   pub reporter_kind: LintReporterKind,
+  pub baseline: Option<PathBuf>,
+  pub update_baseline: bool,
 }
 
 impl WorkspaceLintOptions {
   pub fn resolve(
     lint_config: &WorkspaceLintConfig,
     lint_flags: &LintFlags,
+    initial_cwd: &Path,
   ) -> Result<Self, AnyError> {
     let mut maybe_reporter_kind = if lint_flags.json {
       Some(LintReporterKind::Json)
     } else if lint_flags.compact {
       Some(LintReporterKind::Compact)
+    } else if lint_flags.sarif {
+      Some(LintReporterKind::Sarif)
     } else {
       None
     };
@@ -348,6 +448,7 @@ impl WorkspaceLintOptions {
       maybe_reporter_kind = match lint_config.report.as_deref() {
         Some("json") => Some(LintReporterKind::Json),
         Some("compact") => Some(LintReporterKind::Compact),
+        Some("sarif") => Some(LintReporterKind::Sarif),
         Some("pretty") => Some(LintReporterKind::Pretty),
         Some(_) => {
           bail!("Invalid lint report type in config file")
@@ -357,6 +458,11 @@ impl WorkspaceLintOptions {
     }
     Ok(Self {
       reporter_kind: maybe_reporter_kind.unwrap_or_default(),
+      baseline: lint_flags
+        .baseline
+        .as_ref()
+        .map(|path| initial_cwd.join(path)),
+      update_baseline: lint_flags.update_baseline,
     })
   }
 }
@@ -364,6 +470,7 @@ impl WorkspaceLintOptions {
 #[derive(Clone, Debug)]
 pub struct LintOptions {
   pub rules: LintRulesConfig,
+  pub rule_options: LintRuleOptionsConfig,
   pub files: FilePatterns,
   pub fix: bool,
   pub plugins: Vec<Url>,
@@ -379,6 +486,7 @@ impl LintOptions {
   pub fn new_with_base(base: PathBuf) -> Self {
     Self {
       rules: Default::default(),
+      rule_options: Default::default(),
       files: FilePatterns::new_with_base(base),
       fix: false,
       plugins: vec![],
@@ -389,7 +497,14 @@ impl LintOptions {
     dir_path: PathBuf,
     lint_config: LintConfig,
     lint_flags: &LintFlags,
+    maybe_config_file: Option<&ConfigFile>,
   ) -> Result<Self, AnyError> {
+    let rule_options = match maybe_config_file {
+      Some(config_file) => {
+        LintRuleOptionsConfig::from_config_file(config_file)?
+      }
+      None => Default::default(),
+    };
     let rules = resolve_lint_rules_options(
       lint_config.options.rules,
       lint_flags.maybe_rules_tags.clone(),
@@ -413,6 +528,7 @@ impl LintOptions {
     Ok(Self {
       files: lint_config.files,
       rules,
+      rule_options,
       fix: lint_flags.fix,
       plugins,
     })
@@ -759,6 +875,7 @@ impl CliOptions {
     UnstableFmtOptions {
       component: workspace.has_unstable("fmt-component"),
       sql: workspace.has_unstable("fmt-sql"),
+      toml: workspace.has_unstable("fmt-toml"),
     }
   }
 
@@ -767,7 +884,7 @@ impl CliOptions {
     lint_flags: &LintFlags,
   ) -> Result<WorkspaceLintOptions, AnyError> {
     let lint_config = self.workspace().to_lint_config()?;
-    WorkspaceLintOptions::resolve(&lint_config, lint_flags)
+    WorkspaceLintOptions::resolve(&lint_config, lint_flags, self.initial_cwd())
   }
 
   pub fn resolve_lint_options_for_members(
@@ -781,7 +898,12 @@ impl CliOptions {
       .resolve_lint_config_for_members(&cli_arg_patterns)?;
     let mut result = Vec::with_capacity(member_configs.len());
     for (ctx, config) in member_configs {
-      let options = LintOptions::resolve(ctx.dir_path(), config, lint_flags)?;
+      let options = LintOptions::resolve(
+        ctx.dir_path(),
+        config,
+        lint_flags,
+        ctx.maybe_deno_json().map(|c| c.as_ref()),
+      )?;
       result.push((ctx, options));
     }
     Ok(result)
@@ -835,6 +957,31 @@ impl CliOptions {
     Ok(result)
   }
 
+  pub fn resolve_coverage_options(
+    &self,
+    coverage_flags: &CoverageFlags,
+  ) -> Result<CoverageOptions, AnyError> {
+    // the root config first, so that the member's config takes precedence
+    let mut deno_jsons = Vec::with_capacity(2);
+    deno_jsons.extend(self.workspace().root_deno_json());
+    if let Some(member_deno_json) = self.start_dir.maybe_deno_json() {
+      if deno_jsons
+        .iter()
+        .all(|deno_json| deno_json.specifier != member_deno_json.specifier)
+      {
+        deno_jsons.push(member_deno_json);
+      }
+    }
+    let mut coverage_configs = Vec::with_capacity(deno_jsons.len());
+    for deno_json in deno_jsons {
+      let config_path = deno_path_util::url_to_file_path(&deno_json.specifier)?;
+      let config_dir = config_path.parent().unwrap().to_path_buf();
+      coverage_configs
+        .push((CoverageConfig::from_config_file(deno_json)?, config_dir));
+    }
+    Ok(CoverageOptions::resolve(coverage_configs, coverage_flags))
+  }
+
   /// Vector of user script CLI arguments.
   pub fn argv(&self) -> &Vec<String> {
     &self.flags.argv
@@ -1142,6 +1289,7 @@ impl CliOptions {
           "detect-cjs",
           "fmt-component",
           "fmt-sql",
+          "fmt-toml",
           "lazy-npm-caching",
         ])
         .collect();
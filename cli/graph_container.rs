@@ -87,6 +87,29 @@ impl MainModuleGraphContainer {
     Ok(())
   }
 
+  /// Loads the specifiers into a copy of the graph without type checking
+  /// them, so the graph can be inspected before deciding what to check.
+  ///
+  /// The copy isn't stored, because `check_specifiers` only type checks
+  /// when the stored graph has no roots yet.
+  pub async fn load_specifiers(
+    &self,
+    specifiers: &[ModuleSpecifier],
+  ) -> Result<Arc<ModuleGraph>, AnyError> {
+    let mut graph = (*self.graph()).clone();
+    self
+      .module_load_preparer
+      .load_graph(
+        &mut graph,
+        specifiers,
+        false,
+        self.root_permissions.clone(),
+        None,
+      )
+      .await?;
+    Ok(Arc::new(graph))
+  }
+
   /// Helper around prepare_module_load that loads and type checks
   /// the provided files.
   pub async fn load_and_type_check_files(
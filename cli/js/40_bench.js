@@ -14,9 +14,11 @@ const {
   op_bench_get_origin,
   op_dispatch_bench_event,
   op_bench_now,
+  op_bench_heap_used,
 } = core.ops;
 const {
   ArrayPrototypePush,
+  ArrayPrototypeSlice,
   Error,
   MathCeil,
   SymbolToStringTag,
@@ -199,6 +201,9 @@ function benchStats(
   min,
   max,
   all,
+  samples,
+  allocPerIter,
+  heapGrowth,
 ) {
   return {
     n,
@@ -211,6 +216,9 @@ function benchStats(
     avg: !highPrecision ? (avg / n) : MathCeil(avg / n),
     highPrecision,
     usedExplicitTimers,
+    samples,
+    allocPerIter,
+    heapGrowth,
   };
 }
 
@@ -271,6 +279,20 @@ async function benchMeasure(timeBudget, fn, async, context) {
 
   wavg /= c;
 
+  // Allocations are measured as the growth of the used heap over every batch
+  // of iterations. A garbage collection during a batch shrinks the heap and
+  // hides its allocations, so such batches are left out.
+  let allocatedBytes = 0;
+  let allocatedIterations = 0;
+  const trackAllocations = (heapBefore, iterations) => {
+    const allocated = op_bench_heap_used() - heapBefore;
+    if (allocated >= 0) {
+      allocatedBytes += allocated;
+      allocatedIterations += iterations;
+    }
+  };
+  const heapStart = op_bench_heap_used();
+
   // measure step
   if (wavg > lowPrecisionThresholdInNs) {
     let iterations = 10;
@@ -278,9 +300,11 @@ async function benchMeasure(timeBudget, fn, async, context) {
 
     if (!async) {
       while (budget > 0 || iterations-- > 0) {
+        const heapBefore = op_bench_heap_used();
         const t1 = benchNow();
         fn(context);
         const t2 = benchNow();
+        trackAllocations(heapBefore, 1);
         const totalTime = t2 - t1;
         let measuredTime = totalTime;
         if (currentBenchUserExplicitStart !== null) {
@@ -301,9 +325,11 @@ async function benchMeasure(timeBudget, fn, async, context) {
       }
     } else {
       while (budget > 0 || iterations-- > 0) {
+        const heapBefore = op_bench_heap_used();
         const t1 = benchNow();
         await fn(context);
         const t2 = benchNow();
+        trackAllocations(heapBefore, 1);
         const totalTime = t2 - t1;
         let measuredTime = totalTime;
         if (currentBenchUserExplicitStart !== null) {
@@ -331,11 +357,13 @@ async function benchMeasure(timeBudget, fn, async, context) {
 
     if (!async) {
       while (budget > 0 || iterations-- > 0) {
+        const heapBefore = op_bench_heap_used();
         const t1 = benchNow();
         for (let c = 0; c < lowPrecisionThresholdInNs; c++) {
           fn(context);
         }
         const iterationTime = (benchNow() - t1) / lowPrecisionThresholdInNs;
+        trackAllocations(heapBefore, lowPrecisionThresholdInNs);
 
         n++;
         avg += iterationTime;
@@ -346,6 +374,7 @@ async function benchMeasure(timeBudget, fn, async, context) {
       }
     } else {
       while (budget > 0 || iterations-- > 0) {
+        const heapBefore = op_bench_heap_used();
         const t1 = benchNow();
         for (let c = 0; c < lowPrecisionThresholdInNs; c++) {
           await fn(context);
@@ -353,6 +382,7 @@ async function benchMeasure(timeBudget, fn, async, context) {
           currentBenchUserExplicitEnd = null;
         }
         const iterationTime = (benchNow() - t1) / lowPrecisionThresholdInNs;
+        trackAllocations(heapBefore, lowPrecisionThresholdInNs);
 
         n++;
         avg += iterationTime;
@@ -364,6 +394,10 @@ async function benchMeasure(timeBudget, fn, async, context) {
     }
   }
 
+  const heapGrowth = op_bench_heap_used() - heapStart;
+
+  // keep the samples in the order they were measured
+  const samples = ArrayPrototypeSlice(all);
   all.sort(compareMeasurements);
   return benchStats(
     n,
@@ -373,6 +407,9 @@ async function benchMeasure(timeBudget, fn, async, context) {
     min,
     max,
     all,
+    samples,
+    allocatedIterations > 0 ? allocatedBytes / allocatedIterations : null,
+    heapGrowth,
   );
 }
 
@@ -383,6 +383,11 @@ function installPlugin(plugin) {
   if (typeof plugin.rules !== "object") {
     throw new Error("Linter plugin rules must be an object");
   }
+  if (
+    plugin.cacheable !== undefined && typeof plugin.cacheable !== "boolean"
+  ) {
+    throw new Error("Linter plugin cacheable must be a boolean");
+  }
   if (state.installedPlugins.has(plugin.name)) {
     throw new Error(`Linter plugin ${plugin.name} has already been registered`);
   }
@@ -392,6 +397,7 @@ function installPlugin(plugin) {
   return {
     name: plugin.name,
     ruleNames: Object.keys(plugin.rules),
+    cacheable: plugin.cacheable === true,
   };
 }
 
@@ -7,11 +7,15 @@ import { escapeName, withPermissions } from "ext:cli/40_test_common.js";
 const {
   op_register_test_step,
   op_register_test,
+  op_test_assert_snapshot,
   op_test_event_step_result_failed,
+  op_test_event_step_result_flaky,
   op_test_event_step_result_ignored,
   op_test_event_step_result_ok,
   op_test_event_step_wait,
   op_test_get_origin,
+  op_test_step_timeout_end,
+  op_test_step_timeout_start,
 } = core.ops;
 const {
   ArrayPrototypeFilter,
@@ -21,12 +25,15 @@ const {
   Map,
   MapPrototypeGet,
   MapPrototypeSet,
+  NumberIsInteger,
+  ObjectPrototypeIsPrototypeOf,
   SafeArrayIterator,
   SymbolToStringTag,
   TypeError,
 } = primordials;
 
 import { setExitHandler } from "ext:deno_os/30_os.js";
+import { inspect } from "ext:deno_console/01_console.js";
 
 // Capture `Deno` global so that users deleting or mangling it, won't
 // have impact on our sanitizers.
@@ -45,6 +52,8 @@ const DenoNs = globalThis.Deno;
  *   sanitizeResources: boolean,
  *   sanitizeExit: boolean,
  *   permissions: PermissionOptions,
+ *   retries: number | undefined,
+ *   timeout: number | undefined,
  * }} TestDescription
  *
  * @typedef {{
@@ -61,12 +70,16 @@ const DenoNs = globalThis.Deno;
  *   sanitizeOps: boolean,
  *   sanitizeResources: boolean,
  *   sanitizeExit: boolean,
+ *   retries: number,
+ *   timeout: number | undefined,
  * }} TestStepDescription
  *
  * @typedef {{
  *   context: TestContext,
  *   children: TestStepDescription[],
  *   completed: boolean,
+ *   pendingReports: (() => void)[] | null,
+ *   snapshotCount: number,
  * }} TestState
  *
  * @typedef {{
@@ -74,6 +87,8 @@ const DenoNs = globalThis.Deno;
  *   children: TestStepDescription[],
  *   completed: boolean,
  *   failed: boolean,
+ *   pendingReports: (() => void)[] | null,
+ *   snapshotCount: number,
  * }} TestStepState
  *
  * @typedef {{
@@ -91,6 +106,35 @@ const DenoNs = globalThis.Deno;
 /** @type {Map<number, TestState | TestStepState>} */
 const testStates = new Map();
 
+// Snapshots are stable across runs and platforms, so nothing is abbreviated
+// and object keys are sorted.
+const SNAPSHOT_INSPECT_OPTIONS = {
+  colors: false,
+  compact: false,
+  depth: Infinity,
+  iterableLimit: Infinity,
+  strAbbreviateSize: Infinity,
+  breakLength: Infinity,
+  sorted: true,
+  trailingComma: true,
+  escapeSequences: false,
+};
+
+// Thrown by `t.assertSnapshot()` and turned into a snapshot mismatch failure
+// by `wrapOuter()`, so that the runner can render a diff.
+class SnapshotMismatchError extends Error {
+  constructor(mismatch) {
+    super(
+      mismatch.expected == null
+        ? `Snapshot "${mismatch.name}" does not exist`
+        : `Snapshot "${mismatch.name}" does not match`,
+    );
+    this.name = "SnapshotMismatchError";
+    this.mismatch = mismatch;
+  }
+}
+const SnapshotMismatchErrorPrototype = SnapshotMismatchError.prototype;
+
 // Wrap test function in additional assertion that makes sure
 // that the test case does not accidentally exit prematurely.
 function assertExit(fn, isTest) {
@@ -125,22 +169,49 @@ function assertExit(fn, isTest) {
   };
 }
 
+// When `mayRetry` is set, a failure of this attempt will be retried, so the
+// step events it produces are held back until we know whether the attempt
+// passed. The events of a failed attempt are dropped.
 function wrapOuter(fn, desc) {
-  return async function outerWrapped() {
+  return async function outerWrapped(mayRetry = false) {
+    if (!("parent" in desc)) {
+      // A test that timed out was terminated before it could remove its
+      // exit handler.
+      setExitHandler(null);
+    }
+    const state = MapPrototypeGet(testStates, desc.id);
+    // Reset whatever a previous attempt left behind.
+    state.children = [];
+    state.completed = false;
+    state.pendingReports = mayRetry ? [] : null;
+    state.snapshotCount = 0;
+    let result;
     try {
       if (desc.ignore) {
-        return "ignored";
+        result = "ignored";
+      } else {
+        result = await fn(desc) ?? "ok";
       }
-      return await fn(desc) ?? "ok";
     } catch (error) {
-      return { failed: { jsError: core.destructureError(error) } };
+      if (ObjectPrototypeIsPrototypeOf(SnapshotMismatchErrorPrototype, error)) {
+        result = { failed: { snapshotMismatch: error.mismatch } };
+      } else {
+        result = { failed: { jsError: core.destructureError(error) } };
+      }
     } finally {
-      const state = MapPrototypeGet(testStates, desc.id);
       for (const childDesc of state.children) {
         stepReportResult(childDesc, { failed: "incomplete" }, 0);
       }
       state.completed = true;
     }
+    const pendingReports = state.pendingReports;
+    state.pendingReports = null;
+    if (pendingReports != null && !result.failed) {
+      for (const report of new SafeArrayIterator(pendingReports)) {
+        stepReport(desc, report);
+      }
+    }
+    return result;
   };
 }
 
@@ -301,6 +372,7 @@ function testInner(
   }
 
   testDesc = { ...testDesc, ...overrides };
+  validateTimeout(testDesc.timeout);
 
   // Delete this prop in case the user passed it. It's used to detect steps.
   delete testDesc.parent;
@@ -320,6 +392,8 @@ function testInner(
     testDesc.only,
     testDesc.sanitizeOps,
     testDesc.sanitizeResources,
+    testDesc.retries ?? null,
+    testDesc.timeout ?? null,
     testDesc.location.fileName,
     testDesc.location.lineNumber,
     testDesc.location.columnNumber,
@@ -331,6 +405,8 @@ function testInner(
     context: createTestContext(testDesc),
     children: [],
     completed: false,
+    pendingReports: null,
+    snapshotCount: 0,
   });
 }
 
@@ -362,22 +438,57 @@ function getFullName(desc) {
   return desc.name;
 }
 
+function validateTimeout(timeout) {
+  if (
+    timeout != null &&
+    (typeof timeout !== "number" || !NumberIsInteger(timeout) || timeout < 0)
+  ) {
+    throw new TypeError(
+      `Invalid timeout: expected a non-negative integer of milliseconds, received ${timeout}`,
+    );
+  }
+}
+
 function usesSanitizer(desc) {
   return desc.sanitizeResources || desc.sanitizeOps || desc.sanitizeExit;
 }
 
+/**
+ * Sends a step event right away, or holds it back if an ancestor is running
+ * an attempt that may be retried.
+ * @param desc {TestDescription | TestStepDescription}
+ * @param report {() => void}
+ */
+function stepReport(desc, report) {
+  let ancestor = desc.parent;
+  while (ancestor != null) {
+    const pendingReports = MapPrototypeGet(testStates, ancestor.id)
+      .pendingReports;
+    if (pendingReports != null) {
+      ArrayPrototypePush(pendingReports, report);
+      return;
+    }
+    ancestor = ancestor.parent;
+  }
+  report();
+}
+
 function stepReportResult(desc, result, elapsed) {
   const state = MapPrototypeGet(testStates, desc.id);
   for (const childDesc of state.children) {
     stepReportResult(childDesc, { failed: "incomplete" }, 0);
   }
-  if (result === "ok") {
-    op_test_event_step_result_ok(desc.id, elapsed);
-  } else if (result === "ignored") {
-    op_test_event_step_result_ignored(desc.id, elapsed);
-  } else {
-    op_test_event_step_result_failed(desc.id, result.failed, elapsed);
-  }
+  stepReport(desc, () => {
+    if (result === "ok") {
+      op_test_event_step_result_ok(desc.id, elapsed);
+    } else if (result === "ignored") {
+      op_test_event_step_result_ignored(desc.id, elapsed);
+    } else if (result.flaky) {
+      op_test_event_step_result_flaky(desc.id, result.flaky, elapsed);
+    } else {
+      op_test_event_step_result_failed(desc.id, result.failed, elapsed);
+    }
+  });
 }
 
 /** @param desc {TestDescription | TestStepDescription} */
@@ -411,6 +522,22 @@ function createTestContext(desc) {
      * File Uri of the test code.
      */
     origin: desc.origin,
+    /**
+     * Compares the serialized `value` with the snapshot stored for this
+     * assertion in `__snapshots__/<file>.snap`.
+     * @param value {unknown}
+     */
+    assertSnapshot(value) {
+      const state = MapPrototypeGet(testStates, desc.id);
+      const name = `${getFullName(desc)} ${++state.snapshotCount}`;
+      const mismatch = op_test_assert_snapshot(
+        name,
+        inspect(value, SNAPSHOT_INSPECT_OPTIONS),
+      );
+      if (mismatch != null) {
+        throw new SnapshotMismatchError(mismatch);
+      }
+    },
     /**
      * @param nameOrFnOrOptions {string | TestStepDefinition | ((t: TestContext) => void | Promise<void>)}
      * @param maybeFn {((t: TestContext) => void | Promise<void>) | undefined}
@@ -456,6 +583,8 @@ function createTestContext(desc) {
       stepDesc.sanitizeOps ??= desc.sanitizeOps;
       stepDesc.sanitizeResources ??= desc.sanitizeResources;
       stepDesc.sanitizeExit ??= desc.sanitizeExit;
+      stepDesc.retries ??= 0;
+      validateTimeout(stepDesc.timeout);
       stepDesc.location = core.currentUserCallSite();
       stepDesc.level = level + 1;
       stepDesc.parent = desc;
@@ -480,6 +609,8 @@ function createTestContext(desc) {
         children: [],
         failed: false,
         completed: false,
+        pendingReports: null,
+        snapshotCount: 0,
       };
       MapPrototypeSet(testStates, stepDesc.id, state);
       ArrayPrototypePush(
@@ -487,13 +618,37 @@ function createTestContext(desc) {
         stepDesc,
       );
 
-      op_test_event_step_wait(stepDesc.id);
+      stepReport(stepDesc, () => op_test_event_step_wait(stepDesc.id));
       const earlier = DateNow();
-      const result = await stepDesc.fn(stepDesc);
+      const failedAttempts = [];
+      let result;
+      while (true) {
+        const mayRetry = failedAttempts.length < stepDesc.retries;
+        // The timeout is enforced by the runner, so that a step stuck in
+        // synchronous code is terminated as well.
+        const timeoutId = stepDesc.timeout != null
+          ? op_test_step_timeout_start(
+            stepDesc.id,
+            getFullName(stepDesc),
+            stepDesc.timeout,
+          )
+          : null;
+        result = await stepDesc.fn(mayRetry);
+        if (timeoutId != null) {
+          op_test_step_timeout_end(timeoutId);
+        }
+        if (!result.failed || !mayRetry) {
+          break;
+        }
+        ArrayPrototypePush(failedAttempts, result.failed);
+      }
+      if (result == "ok" && failedAttempts.length > 0) {
+        result = { flaky: failedAttempts };
+      }
       const elapsed = DateNow() - earlier;
       state.failed = !!result.failed;
       stepReportResult(stepDesc, result, elapsed);
-      return result == "ok";
+      return result == "ok" || !!result.flaky;
     },
   };
 }
@@ -1630,6 +1630,7 @@ impl ConfigData {
       member_dir.dir_path(),
       (*lint_config).clone(),
       &LintFlags::default(),
+      member_dir.maybe_deno_json().map(|c| c.as_ref()),
     )
     .inspect_err(|err| lsp_warn!("  Failed to resolve linter options: {}", err))
     .ok()
@@ -1658,10 +1659,14 @@ impl ConfigData {
     }
 
     let linter = Arc::new(CliLinter::new(CliLinterOptions {
-      configured_rules: lint_rule_provider.resolve_lint_rules(
-        lint_options.rules,
-        member_dir.maybe_deno_json().map(|c| c.as_ref()),
-      ),
+      configured_rules: lint_rule_provider
+        .resolve_lint_rules(
+          lint_options.rules,
+          lint_options.rule_options,
+          member_dir.maybe_deno_json().map(|c| c.as_ref()),
+        )
+        .inspect_err(|err| lsp_warn!("  Failed to resolve lint rules: {}", err))
+        .unwrap_or_default(),
       fix: false,
       deno_lint_config,
       maybe_plugin_runner: plugin_runner,
@@ -1011,7 +1011,13 @@ fn generate_lint_diagnostics(
           Arc::new(CliLinter::new(CliLinterOptions {
             configured_rules: {
               let lint_rule_provider = LintRuleProvider::new(None);
-              lint_rule_provider.resolve_lint_rules(Default::default(), None)
+              lint_rule_provider
+                .resolve_lint_rules(
+                  Default::default(),
+                  Default::default(),
+                  None,
+                )
+                .unwrap()
             },
             fix: false,
             deno_lint_config: DenoLintConfig {
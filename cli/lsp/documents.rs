@@ -70,6 +70,7 @@ pub enum LanguageId {
   Less,
   Yaml,
   Sql,
+  Toml,
   Svelte,
   Vue,
   Astro,
@@ -95,6 +96,7 @@ impl LanguageId {
       LanguageId::Less => Some("less"),
       LanguageId::Yaml => Some("yaml"),
       LanguageId::Sql => Some("sql"),
+      LanguageId::Toml => Some("toml"),
       LanguageId::Svelte => Some("svelte"),
       LanguageId::Vue => Some("vue"),
       LanguageId::Astro => Some("astro"),
@@ -119,6 +121,7 @@ impl LanguageId {
       LanguageId::Less => None,
       LanguageId::Yaml => Some("application/yaml"),
       LanguageId::Sql => None,
+      LanguageId::Toml => None,
       LanguageId::Svelte => None,
       LanguageId::Vue => None,
       LanguageId::Astro => None,
@@ -155,6 +158,7 @@ impl FromStr for LanguageId {
       "less" => Ok(Self::Less),
       "yaml" => Ok(Self::Yaml),
       "sql" => Ok(Self::Sql),
+      "toml" => Ok(Self::Toml),
       "svelte" => Ok(Self::Svelte),
       "vue" => Ok(Self::Vue),
       "astro" => Ok(Self::Astro),
@@ -1484,6 +1484,9 @@ impl Inner {
         sql: config_data
           .map(|d| d.unstable.contains("fmt-sql"))
           .unwrap_or(false),
+        toml: config_data
+          .map(|d| d.unstable.contains("fmt-toml"))
+          .unwrap_or(false),
       };
       let document = document.clone();
       move || {
@@ -313,6 +313,10 @@ impl TestRun {
               filter,
               shuffle: None,
               trace_leaks: false,
+              retries: 0,
+              update_snapshots: false,
+              timeout: None,
+              coverage_per_test: false,
             },
           ))
         }
@@ -369,7 +373,9 @@ impl TestRun {
               if tests_with_result.insert(id) {
                 let description = tests.read().get(&id).unwrap().clone();
                 match &result {
-                  test::TestResult::Ok => summary.passed += 1,
+                  test::TestResult::Ok | test::TestResult::Flaky(_) => {
+                    summary.passed += 1
+                  }
                   test::TestResult::Ignored => summary.ignored += 1,
                   test::TestResult::Failed(error) => {
                     summary.failed += 1;
@@ -399,7 +405,7 @@ impl TestRun {
             test::TestEvent::StepResult(id, result, duration) => {
               if tests_with_result.insert(id) {
                 match &result {
-                  test::TestStepResult::Ok => {
+                  test::TestStepResult::Ok | test::TestStepResult::Flaky(_) => {
                     summary.passed_steps += 1;
                   }
                   test::TestStepResult::Ignored => {
@@ -416,6 +422,9 @@ impl TestRun {
                 );
               }
             }
+            test::TestEvent::Snapshots(snapshots) => {
+              summary.snapshots.push(snapshots);
+            }
             test::TestEvent::Completed => {
               reporter.report_completed();
             }
@@ -652,7 +661,7 @@ impl LspTestReporter {
   ) {
     self.current_test = None;
     match result {
-      test::TestResult::Ok => {
+      test::TestResult::Ok | test::TestResult::Flaky(_) => {
         let desc = self.tests.get(&desc.id).unwrap();
         self.progress(lsp_custom::TestRunProgressMessage::Passed {
           test: desc.as_test_identifier(&self.tests),
@@ -755,12 +764,11 @@ impl LspTestReporter {
     }
     let desc = self.tests.get(&desc.id).unwrap();
     match result {
-      test::TestStepResult::Ok => {
-        self.progress(lsp_custom::TestRunProgressMessage::Passed {
+      test::TestStepResult::Ok | test::TestStepResult::Flaky(_) => self
+        .progress(lsp_custom::TestRunProgressMessage::Passed {
           test: desc.as_test_identifier(&self.tests),
           duration: Some(elapsed as u32),
-        })
-      }
+        }),
       test::TestStepResult::Ignored => {
         self.progress(lsp_custom::TestRunProgressMessage::Skipped {
           test: desc.as_test_identifier(&self.tests),
@@ -275,7 +275,8 @@ async fn run_subcommand(flags: Arc<Flags>) -> Result<i32, AnyError> {
     DenoSubcommand::Test(test_flags) => {
       spawn_subcommand(async {
         if let Some(ref coverage_dir) = test_flags.coverage_dir {
-          if test_flags.clean {
+          // the parent of an isolated test process already cleaned it
+          if test_flags.clean && test_flags.isolated_module.is_none() {
             let _ = std::fs::remove_dir_all(coverage_dir);
           }
           std::fs::create_dir_all(coverage_dir)
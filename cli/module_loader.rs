@@ -157,6 +157,47 @@ impl ModuleLoadPreparer {
     ext_overwrite: Option<&String>,
   ) -> Result<(), PrepareModuleLoadError> {
     log::debug!("Preparing module load.");
+    let has_type_checked = !graph.roots.is_empty();
+
+    self
+      .load_graph(graph, roots, is_dynamic, permissions, ext_overwrite)
+      .await?;
+
+    // type check if necessary
+    if self.options.type_check_mode().is_true() && !has_type_checked {
+      self
+        .type_checker
+        .check(
+          // todo(perf): since this is only done the first time the graph is
+          // created, we could avoid the clone of the graph here by providing
+          // the actual graph on the first run and then getting the Arc<ModuleGraph>
+          // back from the return value.
+          graph.clone(),
+          check::CheckOptions {
+            build_fast_check_graph: true,
+            lib,
+            reload: self.options.reload_flag(),
+            type_check_mode: self.options.type_check_mode(),
+          },
+        )
+        .await?;
+    }
+
+    log::debug!("Prepared module load.");
+
+    Ok(())
+  }
+
+  /// Loads the roots and their dependencies into the graph without type
+  /// checking them.
+  pub async fn load_graph(
+    &self,
+    graph: &mut ModuleGraph,
+    roots: &[ModuleSpecifier],
+    is_dynamic: bool,
+    permissions: PermissionsContainer,
+    ext_overwrite: Option<&String>,
+  ) -> Result<(), PrepareModuleLoadError> {
     let _pb_clear_guard = self.progress_bar.clear_guard();
 
     let mut cache = self.module_graph_builder.create_fetch_cacher(permissions);
@@ -181,7 +222,6 @@ impl ModuleLoadPreparer {
       }
     }
     log::debug!("Building module graph.");
-    let has_type_checked = !graph.roots.is_empty();
 
     self
       .module_graph_builder
@@ -204,30 +244,6 @@ impl ModuleLoadPreparer {
       lockfile.write_if_changed()?;
     }
 
-    drop(_pb_clear_guard);
-
-    // type check if necessary
-    if self.options.type_check_mode().is_true() && !has_type_checked {
-      self
-        .type_checker
-        .check(
-          // todo(perf): since this is only done the first time the graph is
-          // created, we could avoid the clone of the graph here by providing
-          // the actual graph on the first run and then getting the Arc<ModuleGraph>
-          // back from the return value.
-          graph.clone(),
-          check::CheckOptions {
-            build_fast_check_graph: true,
-            lib,
-            reload: self.options.reload_flag(),
-            type_check_mode: self.options.type_check_mode(),
-          },
-        )
-        .await?;
-    }
-
-    log::debug!("Prepared module load.");
-
     Ok(())
   }
 
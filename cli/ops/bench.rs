@@ -30,6 +30,7 @@ deno_core::extension!(deno_bench,
     op_bench_get_origin,
     op_dispatch_bench_event,
     op_bench_now,
+    op_bench_heap_used,
   ],
   options = {
     sender: UnboundedSender<BenchEvent>,
@@ -150,3 +151,12 @@ fn op_bench_now(state: &mut OpState) -> Result<u64, std::num::TryFromIntError> {
   let ns_u64 = u64::try_from(ns)?;
   Ok(ns_u64)
 }
+
+/// The used size of the V8 heap in bytes, sampled around bench iterations to
+/// measure their allocations.
+#[op2(fast)]
+fn op_bench_heap_used(scope: &mut v8::HandleScope) -> f64 {
+  let mut stats = v8::HeapStatistics::default();
+  scope.get_heap_statistics(&mut stats);
+  stats.used_heap_size() as f64
+}
@@ -146,10 +146,16 @@ impl LintPluginContainer {
       })
       .collect::<Result<Vec<LintFixChange>, LintReportError>>()?;
 
-    let fixes = vec![LintFix {
-      changes,
-      description: format!("Fix this {} problem", id).into(),
-    }];
+    // a report without fix edits has no fix, so that it's not offered as an
+    // empty quick fix
+    let fixes = if changes.is_empty() {
+      vec![]
+    } else {
+      vec![LintFix {
+        changes,
+        description: format!("Fix this {} problem", id).into(),
+      }]
+    };
 
     let lint_diagnostic = LintDiagnostic {
       specifier,
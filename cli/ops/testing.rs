@@ -1,7 +1,9 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
+use std::collections::HashMap;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 use deno_core::op2;
 use deno_core::v8;
@@ -18,8 +20,12 @@ use crate::tools::test::TestEvent;
 use crate::tools::test::TestEventSender;
 use crate::tools::test::TestFailure;
 use crate::tools::test::TestLocation;
+use crate::tools::test::TestSnapshotMismatch;
+use crate::tools::test::TestSnapshots;
 use crate::tools::test::TestStepDescription;
 use crate::tools::test::TestStepResult;
+use crate::tools::test::TestTimedOut;
+use crate::tools::test::TestTimeouts;
 
 deno_core::extension!(deno_test,
   ops = [
@@ -32,6 +38,10 @@ deno_core::extension!(deno_test,
     op_test_event_step_result_ok,
     op_test_event_step_result_ignored,
     op_test_event_step_result_failed,
+    op_test_event_step_result_flaky,
+    op_test_assert_snapshot,
+    op_test_step_timeout_start,
+    op_test_step_timeout_end,
   ],
   options = {
     sender: TestEventSender,
@@ -39,9 +49,32 @@ deno_core::extension!(deno_test,
   state = |state, options| {
     state.put(options.sender);
     state.put(TestContainer::default());
+    state.put(PendingTestSteps::default());
+    state.put(RunningTestSteps::default());
   },
 );
 
+/// Steps that were registered, but whose `StepRegister` event is only sent
+/// once they start running. A step that runs inside an attempt that gets
+/// retried may never be reported at all.
+#[derive(Default)]
+struct PendingTestSteps(HashMap<usize, TestStepDescription>);
+
+/// Steps whose `StepRegister` and `StepWait` events were sent, but not their
+/// result yet. Only these can be reported when a test is terminated.
+#[derive(Default)]
+struct RunningTestSteps(Vec<usize>);
+
+impl RunningTestSteps {
+  /// Stops tracking the step, returning whether it was reported as running.
+  fn finish(state: &mut OpState, id: usize) -> bool {
+    let running = &mut state.borrow_mut::<Self>().0;
+    let len = running.len();
+    running.retain(|running_id| *running_id != id);
+    running.len() != len
+  }
+}
+
 #[derive(Clone)]
 struct PermissionsHolder(Uuid, PermissionsContainer);
 
@@ -97,6 +130,8 @@ fn op_register_test(
   only: bool,
   sanitize_ops: bool,
   sanitize_resources: bool,
+  #[serde] retries: Option<usize>,
+  #[serde] timeout: Option<u64>,
   #[string] file_name: String,
   #[smi] line_number: u32,
   #[smi] column_number: u32,
@@ -117,6 +152,8 @@ fn op_register_test(
     only,
     sanitize_ops,
     sanitize_resources,
+    retries,
+    timeout,
     origin: origin.clone(),
     location: TestLocation {
       file_name,
@@ -166,14 +203,24 @@ fn op_register_test_step(
     root_id,
     root_name,
   };
-  let sender = state.borrow_mut::<TestEventSender>();
-  sender.send(TestEvent::StepRegister(description)).ok();
+  state
+    .borrow_mut::<PendingTestSteps>()
+    .0
+    .insert(id, description);
   id
 }
 
 #[op2(fast)]
 fn op_test_event_step_wait(state: &mut OpState, #[smi] id: usize) {
+  // The description is gone when the attempt that registered the step was
+  // terminated, in which case the reporter never learns about the step.
+  let Some(description) = state.borrow_mut::<PendingTestSteps>().0.remove(&id)
+  else {
+    return;
+  };
+  state.borrow_mut::<RunningTestSteps>().0.push(id);
   let sender = state.borrow_mut::<TestEventSender>();
+  sender.send(TestEvent::StepRegister(description)).ok();
   sender.send(TestEvent::StepWait(id)).ok();
 }
 
@@ -183,6 +230,9 @@ fn op_test_event_step_result_ok(
   #[smi] id: usize,
   #[smi] duration: u64,
 ) {
+  if !RunningTestSteps::finish(state, id) {
+    return;
+  }
   let sender = state.borrow_mut::<TestEventSender>();
   sender
     .send(TestEvent::StepResult(id, TestStepResult::Ok, duration))
@@ -195,6 +245,13 @@ fn op_test_event_step_result_ignored(
   #[smi] id: usize,
   #[smi] duration: u64,
 ) {
+  if !RunningTestSteps::finish(state, id) {
+    return;
+  }
+  // The snapshots of an ignored step can't be told apart from obsolete ones.
+  if let Some(snapshots) = state.try_borrow_mut::<TestSnapshots>() {
+    snapshots.mark_incomplete();
+  }
   let sender = state.borrow_mut::<TestEventSender>();
   sender
     .send(TestEvent::StepResult(id, TestStepResult::Ignored, duration))
@@ -208,6 +265,9 @@ fn op_test_event_step_result_failed(
   #[serde] failure: TestFailure,
   #[smi] duration: u64,
 ) {
+  if !RunningTestSteps::finish(state, id) {
+    return;
+  }
   let sender = state.borrow_mut::<TestEventSender>();
   sender
     .send(TestEvent::StepResult(
@@ -217,3 +277,96 @@ fn op_test_event_step_result_failed(
     ))
     .ok();
 }
+
+#[op2]
+fn op_test_event_step_result_flaky(
+  state: &mut OpState,
+  #[smi] id: usize,
+  #[serde] failures: Vec<TestFailure>,
+  #[smi] duration: u64,
+) {
+  if !RunningTestSteps::finish(state, id) {
+    return;
+  }
+  let sender = state.borrow_mut::<TestEventSender>();
+  sender
+    .send(TestEvent::StepResult(
+      id,
+      TestStepResult::Flaky(failures),
+      duration,
+    ))
+    .ok();
+}
+
+/// Compares `actual` with the stored snapshot `name`, or stores it when
+/// running with `--update-snapshots`. Returns the mismatch, if any.
+#[op2]
+#[serde]
+fn op_test_assert_snapshot(
+  state: &mut OpState,
+  #[string] name: String,
+  #[string] actual: String,
+) -> Result<Option<TestSnapshotMismatch>, JsErrorBox> {
+  let Some(snapshots) = state.try_borrow_mut::<TestSnapshots>() else {
+    return Err(JsErrorBox::generic(
+      "Snapshots are only supported when running `deno test`.",
+    ));
+  };
+  snapshots.assert(name, actual)
+}
+
+/// Starts the timeout of a step attempt. Returns an id for
+/// `op_test_step_timeout_end`.
+#[op2(fast)]
+#[smi]
+fn op_test_step_timeout_start(
+  state: &mut OpState,
+  #[smi] id: usize,
+  #[string] name: String,
+  #[number] timeout: u64,
+) -> usize {
+  state
+    .borrow_mut::<TestTimeouts>()
+    .start(Duration::from_millis(timeout), Some((id, name)))
+}
+
+#[op2(fast)]
+fn op_test_step_timeout_end(state: &mut OpState, #[smi] timeout_id: usize) {
+  state.borrow::<TestTimeouts>().end(timeout_id);
+}
+
+/// Cleans up after a test that was terminated because it timed out, since
+/// the JS side never got to report the results of its running steps or to
+/// restore the permissions of the worker.
+///
+/// Returns whether the timeout was reported on the step it belonged to.
+pub fn abort_timed_out_test(
+  state: &mut OpState,
+  timed_out: &TestTimedOut,
+) -> bool {
+  if let Some(permissions_holder) = state.try_take::<PermissionsHolder>() {
+    state.put::<PermissionsContainer>(permissions_holder.1);
+  }
+  state.borrow_mut::<PendingTestSteps>().0.clear();
+  let running_steps =
+    std::mem::take(&mut state.borrow_mut::<RunningTestSteps>().0);
+  let sender = state.borrow_mut::<TestEventSender>();
+  let mut reported_on_step = false;
+  // Innermost steps first, like the JS side reports them.
+  for id in running_steps.into_iter().rev() {
+    let failure = if timed_out.step_id == Some(id) {
+      reported_on_step = true;
+      TestFailure::TimedOut(timed_out.clone())
+    } else {
+      TestFailure::Incomplete
+    };
+    sender
+      .send(TestEvent::StepResult(
+        id,
+        TestStepResult::Failed(failure),
+        0,
+      ))
+      .ok();
+  }
+  reported_on_step
+}
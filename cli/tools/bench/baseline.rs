@@ -0,0 +1,406 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::url::Url;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::BenchDescription;
+use super::BenchStats;
+use crate::args::WorkspaceBenchOptions;
+
+const BASELINE_VERSION: u8 = 1;
+
+/// Baselines are stored in this directory of the workspace root.
+const BASELINES_DIR: &str = ".bench-baselines";
+
+/// The z-score of a two-sided 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+/// The results of a bench run, saved with `--save-baseline=<name>`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Baseline {
+  version: u8,
+  benches: Vec<BaselineBench>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BaselineBench {
+  /// The bench module, relative to the workspace root when it's a file.
+  origin: String,
+  group: Option<String>,
+  name: String,
+  stats: BenchStats,
+  samples: SampleStats,
+}
+
+/// The mean and variance of the samples of a bench, which is all a
+/// comparison needs from them.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SampleStats {
+  pub n: usize,
+  pub mean: f64,
+  pub variance: f64,
+}
+
+impl SampleStats {
+  /// Falls back to the average of the stats when JS sent no samples.
+  fn from_stats(stats: &BenchStats) -> Self {
+    let n = stats.samples.len();
+    if n == 0 {
+      return Self {
+        n: stats.n as usize,
+        mean: stats.avg,
+        variance: 0.0,
+      };
+    }
+    let mean = stats.samples.iter().sum::<f64>() / n as f64;
+    let variance = if n > 1 {
+      stats
+        .samples
+        .iter()
+        .map(|sample| (sample - mean).powi(2))
+        .sum::<f64>()
+        / (n - 1) as f64
+    } else {
+      0.0
+    };
+    Self { n, mean, variance }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Verdict {
+  Faster,
+  Slower,
+  Unchanged,
+}
+
+/// How the mean time of a bench changed relative to the baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchComparison {
+  pub group: Option<String>,
+  pub name: String,
+  pub baseline_avg: f64,
+  pub current_avg: f64,
+  /// The change of the mean, in percent of the baseline.
+  pub change: f64,
+  /// Half the width of the 95% confidence interval of `change`.
+  pub margin: f64,
+}
+
+impl BenchComparison {
+  fn new(
+    group: Option<String>,
+    name: String,
+    baseline: &SampleStats,
+    current: &SampleStats,
+  ) -> Self {
+    // Welch's approximation of the standard error of the difference of two
+    // means with unequal variances
+    let standard_error = (baseline.variance / baseline.n.max(1) as f64
+      + current.variance / current.n.max(1) as f64)
+      .sqrt();
+    let (change, margin) = if baseline.mean > 0.0 {
+      (
+        (current.mean - baseline.mean) / baseline.mean * 100.0,
+        Z_95 * standard_error / baseline.mean * 100.0,
+      )
+    } else {
+      (0.0, 0.0)
+    };
+    Self {
+      group,
+      name,
+      baseline_avg: baseline.mean,
+      current_avg: current.mean,
+      change,
+      margin,
+    }
+  }
+
+  /// Whether the confidence interval of the change excludes zero.
+  pub fn verdict(&self) -> Verdict {
+    if self.change - self.margin > 0.0 {
+      Verdict::Slower
+    } else if self.change + self.margin < 0.0 {
+      Verdict::Faster
+    } else {
+      Verdict::Unchanged
+    }
+  }
+
+  /// Whether the bench got significantly slower by more than `threshold`
+  /// percent.
+  pub fn regressed(&self, threshold: f64) -> bool {
+    self.verdict() == Verdict::Slower && self.change > threshold
+  }
+}
+
+/// The baseline to compare a run with and to save it as.
+#[derive(Debug, Clone)]
+pub struct BaselineOptions {
+  root_dir: PathBuf,
+  compare: Option<(String, Arc<Baseline>)>,
+  save: Option<String>,
+  pub fail_on_regression: Option<f64>,
+}
+
+impl BaselineOptions {
+  /// Loads the baseline to compare with, so that a missing one is reported
+  /// before running any benches.
+  pub fn resolve(
+    options: &WorkspaceBenchOptions,
+    root_dir: PathBuf,
+  ) -> Result<Self, AnyError> {
+    let compare = match &options.baseline {
+      Some(name) => {
+        let path = baseline_path(&root_dir, name)?;
+        if !path.exists() {
+          bail!(
+            "Bench baseline \"{name}\" was not found at '{}'. Save it first with --save-baseline={name}",
+            path.display()
+          );
+        }
+        let text = std::fs::read_to_string(&path)
+          .with_context(|| format!("Failed to read '{}'", path.display()))?;
+        let baseline: Baseline =
+          serde_json::from_str(&text).with_context(|| {
+            format!("Invalid bench baseline '{}'", path.display())
+          })?;
+        if baseline.version != BASELINE_VERSION {
+          bail!(
+            "Bench baseline '{}' has unsupported version {}. Save it again with --save-baseline={name}",
+            path.display(),
+            baseline.version
+          );
+        }
+        Some((name.clone(), Arc::new(baseline)))
+      }
+      None => None,
+    };
+    if let Some(name) = &options.save_baseline {
+      baseline_path(&root_dir, name)?;
+    }
+    Ok(Self {
+      root_dir,
+      compare,
+      save: options.save_baseline.clone(),
+      fail_on_regression: options.fail_on_regression.map(f64::from),
+    })
+  }
+
+  pub fn compare_name(&self) -> Option<&str> {
+    self.compare.as_ref().map(|(name, _)| name.as_str())
+  }
+
+  /// Compares the measurements with the ones of the baseline with the same
+  /// module, group and name. Benches missing from the baseline are left out.
+  pub fn compare(
+    &self,
+    measurements: &[(BenchDescription, BenchStats)],
+  ) -> Option<Vec<BenchComparison>> {
+    let (_, baseline) = self.compare.as_ref()?;
+    let mut comparisons = Vec::new();
+    for (desc, stats) in measurements {
+      if desc.warmup {
+        continue;
+      }
+      let origin = relative_origin(&self.root_dir, &desc.origin);
+      let Some(bench) = baseline.benches.iter().find(|bench| {
+        bench.origin == origin
+          && bench.group == desc.group
+          && bench.name == desc.name
+      }) else {
+        continue;
+      };
+      comparisons.push(BenchComparison::new(
+        desc.group.clone(),
+        desc.name.clone(),
+        &bench.samples,
+        &SampleStats::from_stats(stats),
+      ));
+    }
+    Some(comparisons)
+  }
+
+  /// Saves the measurements as the baseline named with `--save-baseline`.
+  pub fn save(
+    &self,
+    measurements: &[(BenchDescription, BenchStats)],
+  ) -> Result<(), AnyError> {
+    let Some(name) = &self.save else {
+      return Ok(());
+    };
+    let benches = measurements
+      .iter()
+      .filter(|(desc, _)| !desc.warmup)
+      .map(|(desc, stats)| BaselineBench {
+        origin: relative_origin(&self.root_dir, &desc.origin),
+        group: desc.group.clone(),
+        name: desc.name.clone(),
+        stats: BenchStats {
+          samples: Vec::new(),
+          ..stats.clone()
+        },
+        samples: SampleStats::from_stats(stats),
+      })
+      .collect();
+    let baseline = Baseline {
+      version: BASELINE_VERSION,
+      benches,
+    };
+    let path = baseline_path(&self.root_dir, name)?;
+    if let Some(dir) = path.parent() {
+      std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create '{}'", dir.display()))?;
+    }
+    let json = serde_json::to_string_pretty(&baseline)?;
+    std::fs::write(&path, json)
+      .with_context(|| format!("Failed to write '{}'", path.display()))?;
+    log::info!(
+      "Saved bench baseline \"{name}\" to '{}'",
+      path.strip_prefix(&self.root_dir).unwrap_or(&path).display()
+    );
+    Ok(())
+  }
+}
+
+fn baseline_path(root_dir: &Path, name: &str) -> Result<PathBuf, AnyError> {
+  let is_valid = !name.is_empty()
+    && !name.starts_with('.')
+    && name
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+  if !is_valid {
+    bail!(
+      "Invalid bench baseline name \"{name}\". Use only letters, digits, '-', '_' and '.'"
+    );
+  }
+  Ok(root_dir.join(BASELINES_DIR).join(format!("{name}.json")))
+}
+
+/// Makes the origin of file modules relative to the workspace root, so that
+/// a baseline can be compared from another checkout.
+fn relative_origin(root_dir: &Path, origin: &str) -> String {
+  Url::parse(origin)
+    .ok()
+    .and_then(|url| url.to_file_path().ok())
+    .and_then(|path| {
+      path
+        .strip_prefix(root_dir)
+        .ok()
+        .map(|path| path.to_string_lossy().replace('\\', "/"))
+    })
+    .unwrap_or_else(|| origin.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn stats(samples: Vec<f64>) -> BenchStats {
+    BenchStats {
+      n: samples.len() as u64,
+      min: 0.0,
+      max: 0.0,
+      avg: 0.0,
+      p75: 0.0,
+      p99: 0.0,
+      p995: 0.0,
+      p999: 0.0,
+      high_precision: true,
+      used_explicit_timers: false,
+      alloc_per_iter: None,
+      heap_growth: 0.0,
+      samples,
+    }
+  }
+
+  #[test]
+  fn sample_stats() {
+    let sample_stats =
+      SampleStats::from_stats(&stats(vec![2.0, 4.0, 4.0, 4.0, 6.0]));
+    assert_eq!(
+      sample_stats,
+      SampleStats {
+        n: 5,
+        mean: 4.0,
+        variance: 2.0,
+      }
+    );
+  }
+
+  #[test]
+  fn compares_with_confidence_interval() {
+    let baseline = SampleStats {
+      n: 100,
+      mean: 100.0,
+      variance: 25.0,
+    };
+
+    // the standard error is sqrt(0.25 + 0.25), so the margin is ~1.39%
+    let slower = BenchComparison::new(
+      None,
+      "slower".to_string(),
+      &baseline,
+      &SampleStats {
+        n: 100,
+        mean: 110.0,
+        variance: 25.0,
+      },
+    );
+    assert_eq!(slower.change, 10.0);
+    assert!((slower.margin - 1.3859).abs() < 0.001);
+    assert_eq!(slower.verdict(), Verdict::Slower);
+    assert!(slower.regressed(5.0));
+    assert!(!slower.regressed(10.0));
+
+    let noisy = BenchComparison::new(
+      None,
+      "noisy".to_string(),
+      &baseline,
+      &SampleStats {
+        n: 4,
+        mean: 110.0,
+        variance: 400.0,
+      },
+    );
+    assert_eq!(noisy.verdict(), Verdict::Unchanged);
+    assert!(!noisy.regressed(5.0));
+
+    let faster = BenchComparison::new(
+      None,
+      "faster".to_string(),
+      &baseline,
+      &SampleStats {
+        n: 100,
+        mean: 90.0,
+        variance: 25.0,
+      },
+    );
+    assert_eq!(faster.verdict(), Verdict::Faster);
+    assert!(!faster.regressed(0.0));
+  }
+
+  #[test]
+  fn validates_baseline_names() {
+    let root = Path::new("/project");
+    assert_eq!(
+      baseline_path(root, "main").unwrap(),
+      root.join(".bench-baselines").join("main.json")
+    );
+    assert!(baseline_path(root, "v1.2_rc-1").is_ok());
+    assert!(baseline_path(root, "").is_err());
+    assert!(baseline_path(root, "../main").is_err());
+    assert!(baseline_path(root, ".hidden").is_err());
+  }
+}
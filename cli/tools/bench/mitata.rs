@@ -20,7 +20,7 @@ fn precision_f64(x: f64, decimals: u32) -> f64 {
   }
 }
 
-fn avg_to_iter_per_s(time: f64) -> String {
+pub fn avg_to_iter_per_s(time: f64) -> String {
   let iter_per_s = precision_f64(1e9 / time, 4);
   let (decimals, fractional) = into_decimal_and_fractional_parts(iter_per_s);
   human_readable_decimal_with_fractional(decimals, fractional)
@@ -88,6 +88,22 @@ pub fn fmt_duration(time: f64) -> String {
   format!("{:.1} h", time / 36e11)
 }
 
+pub fn fmt_bytes(bytes: f64) -> String {
+  let sign = if bytes < 0.0 { "-" } else { "" };
+  let bytes = bytes.abs();
+  if bytes < 1024.0 {
+    return format!("{sign}{:.0} B", bytes);
+  }
+  if bytes < 1024.0 * 1024.0 {
+    return format!("{sign}{:.1} KB", bytes / 1024.0);
+  }
+  if bytes < 1024.0 * 1024.0 * 1024.0 {
+    return format!("{sign}{:.1} MB", bytes / (1024.0 * 1024.0));
+  }
+
+  format!("{sign}{:.1} GB", bytes / (1024.0 * 1024.0 * 1024.0))
+}
+
 pub mod cpu {
   #![allow(dead_code)]
 
@@ -177,6 +193,8 @@ pub mod reporter {
     pub p75: f64,
     pub p99: f64,
     pub p995: f64,
+    pub alloc_per_iter: Option<f64>,
+    pub heap_growth: f64,
   }
 
   #[derive(Clone, PartialEq)]
@@ -193,6 +211,7 @@ pub mod reporter {
     pub avg: bool,
     pub min_max: bool,
     pub percentiles: bool,
+    pub memory: bool,
   }
 
   impl Options {
@@ -202,6 +221,7 @@ pub mod reporter {
         min_max: true,
         size: size(names),
         percentiles: true,
+        memory: true,
       }
     }
   }
@@ -235,6 +255,10 @@ pub mod reporter {
       s.push(' ');
       s.push_str(&"-".repeat(8 + 1 + 8 + 1 + 8));
     }
+    if options.memory {
+      s.push(' ');
+      s.push_str(&"-".repeat(10 + 1 + 11));
+    }
 
     s
   }
@@ -270,6 +294,9 @@ pub mod reporter {
     if options.percentiles {
       s.push_str(&format!(" {:>8} {:>8} {:>8}", "p75", "p99", "p995"));
     }
+    if options.memory {
+      s.push_str(&format!(" {:>10} {:>11}", "alloc/iter", "heap growth"));
+    }
 
     s
   }
@@ -309,6 +336,19 @@ pub mod reporter {
         .to_string(),
       );
     }
+    if options.memory {
+      s.push_str(
+        &colors::gray(format!(
+          " {:>10} {:>11}",
+          stats
+            .alloc_per_iter
+            .map(fmt_bytes)
+            .unwrap_or_else(|| "n/a".to_string()),
+          fmt_bytes(stats.heap_growth)
+        ))
+        .to_string(),
+      );
+    }
 
     s
   }
@@ -390,4 +430,14 @@ mod tests {
     assert_eq!(avg_to_iter_per_s(1_000_000_000.0), "1.0");
     assert_eq!(avg_to_iter_per_s(5_920_000_000.0), "0.2");
   }
+
+  #[test]
+  fn test_fmt_bytes() {
+    assert_eq!(fmt_bytes(0.0), "0 B");
+    assert_eq!(fmt_bytes(96.4), "96 B");
+    assert_eq!(fmt_bytes(1536.0), "1.5 KB");
+    assert_eq!(fmt_bytes(-2048.0), "-2.0 KB");
+    assert_eq!(fmt_bytes(5.0 * 1024.0 * 1024.0), "5.0 MB");
+    assert_eq!(fmt_bytes(3.0 * 1024.0 * 1024.0 * 1024.0), "3.0 GB");
+  }
 }
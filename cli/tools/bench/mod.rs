@@ -34,6 +34,7 @@ use tokio::sync::mpsc::unbounded_channel;
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::args::BenchFlags;
+use crate::args::BenchReporterConfig;
 use crate::args::Flags;
 use crate::colors;
 use crate::display::write_json_to_stdout;
@@ -50,18 +51,24 @@ use crate::util::path::matches_pattern_or_exact_path;
 use crate::worker::CliMainWorkerFactory;
 use crate::worker::CreateCustomWorkerError;
 
+mod baseline;
 mod mitata;
 mod reporters;
 
+use baseline::BaselineOptions;
 use reporters::BenchReporter;
 use reporters::ConsoleReporter;
+use reporters::CsvReporter;
 use reporters::JsonReporter;
+use reporters::MarkdownReporter;
 
 #[derive(Debug, Clone)]
 struct BenchSpecifierOptions {
   filter: TestFilter,
-  json: bool,
+  reporter: BenchReporterConfig,
+  raw_samples: bool,
   log_level: Option<log::Level>,
+  baseline: BaselineOptions,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
@@ -124,6 +131,16 @@ pub struct BenchStats {
   pub p999: f64,
   pub high_precision: bool,
   pub used_explicit_timers: bool,
+  /// The bytes allocated on the heap per iteration, `None` when a garbage
+  /// collection ran during every batch of iterations.
+  pub alloc_per_iter: Option<f64>,
+  /// How much the used heap grew over the measured iterations, in bytes.
+  #[serde(default)]
+  pub heap_growth: f64,
+  /// The measurements in the order they were taken. Only serialized when
+  /// the JSON reporter includes them.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub samples: Vec<f64>,
 }
 
 impl BenchReport {
@@ -139,12 +156,15 @@ impl BenchReport {
 
 fn create_reporter(
   show_output: bool,
-  json: bool,
+  reporter: BenchReporterConfig,
+  raw_samples: bool,
 ) -> Box<dyn BenchReporter + Send> {
-  if json {
-    return Box::new(JsonReporter::new());
+  match reporter {
+    BenchReporterConfig::Pretty => Box::new(ConsoleReporter::new(show_output)),
+    BenchReporterConfig::Json => Box::new(JsonReporter::new(raw_samples)),
+    BenchReporterConfig::Markdown => Box::new(MarkdownReporter::new()),
+    BenchReporterConfig::Csv => Box::new(CsvReporter::new()),
   }
-  Box::new(ConsoleReporter::new(show_output))
 }
 
 /// Run a single specifier as an executable bench module.
@@ -320,8 +340,11 @@ async fn bench_specifiers(
     spawn(async move {
       let mut used_only = false;
       let mut report = BenchReport::new();
-      let mut reporter =
-        create_reporter(log_level != Some(Level::Error), options.json);
+      let mut reporter = create_reporter(
+        log_level != Some(Level::Error),
+        options.reporter,
+        options.raw_samples,
+      );
       let mut benches = IndexMap::new();
 
       while let Some(event) = receiver.recv().await {
@@ -370,6 +393,24 @@ async fn bench_specifiers(
         }
       }
 
+      let baseline = &options.baseline;
+      let mut regressions = Vec::new();
+      if let Some(comparisons) = baseline.compare(&report.measurements) {
+        // reported before the end so that reporters can include it in
+        // their output
+        reporter.report_baseline_comparison(
+          baseline.compare_name().unwrap_or_default(),
+          &comparisons,
+        );
+        if let Some(threshold) = baseline.fail_on_regression {
+          regressions.extend(
+            comparisons
+              .into_iter()
+              .filter(|comparison| comparison.regressed(threshold)),
+          );
+        }
+      }
+
       reporter.report_end(&report);
 
       if used_only {
@@ -382,6 +423,28 @@ async fn bench_specifiers(
         return Err(anyhow!("Bench failed"));
       }
 
+      baseline.save(&report.measurements)?;
+
+      if !regressions.is_empty() {
+        return Err(anyhow!(
+          "Bench failed because {} regressed by more than {}% compared to the baseline:\n{}",
+          if regressions.len() == 1 {
+            "1 bench".to_string()
+          } else {
+            format!("{} benches", regressions.len())
+          },
+          baseline.fail_on_regression.unwrap_or_default(),
+          regressions
+            .iter()
+            .map(|comparison| format!(
+              "  {} ({:+.1}% ± {:.1}%)",
+              comparison.name, comparison.change, comparison.margin
+            ))
+            .collect::<Vec<_>>()
+            .join("\n")
+        ));
+      }
+
       Ok(())
     })
   };
@@ -412,7 +475,7 @@ fn is_supported_bench_path(entry: WalkEntry) -> bool {
   }
 }
 
-fn has_supported_bench_path_name(path: &Path) -> bool {
+pub(crate) fn has_supported_bench_path_name(path: &Path) -> bool {
   if let Some(name) = path.file_stem() {
     let basename = name.to_string_lossy();
     basename.ends_with("_bench")
@@ -431,6 +494,10 @@ pub async fn run_benchmarks(
   let cli_options = factory.cli_options()?;
   let workspace_bench_options =
     cli_options.resolve_workspace_bench_options(&bench_flags);
+  let baseline = BaselineOptions::resolve(
+    &workspace_bench_options,
+    cli_options.workspace().root_dir_path(),
+  )?;
   // Various bench files should not share the same permissions in terms of
   // `PermissionsContainer` - otherwise granting/revoking permissions in one
   // file would have impact on other files, which is undesirable.
@@ -479,8 +546,10 @@ pub async fn run_benchmarks(
     specifiers,
     BenchSpecifierOptions {
       filter: TestFilter::from_flag(&workspace_bench_options.filter),
-      json: workspace_bench_options.json,
+      reporter: workspace_bench_options.reporter,
+      raw_samples: workspace_bench_options.raw_samples,
       log_level,
+      baseline,
     },
   )
   .await?;
@@ -514,6 +583,10 @@ pub async fn run_benchmarks_with_watch(
         let cli_options = factory.cli_options()?;
         let workspace_bench_options =
           cli_options.resolve_workspace_bench_options(&bench_flags);
+        let baseline = BaselineOptions::resolve(
+          &workspace_bench_options,
+          cli_options.workspace().root_dir_path(),
+        )?;
 
         let _ = watcher_communicator.watch_paths(cli_options.watch_paths());
 
@@ -610,8 +683,10 @@ pub async fn run_benchmarks_with_watch(
           specifiers,
           BenchSpecifierOptions {
             filter: TestFilter::from_flag(&workspace_bench_options.filter),
-            json: workspace_bench_options.json,
+            reporter: workspace_bench_options.reporter,
+            raw_samples: workspace_bench_options.raw_samples,
             log_level,
+            baseline,
           },
         )
         .await?;
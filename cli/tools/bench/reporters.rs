@@ -3,6 +3,8 @@
 use deno_lib::version::DENO_VERSION_INFO;
 use serde::Serialize;
 
+use super::baseline::BenchComparison;
+use super::baseline::Verdict;
 use super::*;
 use crate::tools::test::TestFailureFormatOptions;
 
@@ -15,6 +17,12 @@ pub trait BenchReporter {
   fn report_output(&mut self, output: &str);
   fn report_result(&mut self, desc: &BenchDescription, result: &BenchResult);
   fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>);
+  /// Called before `report_end` when the run is compared with a baseline.
+  fn report_baseline_comparison(
+    &mut self,
+    name: &str,
+    comparisons: &[BenchComparison],
+  );
 }
 
 const JSON_SCHEMA_VERSION: u8 = 1;
@@ -25,6 +33,11 @@ struct JsonReporterOutput {
   runtime: String,
   cpu: String,
   benches: Vec<JsonReporterBench>,
+  #[serde(
+    rename = "baselineComparison",
+    skip_serializing_if = "Option::is_none"
+  )]
+  baseline_comparison: Option<JsonBaselineComparison>,
 }
 
 impl Default for JsonReporterOutput {
@@ -34,10 +47,29 @@ impl Default for JsonReporterOutput {
       runtime: format!("{} {}", DENO_VERSION_INFO.user_agent, env!("TARGET")),
       cpu: mitata::cpu::name(),
       benches: vec![],
+      baseline_comparison: None,
     }
   }
 }
 
+#[derive(Debug, Serialize)]
+struct JsonBaselineComparison {
+  name: String,
+  benches: Vec<JsonBenchComparison>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonBenchComparison {
+  group: Option<String>,
+  name: String,
+  baseline_avg: f64,
+  current_avg: f64,
+  change: f64,
+  margin: f64,
+  verdict: Verdict,
+}
+
 #[derive(Debug, Serialize)]
 struct JsonReporterBench {
   origin: String,
@@ -47,12 +79,18 @@ struct JsonReporterBench {
   results: Vec<BenchResult>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct JsonReporter(JsonReporterOutput);
+#[derive(Debug)]
+pub struct JsonReporter {
+  output: JsonReporterOutput,
+  raw_samples: bool,
+}
 
 impl JsonReporter {
-  pub fn new() -> Self {
-    Self(Default::default())
+  pub fn new(raw_samples: bool) -> Self {
+    Self {
+      output: Default::default(),
+      raw_samples,
+    }
   }
 }
 
@@ -63,7 +101,7 @@ impl BenchReporter for JsonReporter {
   fn report_plan(&mut self, _plan: &BenchPlan) {}
 
   fn report_end(&mut self, _report: &BenchReport) {
-    match write_json_to_stdout(self) {
+    match write_json_to_stdout(&self.output) {
       Ok(_) => (),
       Err(e) => println!("{}", e),
     }
@@ -80,7 +118,14 @@ impl BenchReporter for JsonReporter {
       return;
     }
 
-    let maybe_bench = self.0.benches.iter_mut().find(|bench| {
+    let mut result = result.clone();
+    if !self.raw_samples {
+      if let BenchResult::Ok(stats) = &mut result {
+        stats.samples = Vec::new();
+      }
+    }
+
+    let maybe_bench = self.output.benches.iter_mut().find(|bench| {
       bench.origin == desc.origin
         && bench.group == desc.group
         && bench.name == desc.name
@@ -88,19 +133,312 @@ impl BenchReporter for JsonReporter {
     });
 
     if let Some(bench) = maybe_bench {
-      bench.results.push(result.clone());
+      bench.results.push(result);
     } else {
-      self.0.benches.push(JsonReporterBench {
+      self.output.benches.push(JsonReporterBench {
         origin: desc.origin.clone(),
         group: desc.group.clone(),
         name: desc.name.clone(),
         baseline: desc.baseline,
-        results: vec![result.clone()],
+        results: vec![result],
       });
     }
   }
 
   fn report_uncaught_error(&mut self, _origin: &str, _error: Box<JsError>) {}
+
+  fn report_baseline_comparison(
+    &mut self,
+    name: &str,
+    comparisons: &[BenchComparison],
+  ) {
+    let benches = comparisons
+      .iter()
+      .map(|comparison| JsonBenchComparison {
+        group: comparison.group.clone(),
+        name: comparison.name.clone(),
+        baseline_avg: comparison.baseline_avg,
+        current_avg: comparison.current_avg,
+        change: comparison.change,
+        margin: comparison.margin,
+        verdict: comparison.verdict(),
+      })
+      .collect();
+    self.output.baseline_comparison = Some(JsonBaselineComparison {
+      name: name.to_string(),
+      benches,
+    });
+  }
+}
+
+/// Collects the results of the benches and prints them at the end, for the
+/// reporters that output a table.
+#[derive(Default)]
+struct CollectedResults {
+  benches: Vec<(BenchDescription, BenchResult)>,
+}
+
+impl CollectedResults {
+  fn push(&mut self, desc: &BenchDescription, result: &BenchResult) {
+    if desc.warmup {
+      return;
+    }
+    if let BenchResult::Failed(js_error) = result {
+      eprint_bench_failure(desc, js_error);
+    }
+    self.benches.push((desc.clone(), result.clone()));
+  }
+
+  /// The results grouped by the module they come from.
+  fn by_origin(&self) -> IndexMap<&str, Vec<&(BenchDescription, BenchResult)>> {
+    let mut by_origin = IndexMap::<_, Vec<_>>::new();
+    for bench in &self.benches {
+      by_origin
+        .entry(bench.0.origin.as_str())
+        .or_default()
+        .push(bench);
+    }
+    by_origin
+  }
+}
+
+// The reporters that output a table keep stdout for the table, so the errors
+// and the regressions go to stderr.
+
+#[allow(clippy::print_stderr)]
+fn eprint_bench_failure(desc: &BenchDescription, js_error: &JsError) {
+  eprintln!(
+    "{}: bench \"{}\" failed: {}",
+    colors::red_bold("error"),
+    desc.name,
+    format_test_error(js_error, &TestFailureFormatOptions::default())
+  );
+}
+
+#[allow(clippy::print_stderr)]
+fn eprint_uncaught_error(error: &JsError) {
+  eprintln!(
+    "{}: {}",
+    colors::red_bold("error"),
+    format_test_error(error, &TestFailureFormatOptions::default())
+  );
+  eprintln!("This error was not caught from a benchmark and caused the bench runner to fail on the referenced module.");
+  eprintln!("It most likely originated from a dangling promise, event/timeout handler or top-level code.");
+}
+
+/// Prints the benches that got slower than the baseline.
+#[allow(clippy::print_stderr)]
+fn eprint_baseline_regressions(name: &str, comparisons: &[BenchComparison]) {
+  let slower = comparisons
+    .iter()
+    .filter(|comparison| comparison.verdict() == Verdict::Slower);
+  for comparison in slower {
+    let bench_name = match &comparison.group {
+      Some(group) => format!("{group} > {}", comparison.name),
+      None => comparison.name.clone(),
+    };
+    eprintln!(
+      "{}: \"{}\" is {:+.1}% ± {:.1}% slower than baseline \"{}\"",
+      colors::yellow("warning"),
+      bench_name,
+      comparison.change,
+      comparison.margin,
+      name
+    );
+  }
+}
+
+/// Outputs a GitHub-flavored Markdown table for every bench module.
+#[derive(Default)]
+pub struct MarkdownReporter(CollectedResults);
+
+impl MarkdownReporter {
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+fn escape_markdown_cell(text: &str) -> String {
+  text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[allow(clippy::print_stdout)]
+impl BenchReporter for MarkdownReporter {
+  fn report_group_summary(&mut self) {}
+
+  fn report_plan(&mut self, _plan: &BenchPlan) {}
+
+  fn report_end(&mut self, _report: &BenchReport) {
+    println!("- CPU: {}", mitata::cpu::name());
+    println!(
+      "- Runtime: Deno {} ({})",
+      DENO_VERSION_INFO.deno,
+      env!("TARGET")
+    );
+    for (origin, benches) in self.0.by_origin() {
+      let has_groups = benches.iter().any(|(desc, _)| desc.group.is_some());
+      let mut header = vec!["benchmark"];
+      if has_groups {
+        header.push("group");
+      }
+      let columns = header.len();
+      header.extend([
+        "time/iter (avg)",
+        "iter/s",
+        "(min … max)",
+        "p75",
+        "p99",
+        "p995",
+        "alloc/iter",
+        "heap growth",
+      ]);
+      println!("\n### {}\n", escape_markdown_cell(origin));
+      println!("| {} |", header.join(" | "));
+      let alignments = header
+        .iter()
+        .enumerate()
+        .map(|(i, _)| if i < columns { "---" } else { "---:" })
+        .collect::<Vec<_>>();
+      println!("| {} |", alignments.join(" | "));
+      for (desc, result) in benches {
+        let mut cells = vec![escape_markdown_cell(&desc.name)];
+        if has_groups {
+          cells.push(escape_markdown_cell(
+            desc.group.as_deref().unwrap_or_default(),
+          ));
+        }
+        match result {
+          BenchResult::Ok(stats) => cells.extend([
+            mitata::fmt_duration(stats.avg),
+            mitata::avg_to_iter_per_s(stats.avg),
+            format!(
+              "{} … {}",
+              mitata::fmt_duration(stats.min),
+              mitata::fmt_duration(stats.max)
+            ),
+            mitata::fmt_duration(stats.p75),
+            mitata::fmt_duration(stats.p99),
+            mitata::fmt_duration(stats.p995),
+            stats
+              .alloc_per_iter
+              .map(mitata::fmt_bytes)
+              .unwrap_or_else(|| "n/a".to_string()),
+            mitata::fmt_bytes(stats.heap_growth),
+          ]),
+          BenchResult::Failed(_) => {
+            cells.push("failed".to_string());
+            cells.extend(std::iter::repeat_n(String::new(), 7));
+          }
+        }
+        println!("| {} |", cells.join(" | "));
+      }
+    }
+  }
+
+  fn report_register(&mut self, _desc: &BenchDescription) {}
+
+  fn report_wait(&mut self, _desc: &BenchDescription) {}
+
+  fn report_output(&mut self, _output: &str) {}
+
+  fn report_result(&mut self, desc: &BenchDescription, result: &BenchResult) {
+    self.0.push(desc, result);
+  }
+
+  fn report_uncaught_error(&mut self, _origin: &str, error: Box<JsError>) {
+    eprint_uncaught_error(&error);
+  }
+
+  fn report_baseline_comparison(
+    &mut self,
+    name: &str,
+    comparisons: &[BenchComparison],
+  ) {
+    eprint_baseline_regressions(name, comparisons);
+  }
+}
+
+/// Outputs a CSV row for every bench, with times in nanoseconds and memory in
+/// bytes. The stats of the benches that failed are empty.
+#[derive(Default)]
+pub struct CsvReporter(CollectedResults);
+
+impl CsvReporter {
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+fn escape_csv_field(field: &str) -> String {
+  if field.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", field.replace('"', "\"\""))
+  } else {
+    field.to_string()
+  }
+}
+
+#[allow(clippy::print_stdout)]
+impl BenchReporter for CsvReporter {
+  fn report_group_summary(&mut self) {}
+
+  fn report_plan(&mut self, _plan: &BenchPlan) {}
+
+  fn report_end(&mut self, _report: &BenchReport) {
+    println!("origin,group,name,baseline,status,n,avg,min,max,p75,p99,p995,p999,alloc_per_iter,heap_growth");
+    for (desc, result) in &self.0.benches {
+      let mut fields = vec![
+        escape_csv_field(&desc.origin),
+        escape_csv_field(desc.group.as_deref().unwrap_or_default()),
+        escape_csv_field(&desc.name),
+        desc.baseline.to_string(),
+      ];
+      let BenchResult::Ok(stats) = result else {
+        fields.push("failed".to_string());
+        fields.extend(std::iter::repeat_n(String::new(), 10));
+        println!("{}", fields.join(","));
+        continue;
+      };
+      fields.extend([
+        "ok".to_string(),
+        stats.n.to_string(),
+        stats.avg.to_string(),
+        stats.min.to_string(),
+        stats.max.to_string(),
+        stats.p75.to_string(),
+        stats.p99.to_string(),
+        stats.p995.to_string(),
+        stats.p999.to_string(),
+        stats
+          .alloc_per_iter
+          .map(|bytes| bytes.to_string())
+          .unwrap_or_default(),
+        stats.heap_growth.to_string(),
+      ]);
+      println!("{}", fields.join(","));
+    }
+  }
+
+  fn report_register(&mut self, _desc: &BenchDescription) {}
+
+  fn report_wait(&mut self, _desc: &BenchDescription) {}
+
+  fn report_output(&mut self, _output: &str) {}
+
+  fn report_result(&mut self, desc: &BenchDescription, result: &BenchResult) {
+    self.0.push(desc, result);
+  }
+
+  fn report_uncaught_error(&mut self, _origin: &str, error: Box<JsError>) {
+    eprint_uncaught_error(&error);
+  }
+
+  fn report_baseline_comparison(
+    &mut self,
+    name: &str,
+    comparisons: &[BenchComparison],
+  ) {
+    eprint_baseline_regressions(name, comparisons);
+  }
 }
 
 pub struct ConsoleReporter {
@@ -110,6 +448,8 @@ pub struct ConsoleReporter {
   baseline: bool,
   group_measurements: Vec<(BenchDescription, BenchStats)>,
   options: Option<mitata::reporter::Options>,
+  /// Printed at the end, after the summary of the last group.
+  baseline_comparison: Option<(String, Vec<BenchComparison>)>,
 }
 
 impl ConsoleReporter {
@@ -121,6 +461,7 @@ impl ConsoleReporter {
       baseline: false,
       name: String::new(),
       group_measurements: Vec::new(),
+      baseline_comparison: None,
     }
   }
 }
@@ -227,6 +568,8 @@ impl BenchReporter for ConsoleReporter {
               p75: stats.p75,
               p99: stats.p99,
               p995: stats.p995,
+              alloc_per_iter: stats.alloc_per_iter,
+              heap_growth: stats.heap_growth,
             },
             options
           )
@@ -284,6 +627,8 @@ impl BenchReporter for ConsoleReporter {
                 p75: s.p75,
                 p99: s.p99,
                 p995: s.p995,
+                alloc_per_iter: s.alloc_per_iter,
+                heap_growth: s.heap_growth,
               },
             })
             .collect::<Vec<mitata::reporter::GroupBenchmark>>(),
@@ -298,6 +643,9 @@ impl BenchReporter for ConsoleReporter {
 
   fn report_end(&mut self, _: &BenchReport) {
     self.report_group_summary();
+    if let Some((name, comparisons)) = self.baseline_comparison.take() {
+      print_baseline_comparison(&name, &comparisons);
+    }
   }
 
   fn report_uncaught_error(&mut self, _origin: &str, error: Box<JsError>) {
@@ -310,4 +658,63 @@ impl BenchReporter for ConsoleReporter {
     println!("It most likely originated from a dangling promise, event/timeout handler or top-level code.");
     println!();
   }
+
+  fn report_baseline_comparison(
+    &mut self,
+    name: &str,
+    comparisons: &[BenchComparison],
+  ) {
+    self.baseline_comparison = Some((name.to_string(), comparisons.to_vec()));
+  }
+}
+
+#[allow(clippy::print_stdout)]
+fn print_baseline_comparison(name: &str, comparisons: &[BenchComparison]) {
+  println!(
+    "{}",
+    colors::gray(format!("compared to baseline \"{name}\""))
+  );
+  if comparisons.is_empty() {
+    println!(
+      "{}\n",
+      colors::gray("no benchmarks of the baseline were run")
+    );
+    return;
+  }
+  let names = comparisons
+    .iter()
+    .map(|comparison| match &comparison.group {
+      Some(group) => format!("{group} > {}", comparison.name),
+      None => comparison.name.clone(),
+    })
+    .collect::<Vec<_>>();
+  let width = names
+    .iter()
+    .map(|name| name.chars().count())
+    .chain(["benchmark".len()])
+    .max()
+    .unwrap();
+  println!(
+    "\n{:<width$} {:>12} {:>12}   change (95% CI)",
+    "benchmark", "baseline", "current"
+  );
+  for (name, comparison) in names.iter().zip(comparisons) {
+    let change =
+      format!("{:+.1}% ± {:.1}%", comparison.change, comparison.margin);
+    let change = match comparison.verdict() {
+      Verdict::Slower => colors::red(format!("{change} slower")).to_string(),
+      Verdict::Faster => colors::green(format!("{change} faster")).to_string(),
+      Verdict::Unchanged => {
+        colors::gray(format!("{change} no change")).to_string()
+      }
+    };
+    println!(
+      "{:<width$} {:>12} {:>12}   {}",
+      name,
+      mitata::fmt_duration(comparison.baseline_avg),
+      mitata::fmt_duration(comparison.current_avg),
+      change
+    );
+  }
+  println!();
 }
@@ -1,8 +1,13 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -12,13 +17,23 @@ use deno_config::deno_json;
 use deno_config::deno_json::CompilerOptionTypesDeserializeError;
 use deno_config::workspace::WorkspaceDirectory;
 use deno_core::error::AnyError;
+use deno_core::futures::future;
+use deno_core::futures::FutureExt;
+use deno_core::parking_lot::Mutex;
 use deno_core::url::Url;
 use deno_error::JsErrorBox;
+use deno_graph::source::LoadFuture;
+use deno_graph::source::LoadOptions;
+use deno_graph::source::LoadResponse;
+use deno_graph::source::Loader;
+use deno_graph::CheckJsOption;
 use deno_graph::Module;
 use deno_graph::ModuleError;
 use deno_graph::ModuleGraph;
 use deno_graph::ModuleLoadError;
+use deno_graph::WalkOptions;
 use deno_lib::util::hash::FastInsecureHasher;
+use deno_path_util::url_to_file_path;
 use deno_semver::npm::NpmPackageNvReference;
 use deno_terminal::colors;
 use indexmap::IndexMap;
@@ -37,9 +52,12 @@ use crate::cache::CacheDBHash;
 use crate::cache::Caches;
 use crate::cache::TypeCheckCache;
 use crate::factory::CliFactory;
+use crate::graph_util::has_graph_root_local_dependent_changed;
 use crate::graph_util::maybe_additional_sloppy_imports_message;
 use crate::graph_util::BuildFastCheckGraphOptions;
 use crate::graph_util::ModuleGraphBuilder;
+use crate::graph_util::NpmCachingStrategy;
+use crate::module_loader::PrepareModuleLoadError;
 use crate::node::CliNodeResolver;
 use crate::npm::installer::NpmInstaller;
 use crate::npm::CliNpmResolver;
@@ -48,14 +66,93 @@ use crate::tsc;
 use crate::tsc::Diagnostics;
 use crate::tsc::TypeCheckingCjsTracker;
 use crate::util::extract;
+use crate::util::file_watcher;
+use crate::util::file_watcher::WatcherCommunicator;
+use crate::util::fs::canonicalize_path;
 use crate::util::path::to_percent_decoded_str;
+use crate::util::sarif;
 
 pub async fn check(
   flags: Arc<Flags>,
   check_flags: CheckFlags,
 ) -> Result<(), AnyError> {
+  if check_flags.watch.is_some() {
+    return check_with_watch(flags, check_flags).await;
+  }
+
   let factory = CliFactory::from_flags(flags);
+  let result = async {
+    let specifiers_for_typecheck =
+      collect_specifiers_for_typecheck(&factory, &check_flags).await?;
+    let main_graph_container = factory.main_module_graph_container().await?;
+    main_graph_container
+      .check_specifiers(&specifiers_for_typecheck, None)
+      .await
+  }
+  .await;
+  match result {
+    // the type checker only prints the log once the graph was created, so
+    // print one with the failed invocation for any other error
+    Err(err) if check_flags.sarif && !is_failed_type_checking(&err) => {
+      let initial_cwd = factory.cli_options()?.initial_cwd();
+      print_sarif_log(
+        new_sarif_run(initial_cwd),
+        vec![sarif::Notification {
+          level: sarif::Level::Error,
+          message: sarif::Message::new(err.to_string()),
+          locations: Vec::new(),
+        }],
+      );
+      Err(err)
+    }
+    result => result,
+  }
+}
+
+fn is_failed_type_checking(err: &AnyError) -> bool {
+  matches!(
+    err.downcast_ref::<PrepareModuleLoadError>(),
+    Some(PrepareModuleLoadError::Check(
+      CheckError::FailedTypeChecking(_)
+    ))
+  )
+}
 
+fn new_sarif_run(initial_cwd: &Path) -> sarif::Run {
+  sarif::Run::new(
+    "deno check",
+    "https://docs.deno.com/go/check",
+    sarif::ColumnKind::Utf16CodeUnits,
+    initial_cwd,
+  )
+}
+
+/// Prints the log of a run to stdout, where the invocation failed when
+/// there are notifications.
+fn print_sarif_log(
+  mut sarif_run: sarif::Run,
+  notifications: Vec<sarif::Notification>,
+) {
+  sarif_run.invocations.push(sarif::Invocation {
+    execution_successful: notifications.is_empty(),
+    tool_execution_notifications: notifications,
+  });
+  sarif_run.sort_results();
+  let json =
+    deno_core::serde_json::to_string_pretty(&sarif::SarifLog::new(&sarif_run))
+      .unwrap();
+  #[allow(clippy::print_stdout)]
+  {
+    println!("{json}");
+  }
+}
+
+/// Collects the files to check, adding the code blocks of their
+/// documentation with `--doc` or `--doc-only`.
+async fn collect_specifiers_for_typecheck(
+  factory: &CliFactory,
+  check_flags: &CheckFlags,
+) -> Result<Vec<ModuleSpecifier>, AnyError> {
   let main_graph_container = factory.main_module_graph_container().await?;
 
   let specifiers =
@@ -88,9 +185,438 @@ pub async fn check(
     specifiers
   };
 
-  main_graph_container
-    .check_specifiers(&specifiers_for_typecheck, None)
-    .await
+  Ok(specifiers_for_typecheck)
+}
+
+async fn check_with_watch(
+  flags: Arc<Flags>,
+  check_flags: CheckFlags,
+) -> Result<(), AnyError> {
+  let watch_flags = check_flags.watch.as_ref().unwrap();
+  let watch_diagnostics = Arc::new(Mutex::new(WatchDiagnostics::default()));
+  let watch_state = Rc::new(RefCell::new(None));
+
+  file_watcher::watch_func(
+    flags,
+    file_watcher::PrintConfig::new("Check", !watch_flags.no_clear_screen),
+    move |flags, watcher_communicator, changed_paths| {
+      let check_flags = check_flags.clone();
+      watcher_communicator.show_path_changed(changed_paths.clone());
+      Ok(check_with_watch_inner(
+        flags,
+        check_flags,
+        watcher_communicator,
+        changed_paths,
+        watch_diagnostics.clone(),
+        watch_state.clone(),
+      ))
+    },
+  )
+  .await
+}
+
+/// What `deno check --watch` keeps between the checks, so that only the
+/// changed modules are loaded again and the tsc isolates stay warm.
+struct CheckWatchState {
+  /// The flags change after the first check with `--reload`.
+  flags: Arc<Flags>,
+  factory: CliFactory,
+  config_paths: Vec<PathBuf>,
+  /// The graph before it's narrowed to the roots that are checked again, or
+  /// `None` when it couldn't be created.
+  graph: Option<ModuleGraph>,
+}
+
+async fn check_with_watch_inner(
+  flags: Arc<Flags>,
+  check_flags: CheckFlags,
+  watcher_communicator: Arc<WatcherCommunicator>,
+  changed_paths: Option<Vec<PathBuf>>,
+  watch_diagnostics: Arc<Mutex<WatchDiagnostics>>,
+  watch_state: Rc<RefCell<Option<CheckWatchState>>>,
+) -> Result<(), AnyError> {
+  let changed_paths =
+    changed_paths.map(|paths| paths.into_iter().collect::<HashSet<_>>());
+  let previous_state = watch_state.borrow_mut().take().filter(|state| {
+    Arc::ptr_eq(&state.flags, &flags)
+      && changed_paths
+        .as_ref()
+        .zip(state.graph.as_ref())
+        .is_some_and(|(changed_paths, graph)| {
+          has_only_modules_changed(graph, &state.config_paths, changed_paths)
+        })
+  });
+  let (factory, previous_graph) = match previous_state {
+    Some(state) => (state.factory, state.graph),
+    None => (
+      CliFactory::from_flags_for_watcher(
+        flags.clone(),
+        watcher_communicator.clone(),
+      ),
+      None,
+    ),
+  };
+
+  let mut graph = None;
+  let result = check_with_watch_factory(
+    &factory,
+    &check_flags,
+    &watcher_communicator,
+    changed_paths.as_ref(),
+    previous_graph.as_ref(),
+    &watch_diagnostics,
+    &mut graph,
+  )
+  .await;
+  // keep the factory when the check fails, unless its config is invalid
+  if let Ok(cli_options) = factory.cli_options() {
+    let config_paths = cli_options.watch_paths();
+    *watch_state.borrow_mut() = Some(CheckWatchState {
+      flags,
+      factory,
+      config_paths,
+      graph,
+    });
+  }
+  result
+}
+
+async fn check_with_watch_factory(
+  factory: &CliFactory,
+  check_flags: &CheckFlags,
+  watcher_communicator: &WatcherCommunicator,
+  changed_paths: Option<&HashSet<PathBuf>>,
+  previous_graph: Option<&ModuleGraph>,
+  watch_diagnostics: &Mutex<WatchDiagnostics>,
+  new_graph: &mut Option<ModuleGraph>,
+) -> Result<(), AnyError> {
+  let cli_options = factory.cli_options()?;
+  let config_paths = cli_options.watch_paths();
+  let _ = watcher_communicator.watch_paths(config_paths.clone());
+
+  let specifiers =
+    collect_specifiers_for_typecheck(factory, check_flags).await?;
+  let module_graph_creator = factory.module_graph_creator().await?;
+  let mut loader = factory.module_graph_builder().await?.create_graph_loader();
+  let graph_kind = cli_options.type_check_mode().as_graph_kind();
+  let parsed_source_cache = factory.parsed_source_cache();
+  let previous_graph = match previous_graph.zip(changed_paths) {
+    // the code blocks of the documentation can't be traced back to their
+    // files, so they are always loaded again
+    Some(_) if check_flags.doc || check_flags.doc_only => {
+      parsed_source_cache.free_all();
+      None
+    }
+    previous_graph => previous_graph,
+  };
+  let mut graph = match previous_graph {
+    Some((previous_graph, changed_paths)) => {
+      // the changed modules were parsed for the previous check
+      for module in previous_graph.modules() {
+        let specifier = module.specifier();
+        if specifier.scheme() == "file"
+          && !is_unchanged_local_module(specifier, changed_paths)
+        {
+          parsed_source_cache.free(specifier);
+        }
+      }
+      module_graph_creator
+        .create_graph_with_loader(
+          graph_kind,
+          specifiers,
+          &mut WatchGraphLoader {
+            inner_loader: &mut loader,
+            previous_graph,
+            changed_paths,
+          },
+          NpmCachingStrategy::Eager,
+        )
+        .await?
+    }
+    None => {
+      module_graph_creator
+        .create_graph_with_loader(
+          graph_kind,
+          specifiers,
+          &mut loader,
+          NpmCachingStrategy::Eager,
+        )
+        .await?
+    }
+  };
+  // an invalid graph is kept too, since it has the latest modules
+  *new_graph = Some(graph.clone());
+  module_graph_creator.graph_valid(&graph)?;
+  let graph_files = walk_graph_specifiers(&graph);
+
+  // only the roots that depend on a changed file are checked again, unless a
+  // config file changed or the code blocks of the documentation are checked,
+  // which can't be traced back to their files
+  if let Some(changed_paths) = changed_paths {
+    if !has_config_changed(&config_paths, changed_paths)
+      && !check_flags.doc
+      && !check_flags.doc_only
+    {
+      let affected_roots = graph
+        .roots
+        .iter()
+        .filter(|root| {
+          has_graph_root_local_dependent_changed(&graph, root, changed_paths)
+        })
+        .cloned()
+        .collect::<HashSet<_>>();
+      graph.roots.retain(|root| affected_roots.contains(root));
+    }
+  }
+  let checked_files = walk_graph_specifiers(&graph);
+
+  let type_checker = factory.type_checker().await?;
+  let mut diagnostics = Vec::new();
+  for result in type_checker
+    .check_diagnostics(
+      graph,
+      CheckOptions {
+        build_fast_check_graph: true,
+        lib: cli_options.ts_type_lib_window(),
+        reload: cli_options.reload_flag(),
+        type_check_mode: cli_options.type_check_mode(),
+      },
+    )
+    .await?
+  {
+    let mut folder_diagnostics = result?;
+    folder_diagnostics.emit_warnings();
+    diagnostics.extend(folder_diagnostics.into_vec());
+  }
+
+  let mut watch_diagnostics = watch_diagnostics.lock();
+  let delta =
+    watch_diagnostics.update(diagnostics, &checked_files, &graph_files);
+  for diagnostic in &delta.added {
+    log::error!("{}\n", diagnostic);
+  }
+  for diagnostic in &delta.fixed {
+    log::info!(
+      "{} {}",
+      colors::green("Fixed"),
+      diagnostic.to_compact_string()
+    );
+  }
+  let count = watch_diagnostics.count();
+  if count == 0 {
+    return Ok(());
+  }
+  log::info!(
+    "Found {} {} ({} new, {} unchanged, {} fixed).",
+    count,
+    if count == 1 { "problem" } else { "problems" },
+    delta.added.len(),
+    delta.unchanged,
+    delta.fixed.len(),
+  );
+  Err(FailedTypeCheckingError { can_skip: false }.into())
+}
+
+fn has_config_changed(
+  config_paths: &[PathBuf],
+  changed_paths: &HashSet<PathBuf>,
+) -> bool {
+  config_paths.iter().any(|path| {
+    canonicalize_path(path)
+      .map(|path| changed_paths.contains(&path))
+      .unwrap_or(false)
+  })
+}
+
+/// Whether the changed paths are all local modules of the graph, so that the
+/// factory of the previous check can be kept. Added or removed files and
+/// config changes need a new factory, which doesn't have the resolutions and
+/// the config of the previous check cached.
+fn has_only_modules_changed(
+  graph: &ModuleGraph,
+  config_paths: &[PathBuf],
+  changed_paths: &HashSet<PathBuf>,
+) -> bool {
+  if has_config_changed(config_paths, changed_paths) {
+    return false;
+  }
+  let module_paths = graph
+    .specifiers()
+    .filter_map(|(specifier, _)| url_to_file_path(specifier).ok())
+    .filter_map(|path| canonicalize_path(&path).ok())
+    .collect::<HashSet<_>>();
+  changed_paths.iter().all(|path| module_paths.contains(path))
+}
+
+/// Whether the module is a local file, outside of `node_modules`, that
+/// didn't change since the previous check.
+fn is_unchanged_local_module(
+  specifier: &ModuleSpecifier,
+  changed_paths: &HashSet<PathBuf>,
+) -> bool {
+  specifier.scheme() == "file"
+    && !specifier.path().contains("/node_modules/")
+    && url_to_file_path(specifier)
+      .ok()
+      .and_then(|path| canonicalize_path(&path).ok())
+      .is_some_and(|path| !changed_paths.contains(&path))
+}
+
+/// Loads the local modules that didn't change from the graph of the previous
+/// check instead of reading them again.
+struct WatchGraphLoader<'a> {
+  inner_loader: &'a mut dyn Loader,
+  previous_graph: &'a ModuleGraph,
+  changed_paths: &'a HashSet<PathBuf>,
+}
+
+impl<'a> WatchGraphLoader<'a> {
+  fn load_from_previous_graph(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Option<LoadResponse> {
+    if !is_unchanged_local_module(specifier, self.changed_paths) {
+      return None;
+    }
+    let content: Arc<[u8]> = match self.previous_graph.get(specifier)? {
+      Module::Js(module) => Arc::from(module.source.as_bytes()),
+      Module::Json(module) => Arc::from(module.source.as_bytes()),
+      Module::Wasm(module) => module.source.clone(),
+      Module::Npm(_) | Module::Node(_) | Module::External(_) => return None,
+    };
+    Some(LoadResponse::Module {
+      content,
+      specifier: specifier.clone(),
+      maybe_headers: None,
+    })
+  }
+}
+
+impl<'a> Loader for WatchGraphLoader<'a> {
+  fn load(
+    &self,
+    specifier: &ModuleSpecifier,
+    options: LoadOptions,
+  ) -> LoadFuture {
+    match self.load_from_previous_graph(specifier) {
+      Some(response) => future::ready(Ok(Some(response))).boxed_local(),
+      None => self.inner_loader.load(specifier, options),
+    }
+  }
+
+  fn cache_module_info(
+    &self,
+    specifier: &ModuleSpecifier,
+    media_type: MediaType,
+    source: &Arc<[u8]>,
+    module_info: &deno_graph::ModuleInfo,
+  ) {
+    self.inner_loader.cache_module_info(
+      specifier,
+      media_type,
+      source,
+      module_info,
+    )
+  }
+}
+
+/// The local and remote modules that the roots of the graph depend on.
+fn walk_graph_specifiers(graph: &ModuleGraph) -> HashSet<String> {
+  let mut specifiers = HashSet::new();
+  let mut walk = graph.walk(
+    graph.roots.iter(),
+    WalkOptions {
+      follow_dynamic: true,
+      kind: graph.graph_kind(),
+      prefer_fast_check_graph: false,
+      check_js: CheckJsOption::True,
+    },
+  );
+  while let Some((specifier, _)) = walk.next() {
+    specifiers.insert(specifier.to_string());
+  }
+  specifiers
+}
+
+/// The diagnostics of `deno check --watch` by file, so that a run that only
+/// checks some of the roots again can report what changed.
+#[derive(Default)]
+struct WatchDiagnostics {
+  by_file: BTreeMap<Option<String>, Vec<tsc::Diagnostic>>,
+}
+
+#[derive(Debug, Default)]
+struct WatchDiagnosticsDelta {
+  added: Vec<tsc::Diagnostic>,
+  fixed: Vec<tsc::Diagnostic>,
+  unchanged: usize,
+}
+
+impl WatchDiagnostics {
+  /// Replaces the diagnostics of the checked files and the files that were
+  /// removed from the graph.
+  fn update(
+    &mut self,
+    diagnostics: Vec<tsc::Diagnostic>,
+    checked_files: &HashSet<String>,
+    graph_files: &HashSet<String>,
+  ) -> WatchDiagnosticsDelta {
+    let mut new_by_file: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for diagnostic in diagnostics {
+      new_by_file
+        .entry(diagnostic.file_name.clone())
+        .or_default()
+        .push(diagnostic);
+    }
+
+    let mut old_by_file = std::mem::take(&mut self.by_file);
+    // diagnostics without a file are reported again on every check
+    old_by_file.retain(|file, diagnostics| {
+      let is_unchecked = !new_by_file.contains_key(file)
+        && file.as_ref().is_some_and(|file| {
+          !checked_files.contains(file) && graph_files.contains(file)
+        });
+      if is_unchecked {
+        self
+          .by_file
+          .insert(file.clone(), std::mem::take(diagnostics));
+      }
+      !is_unchecked
+    });
+
+    let mut delta = WatchDiagnosticsDelta {
+      unchanged: self.count(),
+      ..Default::default()
+    };
+    for (file, old) in &old_by_file {
+      let new = new_by_file.get(file).map(Vec::as_slice).unwrap_or_default();
+      delta.fixed.extend(
+        old
+          .iter()
+          .filter(|d| !new.iter().any(|n| n.is_same_problem(d)))
+          .cloned(),
+      );
+    }
+    for (file, new) in new_by_file {
+      let old = old_by_file
+        .get(&file)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+      for diagnostic in &new {
+        if old.iter().any(|o| o.is_same_problem(diagnostic)) {
+          delta.unchanged += 1;
+        } else {
+          delta.added.push(diagnostic.clone());
+        }
+      }
+      self.by_file.insert(file, new);
+    }
+    delta
+  }
+
+  fn count(&self) -> usize {
+    self.by_file.values().map(Vec::len).sum()
+  }
 }
 
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
@@ -158,6 +684,8 @@ pub struct TypeChecker {
   sys: CliSys,
   tsconfig_resolver: Arc<TsConfigResolver>,
   code_cache: Option<Arc<crate::cache::CodeCache>>,
+  /// Keeps the tsc isolates between the checks of `deno check --watch`.
+  tsc_workers: Option<TscWorkers>,
 }
 
 impl TypeChecker {
@@ -174,6 +702,11 @@ impl TypeChecker {
     tsconfig_resolver: Arc<TsConfigResolver>,
     code_cache: Option<Arc<crate::cache::CodeCache>>,
   ) -> Self {
+    let tsc_workers = matches!(
+      cli_options.sub_command(),
+      DenoSubcommand::Check(CheckFlags { watch: Some(_), .. })
+    )
+    .then(TscWorkers::default);
     Self {
       caches,
       cjs_tracker,
@@ -185,6 +718,7 @@ impl TypeChecker {
       sys,
       tsconfig_resolver,
       code_cache,
+      tsc_workers,
     }
   }
 
@@ -197,16 +731,30 @@ impl TypeChecker {
     graph: ModuleGraph,
     options: CheckOptions,
   ) -> Result<Arc<ModuleGraph>, CheckError> {
+    let mut sarif_run = match self.cli_options.sub_command() {
+      DenoSubcommand::Check(CheckFlags { sarif: true, .. }) => {
+        Some(new_sarif_run(self.cli_options.initial_cwd()))
+      }
+      _ => None,
+    };
     let mut diagnostics = self.check_diagnostics(graph, options).await?;
     let mut failed = false;
     for result in diagnostics.by_ref() {
       let mut diagnostics = result?;
+      if let Some(sarif_run) = &mut sarif_run {
+        diagnostics.add_to_sarif_run(sarif_run);
+        failed |= diagnostics.has_error();
+        continue;
+      }
       diagnostics.emit_warnings();
       if diagnostics.has_diagnostic() {
         failed = true;
         log::error!("{}\n", diagnostics);
       }
     }
+    if let Some(sarif_run) = sarif_run {
+      print_sarif_log(sarif_run, Vec::new());
+    }
     if failed {
       Err(
         FailedTypeCheckingError {
@@ -303,19 +851,22 @@ impl TypeChecker {
         grouped_roots,
         options,
         seen_diagnotics: Default::default(),
+        results: None,
         code_cache: self.code_cache.clone(),
+        tsc_workers: self.tsc_workers.as_ref(),
       }),
     ))
   }
 
-  /// Groups the roots based on the compiler options, which includes the
-  /// resolved TsConfig and resolved compilerOptions.types
+  /// Groups the roots based on the workspace member and the compiler
+  /// options, which includes the resolved TsConfig and resolved
+  /// compilerOptions.types
   fn group_roots_by_compiler_options<'a>(
     &'a self,
     graph: &ModuleGraph,
     lib: TsTypeLib,
   ) -> Result<IndexMap<CheckGroupKey<'a>, CheckGroupInfo>, CheckError> {
-    let mut imports_for_specifier: HashMap<Arc<Url>, Rc<Vec<Url>>> =
+    let mut imports_for_specifier: HashMap<Arc<Url>, Arc<Vec<Url>>> =
       HashMap::with_capacity(self.tsconfig_resolver.folder_count());
     let mut roots_by_config: IndexMap<_, CheckGroupInfo> =
       IndexMap::with_capacity(self.tsconfig_resolver.folder_count());
@@ -327,7 +878,7 @@ impl TypeChecker {
             entry.get().clone()
           }
           std::collections::hash_map::Entry::Vacant(vacant_entry) => {
-            let value = Rc::new(resolve_graph_imports_for_workspace_dir(
+            let value = Arc::new(resolve_graph_imports_for_workspace_dir(
               graph,
               &folder.dir,
             ));
@@ -401,10 +952,13 @@ fn resolve_graph_imports_for_workspace_dir(
 }
 
 /// Key to use to group roots together by config.
+///
+/// Each group is checked in a separate program, so that workspace members
+/// with different configs are checked concurrently and cached independently.
 #[derive(Debug, Hash, PartialEq, Eq)]
 struct CheckGroupKey<'a> {
   ts_config: &'a Arc<TsConfig>,
-  imports: Rc<Vec<Url>>,
+  imports: Arc<Vec<Url>>,
 }
 
 struct CheckGroupInfo {
@@ -453,16 +1007,33 @@ struct DiagnosticsByFolderRealIterator<'a> {
   log_level: Option<log::Level>,
   npm_check_state_hash: Option<u64>,
   seen_diagnotics: HashSet<String>,
+  /// The results of each group in order, which are all computed on the
+  /// first call to `next`.
+  results: Option<VecDeque<CheckedGroup>>,
   options: CheckOptions,
   code_cache: Option<Arc<crate::cache::CodeCache>>,
+  tsc_workers: Option<&'a TscWorkers>,
 }
 
 impl<'a> Iterator for DiagnosticsByFolderRealIterator<'a> {
   type Item = Result<Diagnostics, CheckError>;
 
   fn next(&mut self) -> Option<Self::Item> {
-    let (group_key, group_info) = self.grouped_roots.shift_remove_index(0)?;
-    let mut result = self.check_diagnostics_in_folder(&group_key, group_info);
+    if self.results.is_none() {
+      self.results = Some(self.check_all_groups());
+    }
+    let CheckedGroup {
+      logged_roots,
+      mut result,
+    } = self.results.as_mut()?.pop_front()?;
+    // log out the roots that were checked next to their diagnostics
+    for root in logged_roots {
+      log::info!(
+        "{} {}",
+        colors::green("Check"),
+        to_percent_decoded_str(root.as_str())
+      );
+    }
     if let Ok(diagnostics) = &mut result {
       diagnostics.retain(|d| {
         if let (Some(file_name), Some(start)) = (&d.file_name, &d.start) {
@@ -486,22 +1057,127 @@ impl<'a> Iterator for DiagnosticsByFolderRealIterator<'a> {
 }
 
 impl<'a> DiagnosticsByFolderRealIterator<'a> {
-  #[allow(clippy::too_many_arguments)]
-  fn check_diagnostics_in_folder(
+  /// Checks all the groups, running the programs that aren't cached
+  /// concurrently. The results are in the order of the groups, so the output
+  /// is the same regardless of which program finishes first.
+  fn check_all_groups(&mut self) -> VecDeque<CheckedGroup> {
+    let grouped_roots = std::mem::take(&mut self.grouped_roots);
+    let mut logged_roots = Vec::with_capacity(grouped_roots.len());
+    let mut results = Vec::with_capacity(grouped_roots.len());
+    let mut pending = Vec::new();
+    for (index, (group_key, group_info)) in
+      grouped_roots.into_iter().enumerate()
+    {
+      let (roots, prepared) = self.prepare_check(&group_key, group_info);
+      logged_roots.push(roots);
+      match prepared {
+        PreparedCheck::Done(diagnostics) => results.push(Some(Ok(diagnostics))),
+        PreparedCheck::Pending(check) => {
+          results.push(None);
+          pending.push((index, check));
+        }
+      }
+    }
+    for (index, result) in self.exec_pending_checks(pending) {
+      results[index] = Some(result);
+    }
+    logged_roots
+      .into_iter()
+      .zip(results)
+      .filter_map(|(logged_roots, result)| {
+        Some(CheckedGroup {
+          logged_roots,
+          result: result?,
+        })
+      })
+      .collect()
+  }
+
+  fn exec_pending_checks(
     &self,
-    group_key: &'a CheckGroupKey<'a>,
-    group_info: CheckGroupInfo,
-  ) -> Result<Diagnostics, CheckError> {
-    fn log_provided_roots(provided_roots: &[Url]) {
-      for root in provided_roots {
-        log::info!(
-          "{} {}",
-          colors::green("Check"),
-          to_percent_decoded_str(root.as_str())
-        );
+    pending: Vec<(usize, PendingCheck<'a>)>,
+  ) -> Vec<(usize, Result<Diagnostics, CheckError>)> {
+    let concurrency = tsc_concurrency().min(pending.len());
+    if let Some(tsc_workers) = self.tsc_workers {
+      if tsc_workers.spawn(concurrency) > 0 {
+        return self.exec_checks_in_workers(tsc_workers, pending);
       }
     }
+    if concurrency <= 1 {
+      return pending
+        .into_iter()
+        .map(|(index, check)| (index, self.exec_check(check)))
+        .collect();
+    }
+
+    // each program is checked in its own tsc isolate
+    let queue = Mutex::new(pending.into_iter());
+    let results = Mutex::new(Vec::new());
+    std::thread::scope(|scope| {
+      for i in 0..concurrency {
+        let worker = || loop {
+          let Some((index, check)) = queue.lock().next() else {
+            break;
+          };
+          let result = self.exec_check(check);
+          results.lock().push((index, result));
+        };
+        let spawn_result = std::thread::Builder::new()
+          .name(format!("check-{i}"))
+          .stack_size(TSC_THREAD_STACK_SIZE)
+          .spawn_scoped(scope, worker);
+        if spawn_result.is_err() {
+          // the other threads or this thread will check the remaining ones
+          break;
+        }
+      }
+    });
+    // check what's left if no thread could be spawned
+    let mut results = results.into_inner();
+    for (index, check) in queue.into_inner() {
+      results.push((index, self.exec_check(check)));
+    }
+    results
+  }
+
+  /// Checks the programs in the isolates that are kept between the checks
+  /// of `deno check --watch`.
+  fn exec_checks_in_workers(
+    &self,
+    tsc_workers: &TscWorkers,
+    pending: Vec<(usize, PendingCheck<'a>)>,
+  ) -> Vec<(usize, Result<Diagnostics, CheckError>)> {
+    let sent = pending
+      .into_iter()
+      .map(|(index, check)| {
+        let receiver =
+          tsc_workers.send(self.tsc_request(&check), self.tsc_code_cache());
+        (index, check, receiver)
+      })
+      .collect::<Vec<_>>();
+    sent
+      .into_iter()
+      .map(|(index, check, receiver)| {
+        let response = match receiver.recv() {
+          Ok(response) => response,
+          // the worker is gone, so check it on this thread
+          Err(_) => tsc::exec(self.tsc_request(&check), self.tsc_code_cache()),
+        };
+        let result = response
+          .map_err(CheckError::from)
+          .map(|response| self.finish_check(check, response));
+        (index, result)
+      })
+      .collect()
+  }
 
+  /// Prepares the program of a group, along with the roots to log when its
+  /// diagnostics are reported.
+  fn prepare_check(
+    &self,
+    group_key: &CheckGroupKey<'a>,
+    group_info: CheckGroupInfo,
+  ) -> (Vec<Url>, PreparedCheck<'a>) {
     // walk the graph
     let ts_config = group_key.ts_config;
     let mut graph_walker = GraphWalker::new(
@@ -514,7 +1190,7 @@ impl<'a> DiagnosticsByFolderRealIterator<'a> {
       ts_config.as_ref(),
       self.options.type_check_mode,
     );
-    let mut provided_roots = group_info.roots;
+    let provided_roots = group_info.roots;
     for import in group_key.imports.iter() {
       graph_walker.add_config_import(import, &group_info.referrer);
     }
@@ -535,10 +1211,12 @@ impl<'a> DiagnosticsByFolderRealIterator<'a> {
     missing_diagnostics.apply_fast_check_source_maps(&self.graph);
 
     if root_names.is_empty() {
-      if missing_diagnostics.has_diagnostic() {
-        log_provided_roots(&provided_roots);
-      }
-      return Ok(missing_diagnostics);
+      let logged_roots = if missing_diagnostics.has_diagnostic() {
+        provided_roots
+      } else {
+        Vec::new()
+      };
+      return (logged_roots, PreparedCheck::Done(missing_diagnostics));
     }
 
     if !self.options.reload && !missing_diagnostics.has_diagnostic() {
@@ -546,53 +1224,86 @@ impl<'a> DiagnosticsByFolderRealIterator<'a> {
       if let Some(check_hash) = maybe_check_hash {
         if self.type_check_cache.has_check_hash(check_hash) {
           log::debug!("Already type checked {}", group_info.referrer);
-          return Ok(Default::default());
+          return (Vec::new(), PreparedCheck::Done(Default::default()));
         }
       }
     }
 
-    // log out the roots that we're checking
-    log_provided_roots(&provided_roots);
-
     // the first root will always either be the specifier that the user provided
     // or the first specifier in a directory
-    let first_root = provided_roots.remove(0);
+    let first_root = provided_roots[0].clone();
+
+    (
+      provided_roots,
+      PreparedCheck::Pending(PendingCheck {
+        ts_config,
+        first_root,
+        root_names,
+        missing_diagnostics,
+        maybe_check_hash,
+      }),
+    )
+  }
+
+  fn exec_check(
+    &self,
+    check: PendingCheck<'a>,
+  ) -> Result<Diagnostics, CheckError> {
+    let response = tsc::exec(self.tsc_request(&check), self.tsc_code_cache())?;
+    Ok(self.finish_check(check, response))
+  }
 
+  fn tsc_request(&self, check: &PendingCheck<'a>) -> tsc::Request {
     // while there might be multiple roots, we can't "merge" the build info, so we
     // try to retrieve the build info for first root, which is the most common use
     // case.
     let maybe_tsbuildinfo = if self.options.reload {
       None
     } else {
-      self.type_check_cache.get_tsbuildinfo(&first_root)
+      self.type_check_cache.get_tsbuildinfo(&check.first_root)
     };
     // to make tsc build info work, we need to consistently hash modules, so that
     // tsc can better determine if an emit is still valid or not, so we provide
     // that data here.
     let tsconfig_hash_data = FastInsecureHasher::new_deno_versioned()
-      .write_hashable(ts_config)
+      .write_hashable(check.ts_config)
       .finish();
-    let code_cache = self.code_cache.as_ref().map(|c| {
+    tsc::Request {
+      config: check.ts_config.clone(),
+      debug: self.log_level == Some(log::Level::Debug),
+      graph: self.graph.clone(),
+      hash_data: tsconfig_hash_data,
+      maybe_npm: Some(tsc::RequestNpmState {
+        cjs_tracker: self.cjs_tracker.clone(),
+        node_resolver: self.node_resolver.clone(),
+        npm_resolver: self.npm_resolver.clone(),
+      }),
+      maybe_tsbuildinfo,
+      root_names: check.root_names.clone(),
+      check_mode: self.options.type_check_mode,
+    }
+  }
+
+  fn tsc_code_cache(
+    &self,
+  ) -> Option<Arc<dyn deno_runtime::code_cache::CodeCache>> {
+    self.code_cache.as_ref().map(|c| {
       let c: Arc<dyn deno_runtime::code_cache::CodeCache> = c.clone();
       c
-    });
-    let response = tsc::exec(
-      tsc::Request {
-        config: ts_config.clone(),
-        debug: self.log_level == Some(log::Level::Debug),
-        graph: self.graph.clone(),
-        hash_data: tsconfig_hash_data,
-        maybe_npm: Some(tsc::RequestNpmState {
-          cjs_tracker: self.cjs_tracker.clone(),
-          node_resolver: self.node_resolver.clone(),
-          npm_resolver: self.npm_resolver.clone(),
-        }),
-        maybe_tsbuildinfo,
-        root_names,
-        check_mode: self.options.type_check_mode,
-      },
-      code_cache,
-    )?;
+    })
+  }
+
+  fn finish_check(
+    &self,
+    check: PendingCheck<'a>,
+    response: tsc::Response,
+  ) -> Diagnostics {
+    let PendingCheck {
+      first_root,
+      missing_diagnostics,
+      maybe_check_hash,
+      ..
+    } = check;
 
     let mut response_diagnostics = response.diagnostics.filter(|d| {
       self.should_include_diagnostic(self.options.type_check_mode, d)
@@ -615,7 +1326,7 @@ impl<'a> DiagnosticsByFolderRealIterator<'a> {
 
     log::debug!("{}", response.stats);
 
-    Ok(diagnostics)
+    diagnostics
   }
 
   fn should_include_diagnostic(
@@ -648,6 +1359,125 @@ impl<'a> DiagnosticsByFolderRealIterator<'a> {
   }
 }
 
+/// tsc needs a larger stack than the default of spawned threads.
+const TSC_THREAD_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Each tsc isolate takes hundreds of megabytes for a large program, so only
+/// a few are run at once unless `DENO_JOBS` asks for more.
+const DEFAULT_MAX_TSC_CONCURRENCY: usize = 4;
+
+/// How many programs are checked at once, which is the value of `DENO_JOBS`
+/// or the number of available CPUs up to `DEFAULT_MAX_TSC_CONCURRENCY`.
+fn tsc_concurrency() -> usize {
+  if let Ok(value) = std::env::var("DENO_JOBS") {
+    if let Ok(value) = value.parse::<NonZeroUsize>() {
+      return value.get();
+    }
+  }
+  std::thread::available_parallelism()
+    .map(|value| value.get().min(DEFAULT_MAX_TSC_CONCURRENCY))
+    .unwrap_or(2)
+}
+
+type TscResult = Result<tsc::Response, tsc::ExecError>;
+
+struct TscJob {
+  request: tsc::Request,
+  code_cache: Option<Arc<dyn deno_runtime::code_cache::CodeCache>>,
+  result_tx: std::sync::mpsc::Sender<TscResult>,
+}
+
+/// Threads that each keep a tsc isolate, so that the next check doesn't
+/// start an isolate and parse the lib files and the unchanged modules
+/// again. The threads stop when this is dropped.
+struct TscWorkers {
+  job_tx: Mutex<std::sync::mpsc::Sender<TscJob>>,
+  job_rx: Arc<Mutex<std::sync::mpsc::Receiver<TscJob>>>,
+  count: Mutex<usize>,
+}
+
+impl Default for TscWorkers {
+  fn default() -> Self {
+    let (job_tx, job_rx) = std::sync::mpsc::channel();
+    Self {
+      job_tx: Mutex::new(job_tx),
+      job_rx: Arc::new(Mutex::new(job_rx)),
+      count: Mutex::new(0),
+    }
+  }
+}
+
+impl TscWorkers {
+  /// Spawns threads until there are `count` of them, returning how many
+  /// there are.
+  fn spawn(&self, count: usize) -> usize {
+    let mut current = self.count.lock();
+    while *current < count {
+      let job_rx = self.job_rx.clone();
+      let spawn_result = std::thread::Builder::new()
+        .name(format!("check-{}", *current))
+        .stack_size(TSC_THREAD_STACK_SIZE)
+        .spawn(move || {
+          let mut maybe_runtime = None;
+          loop {
+            // the lock is only held while waiting for a job
+            let job = job_rx.lock().recv();
+            let Ok(job) = job else {
+              break;
+            };
+            let result = tsc::exec_with_runtime(
+              &mut maybe_runtime,
+              job.request,
+              job.code_cache,
+            );
+            let _ = job.result_tx.send(result);
+          }
+        });
+      if spawn_result.is_err() {
+        break;
+      }
+      *current += 1;
+    }
+    *current
+  }
+
+  fn send(
+    &self,
+    request: tsc::Request,
+    code_cache: Option<Arc<dyn deno_runtime::code_cache::CodeCache>>,
+  ) -> std::sync::mpsc::Receiver<TscResult> {
+    let (result_tx, result_rx) = std::sync::mpsc::channel();
+    // when this fails, the receiver reports that the worker is gone
+    let _ = self.job_tx.lock().send(TscJob {
+      request,
+      code_cache,
+      result_tx,
+    });
+    result_rx
+  }
+}
+
+/// The result of a group, along with the roots that were checked.
+struct CheckedGroup {
+  logged_roots: Vec<Url>,
+  result: Result<Diagnostics, CheckError>,
+}
+
+enum PreparedCheck<'a> {
+  /// The group didn't need to be type checked by tsc.
+  Done(Diagnostics),
+  Pending(PendingCheck<'a>),
+}
+
+/// A group whose program needs to be type checked by tsc.
+struct PendingCheck<'a> {
+  ts_config: &'a Arc<TsConfig>,
+  first_root: Url,
+  root_names: Vec<(ModuleSpecifier, MediaType)>,
+  missing_diagnostics: Diagnostics,
+  maybe_check_hash: Option<CacheDBHash>,
+}
+
 struct TscRoots {
   roots: Vec<(ModuleSpecifier, MediaType)>,
   missing_diagnostics: tsc::Diagnostics,
@@ -0,0 +1,192 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::url::Url;
+
+use crate::args::CoverageDiff;
+use crate::util::fs::canonicalize_path;
+use crate::util::git;
+
+/// The lines that a change added or modified, as line indexes by the
+/// canonicalized path of their file.
+#[derive(Debug, Default)]
+pub struct ChangedLines {
+  files: HashMap<PathBuf, HashSet<usize>>,
+}
+
+impl ChangedLines {
+  pub fn resolve(diff: &CoverageDiff, cwd: &Path) -> Result<Self, AnyError> {
+    match diff {
+      CoverageDiff::GitRef(git_ref) => Self::since(cwd, git_ref),
+      CoverageDiff::File(path) => {
+        let path = cwd.join(path);
+        let text = std::fs::read_to_string(&path).with_context(|| {
+          format!("Failed to read diff file '{}'", path.display())
+        })?;
+        Ok(Self::from_diff(cwd, &text))
+      }
+    }
+  }
+
+  /// The lines changed since `git_ref`, where every line of an untracked
+  /// file counts as changed.
+  fn since(cwd: &Path, git_ref: &str) -> Result<Self, AnyError> {
+    let (root, diff) = git::diff_since(cwd, git_ref)?;
+    let mut changed_lines = Self::from_diff(&root, &diff);
+    for path in git::untracked_files(cwd)? {
+      let Ok(text) = std::fs::read_to_string(&path) else {
+        continue;
+      };
+      if let Ok(path) = canonicalize_path(&path) {
+        changed_lines
+          .files
+          .insert(path, (0..text.lines().count()).collect());
+      }
+    }
+    Ok(changed_lines)
+  }
+
+  /// Resolves the paths of a unified diff relative to `base`. Files that no
+  /// longer exist are left out.
+  fn from_diff(base: &Path, diff: &str) -> Self {
+    let files = parse_unified_diff(diff)
+      .into_iter()
+      .filter_map(|(path, lines)| {
+        let path = canonicalize_path(&base.join(path)).ok()?;
+        Some((path, lines))
+      })
+      .collect();
+    Self { files }
+  }
+
+  pub fn get(&self, url: &Url) -> Option<&HashSet<usize>> {
+    let path = canonicalize_path(&url.to_file_path().ok()?).ok()?;
+    self.files.get(&path)
+  }
+}
+
+/// Gets the indexes of the lines added or modified in every file of a
+/// unified diff, by the path of the file in the diff.
+fn parse_unified_diff(diff: &str) -> HashMap<String, HashSet<usize>> {
+  let mut files = HashMap::<String, HashSet<usize>>::new();
+  let mut current_file: Option<String> = None;
+  // lines of the hunk that are left to read, in the old and new file
+  let mut old_remaining = 0usize;
+  let mut new_remaining = 0usize;
+  let mut new_line_index = 0;
+  for line in diff.lines() {
+    if old_remaining > 0 || new_remaining > 0 {
+      match line.chars().next() {
+        Some('+') => {
+          if let Some(file) = &current_file {
+            files
+              .entry(file.clone())
+              .or_default()
+              .insert(new_line_index);
+          }
+          new_line_index += 1;
+          new_remaining = new_remaining.saturating_sub(1);
+        }
+        Some('-') => old_remaining = old_remaining.saturating_sub(1),
+        // "\ No newline at end of file"
+        Some('\\') => {}
+        _ => {
+          new_line_index += 1;
+          old_remaining = old_remaining.saturating_sub(1);
+          new_remaining = new_remaining.saturating_sub(1);
+        }
+      }
+      continue;
+    }
+    if let Some(path) = line.strip_prefix("+++ ") {
+      // the path can be followed by a tab and a timestamp
+      let path = path.split('\t').next().unwrap_or(path).trim_end();
+      current_file = (path != "/dev/null")
+        .then(|| path.strip_prefix("b/").unwrap_or(path).to_string());
+    } else if let Some(hunk) = line.strip_prefix("@@ ") {
+      if let Some((old, new)) = parse_hunk_header(hunk) {
+        old_remaining = old.1;
+        new_remaining = new.1;
+        // the start is 1-based, or 0 when the new file is empty
+        new_line_index = new.0.saturating_sub(1);
+      }
+    }
+  }
+  files
+}
+
+/// Parses `-1,2 +3,4 @@` into the start and length of the old and the new
+/// range.
+fn parse_hunk_header(hunk: &str) -> Option<((usize, usize), (usize, usize))> {
+  let mut parts = hunk.split(' ');
+  let old = parse_hunk_range(parts.next()?.strip_prefix('-')?)?;
+  let new = parse_hunk_range(parts.next()?.strip_prefix('+')?)?;
+  Some((old, new))
+}
+
+fn parse_hunk_range(range: &str) -> Option<(usize, usize)> {
+  match range.split_once(',') {
+    Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+    None => Some((range.parse().ok()?, 1)),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_unified_diff() {
+    let diff = "diff --git a/src/mod.ts b/src/mod.ts
+index 1111111..2222222 100644
+--- a/src/mod.ts
++++ b/src/mod.ts
+@@ -2,0 +3,2 @@ export function add(a: number, b: number) {
++  if (a < 0) {
++    throw new Error();
+@@ -10 +12 @@ export function sub(a: number, b: number) {
+-  return a - b;
++++ counts as an added line
+diff --git a/old.ts b/old.ts
+deleted file mode 100644
+--- a/old.ts
++++ /dev/null
+@@ -1,2 +0,0 @@
+-export const a = 1;
+-export const b = 2;
+diff --git a/new.ts b/new.ts
+new file mode 100644
+--- /dev/null
++++ b/new.ts
+@@ -0,0 +1,2 @@
++export const a = 1;
++export const b = 2;
+\\ No newline at end of file
+";
+    let files = parse_unified_diff(diff);
+    assert_eq!(files.len(), 2);
+    assert_eq!(files["src/mod.ts"], HashSet::from([2, 3, 11]));
+    assert_eq!(files["new.ts"], HashSet::from([0, 1]));
+  }
+
+  #[test]
+  fn parses_hunks_with_context() {
+    let diff = "--- a.ts
++++ a.ts
+@@ -1,4 +1,4 @@
+ const a = 1;
+-const b = 2;
++const b = 3;
+ const c = 3;
+ const d = 4;
+";
+    let files = parse_unified_diff(diff);
+    assert_eq!(files["a.ts"], HashSet::from([1]));
+  }
+}
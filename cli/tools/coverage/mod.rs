@@ -1,21 +1,31 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fs;
 use std::fs::File;
 use std::io::BufWriter;
 use std::io::Write;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use deno_ast::swc::ast;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
 use deno_ast::MediaType;
 use deno_ast::ModuleKind;
 use deno_ast::ModuleSpecifier;
+use deno_ast::SourceRangedForSpanned;
+use deno_ast::StartSourcePos;
 use deno_config::glob::FileCollector;
 use deno_config::glob::FilePatterns;
 use deno_config::glob::PathOrPattern;
 use deno_config::glob::PathOrPatternSet;
 use deno_core::anyhow::anyhow;
+use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::error::CoreError;
@@ -27,11 +37,14 @@ use deno_error::JsErrorBox;
 use deno_resolver::npm::DenoInNpmPackageChecker;
 use node_resolver::InNpmPackageChecker;
 use regex::Regex;
+use serde::Deserialize;
+use serde::Serialize;
 use text_lines::TextLines;
 use uuid::Uuid;
 
 use crate::args::CliOptions;
 use crate::args::CoverageFlags;
+use crate::args::CoverageType;
 use crate::args::FileFlags;
 use crate::args::Flags;
 use crate::cdp;
@@ -39,18 +52,49 @@ use crate::factory::CliFactory;
 use crate::file_fetcher::TextDecodedFile;
 use crate::sys::CliSys;
 use crate::tools::fmt::format_json;
+use crate::tools::test::fmt::to_relative_path_or_remote_url;
 use crate::tools::test::is_supported_test_path;
 use crate::util::text_encoding::source_map_from_code;
 
+mod diff;
 mod merge;
 mod range_tree;
 mod reporter;
+mod threshold;
 mod util;
 use merge::ProcessCoverage;
+use range_tree::RangeTree;
+use range_tree::RangeTreeArena;
 
 pub struct CoverageCollector {
   pub dir: PathBuf,
   session: LocalInspectorSession,
+  /// The module that coverage is attributed to once it's collected per test.
+  module: Option<String>,
+}
+
+/// The test that a coverage profile was collected for with
+/// `deno test --coverage-per-test`.
+#[derive(
+  Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverageTest {
+  pub origin: String,
+  /// `None` for the code that ran outside of the tests of the module, like
+  /// its top-level code.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+}
+
+/// A file in the coverage directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CoverageProfile {
+  #[serde(flatten)]
+  coverage: cdp::ScriptCoverage,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  test: Option<CoverageTest>,
 }
 
 #[async_trait::async_trait(?Send)]
@@ -70,9 +114,53 @@ impl crate::worker::CoverageCollector for CoverageCollector {
   }
 
   async fn stop_collecting(&mut self) -> Result<(), CoreError> {
-    fs::create_dir_all(&self.dir)?;
+    let script_coverages = self.take_precise_coverage().await?.result;
+    let test = self
+      .module
+      .take()
+      .map(|origin| CoverageTest { origin, name: None });
+    self.write_coverages(script_coverages, test)?;
+
+    self.disable_debugger().await?;
+    self.disable_profiler().await?;
+
+    Ok(())
+  }
 
+  async fn take_test_coverage(
+    &mut self,
+    origin: &str,
+    test: Option<&str>,
+  ) -> Result<(), CoreError> {
+    // taking the coverage resets the counts, so every profile only holds
+    // what ran since the previous one
     let script_coverages = self.take_precise_coverage().await?.result;
+    let test = CoverageTest {
+      origin: origin.to_string(),
+      name: test.map(|name| name.to_string()),
+    };
+    self.write_coverages(script_coverages, Some(test))?;
+    self.module = Some(origin.to_string());
+    Ok(())
+  }
+}
+
+impl CoverageCollector {
+  pub fn new(dir: PathBuf, session: LocalInspectorSession) -> Self {
+    Self {
+      dir,
+      session,
+      module: None,
+    }
+  }
+
+  fn write_coverages(
+    &self,
+    script_coverages: Vec<cdp::ScriptCoverage>,
+    test: Option<CoverageTest>,
+  ) -> Result<(), CoreError> {
+    fs::create_dir_all(&self.dir)?;
+
     for script_coverage in script_coverages {
       // Filter out internal and http/https JS files and eval'd scripts
       // from being included in coverage reports
@@ -85,13 +173,26 @@ impl crate::worker::CoverageCollector for CoverageCollector {
       {
         continue;
       }
+      // otherwise every test writes a profile of every script
+      let is_test = test.as_ref().is_some_and(|test| test.name.is_some());
+      if is_test
+        && script_coverage
+          .functions
+          .iter()
+          .all(|function| function.ranges.iter().all(|range| range.count == 0))
+      {
+        continue;
+      }
 
       let filename = format!("{}.json", Uuid::new_v4());
       let filepath = self.dir.join(filename);
 
       let mut out = BufWriter::new(File::create(&filepath)?);
-      let coverage = serde_json::to_string(&script_coverage)
-        .map_err(JsErrorBox::from_err)?;
+      let coverage = serde_json::to_string(&CoverageProfile {
+        coverage: script_coverage,
+        test: test.clone(),
+      })
+      .map_err(JsErrorBox::from_err)?;
       let formatted_coverage =
         format_json(&filepath, &coverage, &Default::default())
           .ok()
@@ -102,17 +203,8 @@ impl crate::worker::CoverageCollector for CoverageCollector {
       out.flush()?;
     }
 
-    self.disable_debugger().await?;
-    self.disable_profiler().await?;
-
     Ok(())
   }
-}
-
-impl CoverageCollector {
-  pub fn new(dir: PathBuf, session: LocalInspectorSession) -> Self {
-    Self { dir, session }
-  }
 
   async fn enable_debugger(&mut self) -> Result<(), CoreError> {
     self
@@ -176,6 +268,13 @@ impl CoverageCollector {
   }
 }
 
+/// A position in the original source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SourcePosition {
+  line_index: usize,
+  column_index: usize,
+}
+
 #[derive(Debug, Clone)]
 struct BranchCoverageItem {
   line_index: usize,
@@ -183,6 +282,34 @@ struct BranchCoverageItem {
   branch_number: usize,
   taken: Option<i64>,
   is_hit: bool,
+  start: SourcePosition,
+  end: SourcePosition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConditionalKind {
+  If,
+  CondExpr,
+}
+
+/// An arm of a conditional, with how often it was taken.
+#[derive(Debug, Clone)]
+struct ConditionalArm {
+  start: SourcePosition,
+  end: SourcePosition,
+  count: i64,
+}
+
+/// An `if` statement or a conditional expression with its arms, the
+/// consequent first. An `if` without an `else` has an implicit `else` arm at
+/// the location of the statement.
+#[derive(Debug, Clone)]
+struct ConditionalCoverageItem {
+  kind: ConditionalKind,
+  line_index: usize,
+  start: SourcePosition,
+  end: SourcePosition,
+  arms: Vec<ConditionalArm>,
 }
 
 #[derive(Debug, Clone)]
@@ -190,6 +317,8 @@ struct FunctionCoverageItem {
   name: String,
   line_index: usize,
   execution_count: i64,
+  start: SourcePosition,
+  end: SourcePosition,
 }
 
 #[derive(Debug, Clone)]
@@ -197,11 +326,42 @@ pub struct CoverageReport {
   url: ModuleSpecifier,
   named_functions: Vec<FunctionCoverageItem>,
   branches: Vec<BranchCoverageItem>,
+  conditionals: Vec<ConditionalCoverageItem>,
   /// (line_index, number_of_hits)
   found_lines: Vec<(usize, i64)>,
+  /// line_index -> the tests that ran the line, when collected with
+  /// `deno test --coverage-per-test`
+  covering_tests: HashMap<usize, Vec<String>>,
   output: Option<PathBuf>,
 }
 
+impl CoverageReport {
+  /// Drops everything that isn't on one of `lines`, so that the report only
+  /// covers those lines. Functions, branches and conditionals are kept when
+  /// any of their lines is one of `lines`.
+  fn retain_lines(&mut self, lines: &HashSet<usize>) {
+    let overlaps = |start: &SourcePosition, end: &SourcePosition| {
+      (start.line_index..=end.line_index)
+        .any(|line_index| lines.contains(&line_index))
+    };
+    self
+      .found_lines
+      .retain(|(line_index, _)| lines.contains(line_index));
+    self
+      .branches
+      .retain(|branch| overlaps(&branch.start, &branch.end));
+    self
+      .conditionals
+      .retain(|conditional| overlaps(&conditional.start, &conditional.end));
+    self
+      .named_functions
+      .retain(|function| overlaps(&function.start, &function.end));
+    self
+      .covering_tests
+      .retain(|line_index, _| lines.contains(line_index));
+  }
+}
+
 fn generate_coverage_report(
   script_coverage: &cdp::ScriptCoverage,
   script_source: String,
@@ -212,14 +372,7 @@ fn generate_coverage_report(
     .as_ref()
     .map(|source_map| SourceMap::from_slice(source_map).unwrap());
   let text_lines = TextLines::new(&script_source);
-
-  let comment_ranges = deno_ast::lex(&script_source, MediaType::JavaScript)
-    .into_iter()
-    .filter(|item| {
-      matches!(item.inner, deno_ast::TokenOrComment::Comment { .. })
-    })
-    .map(|item| item.range)
-    .collect::<Vec<_>>();
+  let ignored_lines = find_ignored_lines(&script_source, &text_lines);
 
   let url = Url::parse(&script_coverage.url).unwrap();
   let mut coverage_report = CoverageReport {
@@ -232,7 +385,9 @@ fn generate_coverage_report(
         .count(),
     ),
     branches: Vec::new(),
+    conditionals: Vec::new(),
     found_lines: Vec::new(),
+    covering_tests: HashMap::new(),
     output: output.clone(),
   };
 
@@ -241,23 +396,25 @@ fn generate_coverage_report(
       continue;
     }
 
-    let line_index = range_to_src_line_index(
+    let (start, end) = range_to_src_positions(
       &function.ranges[0],
       &text_lines,
       &maybe_source_map,
     );
     coverage_report.named_functions.push(FunctionCoverageItem {
       name: function.function_name.clone(),
-      line_index,
+      line_index: start.line_index,
       execution_count: function.ranges[0].count,
+      start,
+      end,
     });
   }
 
   for (block_number, function) in script_coverage.functions.iter().enumerate() {
     let block_hits = function.ranges[0].count;
     for (branch_number, range) in function.ranges[1..].iter().enumerate() {
-      let line_index =
-        range_to_src_line_index(range, &text_lines, &maybe_source_map);
+      let (start, end) =
+        range_to_src_positions(range, &text_lines, &maybe_source_map);
 
       // From https://manpages.debian.org/unstable/lcov/geninfo.1.en.html:
       //
@@ -275,29 +432,31 @@ fn generate_coverage_report(
       };
 
       coverage_report.branches.push(BranchCoverageItem {
-        line_index,
+        line_index: start.line_index,
         block_number,
         branch_number,
         taken,
         is_hit: range.count > 0,
+        start,
+        end,
       })
     }
   }
 
+  coverage_report.conditionals = collect_conditionals(
+    &coverage_report.url,
+    script_coverage,
+    &script_source,
+    &text_lines,
+    &maybe_source_map,
+  );
+
   // TODO(caspervonb): collect uncovered ranges on the lines so that we can highlight specific
   // parts of a line in color (word diff style) instead of the entire line.
   let mut line_counts = Vec::with_capacity(text_lines.lines_count());
-  for line_index in 0..text_lines.lines_count() {
-    let line_start_byte_offset = text_lines.line_start(line_index);
-    let line_start_char_offset = text_lines.char_index(line_start_byte_offset);
-    let line_end_byte_offset = text_lines.line_end(line_index);
-    let line_end_char_offset = text_lines.char_index(line_end_byte_offset);
-    let ignore = comment_ranges.iter().any(|range| {
-      range.start <= line_start_byte_offset && range.end >= line_end_byte_offset
-    }) || script_source
-      [line_start_byte_offset..line_end_byte_offset]
-      .trim()
-      .is_empty();
+  for (line_index, ignore) in ignored_lines.into_iter().enumerate() {
+    let (line_start_char_offset, line_end_char_offset) =
+      line_char_range(&text_lines, line_index);
     let mut count = 0;
 
     if ignore {
@@ -334,76 +493,355 @@ fn generate_coverage_report(
     line_counts.push(count);
   }
 
-  coverage_report.found_lines =
-    if let Some(source_map) = maybe_source_map.as_ref() {
-      let script_source_lines = script_source.lines().collect::<Vec<_>>();
-      let mut found_lines = line_counts
-        .iter()
-        .enumerate()
-        .flat_map(|(index, count)| {
-          // get all the mappings from this destination line to a different src line
-          let mut results = source_map
-            .tokens()
-            .filter(|token| {
-              let dst_line = token.get_dst_line() as usize;
-              dst_line == index && {
-                let dst_col = token.get_dst_col() as usize;
-                let content = script_source_lines
-                  .get(dst_line)
-                  .and_then(|line| {
-                    line.get(dst_col..std::cmp::min(dst_col + 2, line.len()))
-                  })
-                  .unwrap_or("");
-
-                !content.is_empty()
-                  && content != "/*"
-                  && content != "*/"
-                  && content != "//"
-              }
-            })
-            .map(move |token| (token.get_src_line() as usize, *count))
-            .collect::<Vec<_>>();
-          // only keep the results that point at different src lines
-          results.sort_unstable_by_key(|(index, _)| *index);
-          results.dedup_by_key(|(index, _)| *index);
-          results.into_iter()
-        })
-        .collect::<Vec<(usize, i64)>>();
-
-      found_lines.sort_unstable_by_key(|(index, _)| *index);
-      // combine duplicated lines
-      for i in (1..found_lines.len()).rev() {
-        if found_lines[i].0 == found_lines[i - 1].0 {
-          found_lines[i - 1].1 += found_lines[i].1;
-          found_lines.remove(i);
-        }
-      }
-      found_lines
-    } else {
-      line_counts
-        .into_iter()
-        .enumerate()
-        .collect::<Vec<(usize, i64)>>()
-    };
+  coverage_report.found_lines = to_source_line_counts(
+    line_counts,
+    &script_source,
+    maybe_source_map.as_ref(),
+  );
 
   coverage_report
 }
 
-fn range_to_src_line_index(
-  range: &cdp::CoverageRange,
+/// Gets whether each line only holds comments or whitespace.
+fn find_ignored_lines(
+  script_source: &str,
+  text_lines: &TextLines,
+) -> Vec<bool> {
+  let comment_ranges = deno_ast::lex(script_source, MediaType::JavaScript)
+    .into_iter()
+    .filter(|item| {
+      matches!(item.inner, deno_ast::TokenOrComment::Comment { .. })
+    })
+    .map(|item| item.range)
+    .collect::<Vec<_>>();
+  (0..text_lines.lines_count())
+    .map(|line_index| {
+      let line_start_byte_offset = text_lines.line_start(line_index);
+      let line_end_byte_offset = text_lines.line_end(line_index);
+      comment_ranges.iter().any(|range| {
+        range.start <= line_start_byte_offset
+          && range.end >= line_end_byte_offset
+      }) || script_source[line_start_byte_offset..line_end_byte_offset]
+        .trim()
+        .is_empty()
+    })
+    .collect()
+}
+
+/// Finds the conditionals of the runtime code and counts how often each of
+/// their arms was taken, with the count of the innermost block at the start
+/// of the arm.
+fn collect_conditionals(
+  specifier: &ModuleSpecifier,
+  script_coverage: &cdp::ScriptCoverage,
+  script_source: &str,
   text_lines: &TextLines,
   maybe_source_map: &Option<SourceMap>,
-) -> usize {
-  let source_lc = text_lines.line_and_column_index(
-    text_lines.byte_index_from_char_index(range.start_char_offset),
+) -> Vec<ConditionalCoverageItem> {
+  let Ok(parsed_source) = deno_ast::parse_program(deno_ast::ParseParams {
+    specifier: specifier.clone(),
+    text: script_source.into(),
+    media_type: MediaType::JavaScript,
+    capture_tokens: false,
+    scope_analysis: false,
+    maybe_syntax: None,
+  }) else {
+    return Vec::new();
+  };
+  let mut collector = ConditionalCollector {
+    start_pos: parsed_source.range().start,
+    conditionals: Vec::new(),
+  };
+  parsed_source.program_ref().visit_with(&mut collector);
+
+  let rta = RangeTreeArena::with_capacity(
+    script_coverage
+      .functions
+      .iter()
+      .map(|function| function.ranges.len())
+      .sum(),
   );
+  let trees = script_coverage
+    .functions
+    .iter()
+    .filter_map(|function| {
+      RangeTree::from_sorted_ranges(&rta, &function.ranges)
+    })
+    .collect::<Vec<_>>();
+  // the count of the innermost block of the innermost function
+  let count_at = |byte_index: usize| {
+    let char_offset = text_lines.char_index(byte_index);
+    trees
+      .iter()
+      .filter(|tree| tree.start <= char_offset && char_offset < tree.end)
+      .min_by_key(|tree| tree.end - tree.start)
+      .map(|tree| tree.count_at(char_offset, 0))
+      .unwrap_or(0)
+  };
+  let position = |byte_index: usize| {
+    char_offset_to_src_position(
+      text_lines.char_index(byte_index),
+      text_lines,
+      maybe_source_map,
+    )
+  };
+
+  collector
+    .conditionals
+    .into_iter()
+    .map(|conditional| {
+      let start = position(conditional.range.start);
+      let end = position(conditional.range.end);
+      let consequent_count = count_at(conditional.consequent.start);
+      let alternate = match &conditional.alternate {
+        Some(alternate) => ConditionalArm {
+          start: position(alternate.start),
+          end: position(alternate.end),
+          count: count_at(alternate.start),
+        },
+        None => ConditionalArm {
+          start,
+          end,
+          count: (count_at(conditional.range.start) - consequent_count).max(0),
+        },
+      };
+      ConditionalCoverageItem {
+        kind: conditional.kind,
+        line_index: start.line_index,
+        start,
+        end,
+        arms: vec![
+          ConditionalArm {
+            start: position(conditional.consequent.start),
+            end: position(conditional.consequent.end),
+            count: consequent_count,
+          },
+          alternate,
+        ],
+      }
+    })
+    .collect()
+}
+
+/// A conditional of the runtime code, with byte ranges.
+struct ConditionalRanges {
+  kind: ConditionalKind,
+  range: Range<usize>,
+  consequent: Range<usize>,
+  /// `None` for an `if` without an `else`.
+  alternate: Option<Range<usize>>,
+}
+
+struct ConditionalCollector {
+  start_pos: StartSourcePos,
+  conditionals: Vec<ConditionalRanges>,
+}
+
+impl ConditionalCollector {
+  fn byte_range(&self, node: &impl SourceRangedForSpanned) -> Range<usize> {
+    node.start().as_byte_index(self.start_pos)
+      ..node.end().as_byte_index(self.start_pos)
+  }
+}
+
+impl Visit for ConditionalCollector {
+  fn visit_if_stmt(&mut self, node: &ast::IfStmt) {
+    self.conditionals.push(ConditionalRanges {
+      kind: ConditionalKind::If,
+      range: self.byte_range(node),
+      consequent: self.byte_range(&node.cons),
+      alternate: node.alt.as_ref().map(|alt| self.byte_range(alt)),
+    });
+    node.visit_children_with(self);
+  }
+
+  fn visit_cond_expr(&mut self, node: &ast::CondExpr) {
+    self.conditionals.push(ConditionalRanges {
+      kind: ConditionalKind::CondExpr,
+      range: self.byte_range(node),
+      consequent: self.byte_range(&node.cons),
+      alternate: Some(self.byte_range(&node.alt)),
+    });
+    node.visit_children_with(self);
+  }
+}
+
+fn line_char_range(
+  text_lines: &TextLines,
+  line_index: usize,
+) -> (usize, usize) {
+  let line_start_byte_offset = text_lines.line_start(line_index);
+  let line_end_byte_offset = text_lines.line_end(line_index);
+  (
+    text_lines.char_index(line_start_byte_offset),
+    text_lines.char_index(line_end_byte_offset),
+  )
+}
+
+/// Maps the counts of the lines of the runtime code to the lines of the
+/// original source.
+fn to_source_line_counts(
+  line_counts: Vec<i64>,
+  script_source: &str,
+  maybe_source_map: Option<&SourceMap>,
+) -> Vec<(usize, i64)> {
+  if let Some(source_map) = maybe_source_map {
+    let script_source_lines = script_source.lines().collect::<Vec<_>>();
+    let mut found_lines = line_counts
+      .iter()
+      .enumerate()
+      .flat_map(|(index, count)| {
+        // get all the mappings from this destination line to a different src line
+        let mut results = source_map
+          .tokens()
+          .filter(|token| {
+            let dst_line = token.get_dst_line() as usize;
+            dst_line == index && {
+              let dst_col = token.get_dst_col() as usize;
+              let content = script_source_lines
+                .get(dst_line)
+                .and_then(|line| {
+                  line.get(dst_col..std::cmp::min(dst_col + 2, line.len()))
+                })
+                .unwrap_or("");
+
+              !content.is_empty()
+                && content != "/*"
+                && content != "*/"
+                && content != "//"
+            }
+          })
+          .map(move |token| (token.get_src_line() as usize, *count))
+          .collect::<Vec<_>>();
+        // only keep the results that point at different src lines
+        results.sort_unstable_by_key(|(index, _)| *index);
+        results.dedup_by_key(|(index, _)| *index);
+        results.into_iter()
+      })
+      .collect::<Vec<(usize, i64)>>();
+
+    found_lines.sort_unstable_by_key(|(index, _)| *index);
+    // combine duplicated lines
+    for i in (1..found_lines.len()).rev() {
+      if found_lines[i].0 == found_lines[i - 1].0 {
+        found_lines[i - 1].1 += found_lines[i].1;
+        found_lines.remove(i);
+      }
+    }
+    found_lines
+  } else {
+    line_counts
+      .into_iter()
+      .enumerate()
+      .collect::<Vec<(usize, i64)>>()
+  }
+}
+
+/// Finds the lines that each test ran, from the profiles collected per test,
+/// which only hold the counts of their own test.
+fn find_covering_tests(
+  test_coverages: BTreeMap<CoverageTest, Vec<cdp::ScriptCoverage>>,
+  script_source: &str,
+  maybe_source_map: Option<&[u8]>,
+  cwd: &Url,
+) -> HashMap<usize, Vec<String>> {
+  let maybe_source_map = maybe_source_map
+    .map(|source_map| SourceMap::from_slice(source_map).unwrap());
+  let text_lines = TextLines::new(script_source);
+  let ignored_lines = find_ignored_lines(script_source, &text_lines);
+  let mut covering_tests = HashMap::<usize, Vec<String>>::new();
+  for (test, script_coverages) in test_coverages {
+    let Some(script_coverage) = merge::merge_scripts(script_coverages) else {
+      continue;
+    };
+    // A line counts as run when the innermost range that contains it was.
+    // Unlike for the totals, ranges that weren't run don't mark the lines
+    // within them as missed, as the range of the top-level code of a module
+    // has a count of zero in the profiles of its tests.
+    let line_counts = ignored_lines
+      .iter()
+      .enumerate()
+      .map(|(line_index, ignore)| {
+        if *ignore {
+          return 0;
+        }
+        let (start, end) = line_char_range(&text_lines, line_index);
+        script_coverage
+          .functions
+          .iter()
+          .flat_map(|function| &function.ranges)
+          .filter(|range| {
+            range.start_char_offset <= start && range.end_char_offset >= end
+          })
+          .min_by_key(|range| range.end_char_offset - range.start_char_offset)
+          .map(|range| range.count)
+          .unwrap_or(0)
+      })
+      .collect();
+    let origin = to_relative_path_or_remote_url(cwd, &test.origin);
+    let label = match &test.name {
+      Some(name) => format!("{name} ({origin})"),
+      None => origin,
+    };
+    for (line_index, count) in to_source_line_counts(
+      line_counts,
+      script_source,
+      maybe_source_map.as_ref(),
+    ) {
+      if count > 0 {
+        covering_tests
+          .entry(line_index)
+          .or_default()
+          .push(label.clone());
+      }
+    }
+  }
+  covering_tests
+}
+
+/// Gets the start and end of `range` in the original source.
+fn range_to_src_positions(
+  range: &cdp::CoverageRange,
+  text_lines: &TextLines,
+  maybe_source_map: &Option<SourceMap>,
+) -> (SourcePosition, SourcePosition) {
+  (
+    char_offset_to_src_position(
+      range.start_char_offset,
+      text_lines,
+      maybe_source_map,
+    ),
+    char_offset_to_src_position(
+      range.end_char_offset,
+      text_lines,
+      maybe_source_map,
+    ),
+  )
+}
+
+fn char_offset_to_src_position(
+  char_offset: usize,
+  text_lines: &TextLines,
+  maybe_source_map: &Option<SourceMap>,
+) -> SourcePosition {
+  let source_lc = text_lines
+    .line_and_column_index(text_lines.byte_index_from_char_index(char_offset));
   if let Some(source_map) = maybe_source_map.as_ref() {
     source_map
       .lookup_token(source_lc.line_index as u32, source_lc.column_index as u32)
-      .map(|token| token.get_src_line() as usize)
-      .unwrap_or(0)
+      .map(|token| SourcePosition {
+        line_index: token.get_src_line() as usize,
+        column_index: token.get_src_col() as usize,
+      })
+      .unwrap_or(SourcePosition {
+        line_index: 0,
+        column_index: 0,
+      })
   } else {
-    source_lc.line_index
+    SourcePosition {
+      line_index: source_lc.line_index,
+      column_index: source_lc.column_index,
+    }
   }
 }
 
@@ -411,8 +849,8 @@ fn collect_coverages(
   cli_options: &CliOptions,
   files: FileFlags,
   initial_cwd: &Path,
-) -> Result<Vec<cdp::ScriptCoverage>, AnyError> {
-  let mut coverages: Vec<cdp::ScriptCoverage> = Vec::new();
+) -> Result<Vec<CoverageProfile>, AnyError> {
+  let mut coverages: Vec<CoverageProfile> = Vec::new();
   let file_patterns = FilePatterns {
     base: initial_cwd.to_path_buf(),
     include: Some({
@@ -451,27 +889,26 @@ fn collect_coverages(
     let new_coverage = fs::read_to_string(file_path.as_path())
       .map_err(AnyError::from)
       .and_then(|json| {
-        serde_json::from_str::<cdp::ScriptCoverage>(&json)
-          .map_err(AnyError::from)
+        serde_json::from_str::<CoverageProfile>(&json).map_err(AnyError::from)
       })
       .with_context(|| format!("Failed reading '{}'", file_path.display()))?;
-    let url = Url::parse(&new_coverage.url)?;
+    let url = Url::parse(&new_coverage.coverage.url)?;
     if coverage_patterns.matches_specifier(&url) {
       coverages.push(new_coverage);
     }
   }
 
-  coverages.sort_by_key(|k| k.url.clone());
+  coverages.sort_by_key(|k| k.coverage.url.clone());
 
   Ok(coverages)
 }
 
 fn filter_coverages(
-  coverages: Vec<cdp::ScriptCoverage>,
+  coverages: Vec<CoverageProfile>,
   include: Vec<String>,
   exclude: Vec<String>,
   in_npm_pkg_checker: &DenoInNpmPackageChecker,
-) -> Vec<cdp::ScriptCoverage> {
+) -> Vec<CoverageProfile> {
   let include: Vec<Regex> =
     include.iter().map(|e| Regex::new(e).unwrap()).collect();
 
@@ -485,7 +922,8 @@ fn filter_coverages(
 
   coverages
     .into_iter()
-    .filter(|e| {
+    .filter(|profile| {
+      let e = &profile.coverage;
       let is_internal = e.url.starts_with("ext:")
         || e.url.ends_with("__anonymous__")
         || e.url.ends_with("$deno$test.mjs")
@@ -502,7 +940,7 @@ fn filter_coverages(
 
       (include.is_empty() || is_included) && !is_excluded && !is_internal
     })
-    .collect::<Vec<cdp::ScriptCoverage>>()
+    .collect::<Vec<CoverageProfile>>()
 }
 
 pub fn cover_files(
@@ -522,6 +960,15 @@ pub fn cover_files(
 
   assert!(!coverage_flags.files.include.is_empty());
 
+  let thresholds = cli_options
+    .resolve_coverage_options(&coverage_flags)?
+    .thresholds;
+  let changed_lines = coverage_flags
+    .diff
+    .as_ref()
+    .map(|diff| diff::ChangedLines::resolve(diff, cli_options.initial_cwd()))
+    .transpose()?;
+
   // Use the first include path as the default output path.
   let coverage_root = cli_options
     .initial_cwd()
@@ -544,9 +991,24 @@ pub fn cover_files(
     return Err(anyhow!("No covered files included in the report"));
   }
 
+  // the profiles written per test, by script url
+  let mut test_coverages =
+    HashMap::<String, BTreeMap<CoverageTest, Vec<cdp::ScriptCoverage>>>::new();
   let proc_coverages: Vec<_> = script_coverages
     .into_iter()
-    .map(|cov| ProcessCoverage { result: vec![cov] })
+    .map(|profile| {
+      if let Some(test) = profile.test {
+        test_coverages
+          .entry(profile.coverage.url.clone())
+          .or_default()
+          .entry(test)
+          .or_default()
+          .push(profile.coverage.clone());
+      }
+      ProcessCoverage {
+        result: vec![profile.coverage],
+      }
+    })
     .collect();
 
   let script_coverages = if let Some(c) = merge::merge_processes(proc_coverages)
@@ -556,9 +1018,20 @@ pub fn cover_files(
     vec![]
   };
 
-  let mut reporter = reporter::create(coverage_flags.r#type);
+  let mut reporter = reporter::create(coverage_flags.r#type.clone());
+  // the reports to check the thresholds against
+  let mut file_reports = Vec::new();
 
   let out_mode = match coverage_flags.output {
+    // the istanbul reporter writes several files to the directory
+    Some(ref path) if coverage_flags.r#type == CoverageType::Istanbul => {
+      match fs::create_dir_all(path) {
+        Ok(_) => Some(PathBuf::from(path)),
+        Err(e) => {
+          return Err(anyhow!("Failed to create output directory: {}", e));
+        }
+      }
+    }
     Some(ref path) => match File::create(path) {
       Ok(_) => Some(PathBuf::from(path)),
       Err(e) => {
@@ -567,6 +1040,8 @@ pub fn cover_files(
     },
     None => None,
   };
+  let cwd = Url::from_directory_path(cli_options.initial_cwd())
+    .map_err(|_| anyhow!("Unable to construct URL from the path of cwd"))?;
   let get_message = |specifier: &ModuleSpecifier| -> String {
     format!(
       "Failed to fetch \"{}\" from cache. Before generating coverage report, run `deno test --coverage` to ensure consistent state.",
@@ -574,11 +1049,20 @@ pub fn cover_files(
     )
   };
 
+  let mut reported_files = 0;
   for script_coverage in script_coverages {
     let module_specifier = deno_core::resolve_url_or_path(
       &script_coverage.url,
       cli_options.initial_cwd(),
     )?;
+    let maybe_changed_lines = match &changed_lines {
+      Some(changed_lines) => match changed_lines.get(&module_specifier) {
+        Some(lines) => Some(lines),
+        // the file wasn't changed
+        None => continue,
+      },
+      None => None,
+    };
 
     let maybe_file_result = file_fetcher
       .get_cached_source_or_local(&module_specifier)
@@ -629,19 +1113,169 @@ pub fn cover_files(
     };
 
     let source_map = source_map_from_code(runtime_code.as_bytes());
-    let coverage_report = generate_coverage_report(
+    let mut coverage_report = generate_coverage_report(
       &script_coverage,
       runtime_code.as_str().to_owned(),
       source_map.as_deref(),
       &out_mode,
     );
+    if let Some(test_coverages) = test_coverages.remove(&script_coverage.url) {
+      coverage_report.covering_tests = find_covering_tests(
+        test_coverages,
+        &runtime_code,
+        source_map.as_deref(),
+        &cwd,
+      );
+    }
+    if let Some(lines) = maybe_changed_lines {
+      coverage_report.retain_lines(lines);
+    }
 
     if !coverage_report.found_lines.is_empty() {
+      reported_files += 1;
       reporter.report(&coverage_report, &original_source)?;
+      if !thresholds.is_empty() {
+        file_reports.push((coverage_report, original_source.to_string()));
+      }
     }
   }
 
-  reporter.done(&coverage_root);
+  if reported_files == 0 && changed_lines.is_some() {
+    log::info!("No changed lines are covered by the coverage profiles.");
+    return Ok(());
+  }
+
+  reporter.done(&coverage_root)?;
+
+  if !thresholds.is_empty() {
+    let summary = reporter.collect_summary(&file_reports);
+    let results = threshold::check_thresholds(&thresholds, &summary);
+    reporter.report_thresholds(&results);
+    let failures = results
+      .iter()
+      .filter(|result| !result.passed())
+      .map(|result| {
+        format!(
+          "  {} coverage of {} is {:.1}%, below the threshold of {}%",
+          result.metric, result.target, result.percent, result.threshold
+        )
+      })
+      .collect::<Vec<_>>();
+    if !failures.is_empty() {
+      bail!("Coverage thresholds not met:\n{}", failures.join("\n"));
+    }
+  }
 
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn script_coverage(ranges: &[(usize, usize, i64)]) -> cdp::ScriptCoverage {
+    cdp::ScriptCoverage {
+      script_id: "1".to_string(),
+      url: "file:///a/mod.ts".to_string(),
+      functions: vec![cdp::FunctionCoverage {
+        function_name: "".to_string(),
+        ranges: ranges
+          .iter()
+          .map(|(start, end, count)| cdp::CoverageRange {
+            start_char_offset: *start,
+            end_char_offset: *end,
+            count: *count,
+          })
+          .collect(),
+        is_block_coverage: true,
+      }],
+    }
+  }
+
+  #[test]
+  fn covering_tests() {
+    let source =
+      "function a() {\n  return 1;\n}\nfunction b() {\n  return 2;\n}\n";
+    let test = |name: &str| CoverageTest {
+      origin: "file:///a/mod_test.ts".to_string(),
+      name: Some(name.to_string()),
+    };
+    let test_coverages = BTreeMap::from([
+      (test("a"), vec![script_coverage(&[(0, 58, 0), (0, 28, 1)])]),
+      (test("b"), vec![script_coverage(&[(0, 58, 0), (29, 58, 1)])]),
+      (
+        CoverageTest {
+          origin: "file:///a/mod_test.ts".to_string(),
+          name: None,
+        },
+        vec![script_coverage(&[(0, 58, 0)])],
+      ),
+    ]);
+    let covering_tests = find_covering_tests(
+      test_coverages,
+      source,
+      None,
+      &Url::parse("file:///a/").unwrap(),
+    );
+    let lines = |names: &[&str]| {
+      Some(names.iter().map(|n| n.to_string()).collect::<Vec<_>>())
+    };
+    assert_eq!(
+      covering_tests.get(&0).cloned(),
+      lines(&["a (./mod_test.ts)"])
+    );
+    assert_eq!(
+      covering_tests.get(&2).cloned(),
+      lines(&["a (./mod_test.ts)"])
+    );
+    assert_eq!(
+      covering_tests.get(&4).cloned(),
+      lines(&["b (./mod_test.ts)"])
+    );
+    assert_eq!(covering_tests.len(), 6);
+  }
+
+  #[test]
+  fn retain_lines_keeps_functions_with_changed_lines() {
+    let position = |line_index| SourcePosition {
+      line_index,
+      column_index: 0,
+    };
+    let function = |name: &str, start, end| FunctionCoverageItem {
+      name: name.to_string(),
+      line_index: start,
+      execution_count: 0,
+      start: position(start),
+      end: position(end),
+    };
+    let mut report = CoverageReport {
+      url: Url::parse("file:///a/mod.ts").unwrap(),
+      named_functions: vec![function("a", 0, 4), function("b", 6, 8)],
+      branches: vec![BranchCoverageItem {
+        line_index: 1,
+        block_number: 0,
+        branch_number: 0,
+        taken: Some(0),
+        is_hit: false,
+        start: position(1),
+        end: position(3),
+      }],
+      conditionals: Vec::new(),
+      found_lines: (0..9).map(|line_index| (line_index, 0)).collect(),
+      covering_tests: HashMap::new(),
+      output: None,
+    };
+    // a change within the body of `a`, but not on its first line
+    report.retain_lines(&HashSet::from([2]));
+    assert_eq!(
+      report
+        .named_functions
+        .iter()
+        .map(|function| function.name.as_str())
+        .collect::<Vec<_>>(),
+      vec!["a"]
+    );
+    assert_eq!(report.branches.len(), 1);
+    assert_eq!(report.found_lines, vec![(2, 0)]);
+  }
+}
@@ -145,6 +145,20 @@ impl<'rt> RangeTree<'rt> {
     ranges
   }
 
+  /// Gets the count of the innermost range of the tree that contains
+  /// `offset`, given the count of the range around the tree.
+  pub fn count_at(&self, offset: usize, parent_count: i64) -> i64 {
+    let count = parent_count + self.delta;
+    match self
+      .children
+      .iter()
+      .find(|child| child.start <= offset && offset < child.end)
+    {
+      Some(child) => child.count_at(offset, count),
+      None => count,
+    }
+  }
+
   pub fn from_sorted_ranges<'a>(
     rta: &'a RangeTreeArena<'a>,
     ranges: &[cdp::CoverageRange],
@@ -204,4 +218,28 @@ mod tests {
 
     assert_eq!(actual, expected);
   }
+
+  #[test]
+  fn count_at() {
+    let rta = RangeTreeArena::new();
+    let range =
+      |start_char_offset, end_char_offset, count| cdp::CoverageRange {
+        start_char_offset,
+        end_char_offset,
+        count,
+      };
+    let inputs = vec![
+      range(0, 20, 2),
+      range(2, 10, 1),
+      range(4, 6, 0),
+      range(12, 18, 0),
+    ];
+    let tree = RangeTree::from_sorted_ranges(&rta, &inputs).unwrap();
+    assert_eq!(tree.count_at(0, 0), 2);
+    assert_eq!(tree.count_at(2, 0), 1);
+    assert_eq!(tree.count_at(5, 0), 0);
+    assert_eq!(tree.count_at(6, 0), 1);
+    assert_eq!(tree.count_at(10, 0), 2);
+    assert_eq!(tree.count_at(17, 0), 0);
+  }
 }
@@ -1,5 +1,6 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fs;
 use std::fs::File;
@@ -9,12 +10,20 @@ use std::io::{self};
 use std::path::Path;
 use std::path::PathBuf;
 
+use deno_core::anyhow::bail;
 use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_core::serde_json::Map;
+use deno_core::serde_json::Value;
 use deno_core::url::Url;
 use deno_lib::version::DENO_VERSION_INFO;
 
+use super::threshold::ThresholdResult;
 use super::util;
+use super::ConditionalKind;
 use super::CoverageReport;
+use super::SourcePosition;
 use crate::args::CoverageType;
 use crate::colors;
 
@@ -24,12 +33,14 @@ pub struct CoverageStats<'a> {
   pub line_miss: usize,
   pub branch_hit: usize,
   pub branch_miss: usize,
+  pub function_hit: usize,
+  pub function_miss: usize,
   pub parent: Option<String>,
   pub file_text: Option<String>,
   pub report: Option<&'a CoverageReport>,
 }
 
-type CoverageSummary<'a> = HashMap<String, CoverageStats<'a>>;
+pub type CoverageSummary<'a> = HashMap<String, CoverageStats<'a>>;
 
 pub fn create(kind: CoverageType) -> Box<dyn CoverageReporter + Send> {
   match kind {
@@ -37,6 +48,8 @@ pub fn create(kind: CoverageType) -> Box<dyn CoverageReporter + Send> {
     CoverageType::Lcov => Box::new(LcovCoverageReporter::new()),
     CoverageType::Detailed => Box::new(DetailedCoverageReporter::new()),
     CoverageType::Html => Box::new(HtmlCoverageReporter::new()),
+    CoverageType::Cobertura => Box::new(CoberturaCoverageReporter::new()),
+    CoverageType::Istanbul => Box::new(IstanbulCoverageReporter::new()),
   }
 }
 
@@ -47,7 +60,13 @@ pub trait CoverageReporter {
     file_text: &str,
   ) -> Result<(), AnyError>;
 
-  fn done(&mut self, _coverage_root: &Path) {}
+  fn done(&mut self, _coverage_root: &Path) -> Result<(), AnyError> {
+    Ok(())
+  }
+
+  /// Called after `done()` with the outcome of the coverage thresholds, when
+  /// any are set.
+  fn report_thresholds(&mut self, _results: &[ThresholdResult]) {}
 
   /// Collects the coverage summary of each file or directory.
   fn collect_summary<'a>(
@@ -98,6 +117,16 @@ pub trait CoverageReporter {
         stats.branch_hit += report.branches.iter().filter(|b| b.is_hit).count();
         stats.branch_miss +=
           report.branches.iter().filter(|b| !b.is_hit).count();
+        stats.function_hit += report
+          .named_functions
+          .iter()
+          .filter(|f| f.execution_count > 0)
+          .count();
+        stats.function_miss += report
+          .named_functions
+          .iter()
+          .filter(|f| f.execution_count == 0)
+          .count();
 
         file_text = None;
         summary_path = path.parent();
@@ -188,7 +217,7 @@ impl CoverageReporter for SummaryCoverageReporter {
     Ok(())
   }
 
-  fn done(&mut self, _coverage_root: &Path) {
+  fn done(&mut self, _coverage_root: &Path) -> Result<(), AnyError> {
     let summary = self.collect_summary(&self.file_reports);
     let root_stats = summary.get("").unwrap();
 
@@ -211,6 +240,41 @@ impl CoverageReporter for SummaryCoverageReporter {
     println!("{}", separator);
     self.print_coverage_line("All files", node_max, root_stats);
     println!("{}", separator);
+    Ok(())
+  }
+
+  fn report_thresholds(&mut self, results: &[ThresholdResult]) {
+    let labels = results
+      .iter()
+      .map(|result| format!("{} {}", result.target, result.metric))
+      .collect::<Vec<_>>();
+    let label_max = labels
+      .iter()
+      .map(|label| label.len())
+      .chain(["Threshold".len()])
+      .max()
+      .unwrap();
+
+    let header = format!(
+      "{label:label_max$} | Actual % | Minimum % |",
+      label = "Threshold"
+    );
+    let separator = "-".repeat(header.len());
+    println!("{}", separator);
+    println!("{}", header);
+    println!("{}", separator);
+    for (label, result) in labels.iter().zip(results) {
+      let line = format!(
+        "{label:label_max$} | {:>8.1} | {:>9.1} |",
+        result.percent, result.threshold
+      );
+      if result.passed() {
+        println!("{}", colors::green(&line));
+      } else {
+        println!("{} {}", colors::red(&line), colors::red_bold("FAILED"));
+      }
+    }
+    println!("{}", separator);
   }
 }
 
@@ -314,6 +378,457 @@ impl CoverageReporter for LcovCoverageReporter {
   }
 }
 
+/// Opens the file given with `--output`, or stdout.
+fn open_output(output: &Option<PathBuf>) -> Result<Box<dyn Write>, Error> {
+  match output {
+    Some(path) => File::create(path).map(|f| Box::new(f) as Box<dyn Write>),
+    None => Ok(Box::new(io::stdout())),
+  }
+}
+
+fn coverage_rate(hit: usize, total: usize) -> f64 {
+  if total == 0 {
+    1.0
+  } else {
+    hit as f64 / total as f64
+  }
+}
+
+/// The lines and branches of `report` that fall within the lines
+/// `start..=end`.
+fn count_lines_and_branches(
+  report: &CoverageReport,
+  start: usize,
+  end: usize,
+) -> (usize, usize, usize, usize) {
+  let lines = report
+    .found_lines
+    .iter()
+    .filter(|(index, _)| (start..=end).contains(index));
+  let branches = report
+    .branches
+    .iter()
+    .filter(|b| (start..=end).contains(&b.line_index));
+  (
+    lines.clone().filter(|(_, count)| *count > 0).count(),
+    lines.count(),
+    branches.clone().filter(|b| b.is_hit).count(),
+    branches.count(),
+  )
+}
+
+/// Writes a single report in the Cobertura XML format, grouping the files
+/// into a package per directory.
+struct CoberturaCoverageReporter {
+  file_reports: Vec<(CoverageReport, String)>,
+}
+
+impl CoberturaCoverageReporter {
+  pub fn new() -> CoberturaCoverageReporter {
+    CoberturaCoverageReporter {
+      file_reports: Vec::new(),
+    }
+  }
+
+  fn write_class(
+    &self,
+    out: &mut dyn Write,
+    name: &str,
+    report: &CoverageReport,
+  ) -> Result<(), AnyError> {
+    let (line_hit, lines, branch_hit, branches) =
+      count_lines_and_branches(report, 0, usize::MAX);
+    writeln!(
+      out,
+      r#"        <class name="{name}" filename="{name}" line-rate="{:.4}" branch-rate="{:.4}" complexity="0">"#,
+      coverage_rate(line_hit, lines),
+      coverage_rate(branch_hit, branches),
+      name = escape_attribute(name),
+    )?;
+    writeln!(out, "          <methods>")?;
+    for function in &report.named_functions {
+      let (line_hit, lines, branch_hit, branches) = count_lines_and_branches(
+        report,
+        function.start.line_index,
+        function.end.line_index,
+      );
+      writeln!(
+        out,
+        r#"            <method name="{}" signature="" line-rate="{:.4}" branch-rate="{:.4}" complexity="0">"#,
+        escape_attribute(&function.name),
+        coverage_rate(line_hit, lines),
+        coverage_rate(branch_hit, branches),
+      )?;
+      writeln!(out, "              <lines>")?;
+      Self::write_lines(
+        out,
+        "                ",
+        report,
+        function.start.line_index,
+        function.end.line_index,
+      )?;
+      writeln!(out, "              </lines>")?;
+      writeln!(out, "            </method>")?;
+    }
+    writeln!(out, "          </methods>")?;
+    writeln!(out, "          <lines>")?;
+    Self::write_lines(out, "            ", report, 0, usize::MAX)?;
+    writeln!(out, "          </lines>")?;
+    writeln!(out, "        </class>")?;
+    Ok(())
+  }
+
+  /// Writes the `<line>` elements of the lines `start..=end` of `report`.
+  fn write_lines(
+    out: &mut dyn Write,
+    indent: &str,
+    report: &CoverageReport,
+    start: usize,
+    end: usize,
+  ) -> Result<(), AnyError> {
+    let lines = report
+      .found_lines
+      .iter()
+      .filter(|(index, _)| (start..=end).contains(index));
+    for (index, count) in lines {
+      let (_, _, branch_hit, branches) =
+        count_lines_and_branches(report, *index, *index);
+      if branches == 0 {
+        writeln!(
+          out,
+          r#"{indent}<line number="{}" hits="{}" branch="false"/>"#,
+          index + 1,
+          count
+        )?;
+      } else {
+        writeln!(
+          out,
+          r#"{indent}<line number="{}" hits="{}" branch="true" condition-coverage="{}% ({}/{})"/>"#,
+          index + 1,
+          count,
+          (coverage_rate(branch_hit, branches) * 100.0).round(),
+          branch_hit,
+          branches
+        )?;
+      }
+    }
+    Ok(())
+  }
+}
+
+impl CoverageReporter for CoberturaCoverageReporter {
+  fn report(
+    &mut self,
+    coverage_report: &CoverageReport,
+    file_text: &str,
+  ) -> Result<(), AnyError> {
+    self
+      .file_reports
+      .push((coverage_report.clone(), file_text.to_string()));
+    Ok(())
+  }
+
+  fn done(&mut self, _coverage_root: &Path) -> Result<(), AnyError> {
+    let Some((first_report, _)) = self.file_reports.first() else {
+      return Ok(());
+    };
+    let mut out = open_output(&first_report.output)?;
+    let summary = self.collect_summary(&self.file_reports);
+    let root = util::find_root(
+      self
+        .file_reports
+        .iter()
+        .map(|(report, _)| &report.url)
+        .collect(),
+    )
+    .and_then(|root| root.to_file_path().ok());
+    let root_stats = summary.get("").unwrap();
+
+    // package (directory) -> classes (files)
+    let mut packages = BTreeMap::<&str, Vec<(String, &CoverageReport)>>::new();
+    for (node, stats) in &summary {
+      let (Some(report), Some(_)) = (stats.report, &stats.file_text) else {
+        continue;
+      };
+      let package = stats.parent.as_deref().unwrap_or("");
+      packages
+        .entry(package)
+        .or_default()
+        .push((node.replace('\\', "/"), report));
+    }
+
+    writeln!(out, r#"<?xml version="1.0" ?>"#)?;
+    writeln!(
+      out,
+      r#"<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">"#
+    )?;
+    writeln!(
+      out,
+      r#"<coverage lines-valid="{}" lines-covered="{}" line-rate="{:.4}" branches-valid="{}" branches-covered="{}" branch-rate="{:.4}" timestamp="{}" complexity="0" version="{}">"#,
+      root_stats.line_hit + root_stats.line_miss,
+      root_stats.line_hit,
+      coverage_rate(
+        root_stats.line_hit,
+        root_stats.line_hit + root_stats.line_miss
+      ),
+      root_stats.branch_hit + root_stats.branch_miss,
+      root_stats.branch_hit,
+      coverage_rate(
+        root_stats.branch_hit,
+        root_stats.branch_hit + root_stats.branch_miss
+      ),
+      chrono::Utc::now().timestamp_millis(),
+      DENO_VERSION_INFO.deno,
+    )?;
+    writeln!(out, "  <sources>")?;
+    if let Some(root) = root {
+      writeln!(
+        out,
+        "    <source>{}</source>",
+        escape_attribute(&root.to_string_lossy())
+      )?;
+    }
+    writeln!(out, "  </sources>")?;
+    writeln!(out, "  <packages>")?;
+    for (package, mut classes) in packages {
+      classes.sort_by(|(a, _), (b, _)| a.cmp(b));
+      let (mut line_hit, mut lines, mut branch_hit, mut branches) =
+        (0, 0, 0, 0);
+      for (_, report) in &classes {
+        let counts = count_lines_and_branches(report, 0, usize::MAX);
+        line_hit += counts.0;
+        lines += counts.1;
+        branch_hit += counts.2;
+        branches += counts.3;
+      }
+      let package = package.replace('\\', "/");
+      writeln!(
+        out,
+        r#"    <package name="{}" line-rate="{:.4}" branch-rate="{:.4}" complexity="0">"#,
+        escape_attribute(if package.is_empty() { "." } else { &package }),
+        coverage_rate(line_hit, lines),
+        coverage_rate(branch_hit, branches),
+      )?;
+      writeln!(out, "      <classes>")?;
+      for (name, report) in classes {
+        self.write_class(&mut out, &name, report)?;
+      }
+      writeln!(out, "      </classes>")?;
+      writeln!(out, "    </package>")?;
+    }
+    writeln!(out, "  </packages>")?;
+    writeln!(out, "</coverage>")?;
+    Ok(())
+  }
+}
+
+/// Writes `coverage-final.json` and `coverage-summary.json` in the formats
+/// of Istanbul, to the directory given with `--output`.
+struct IstanbulCoverageReporter {
+  file_reports: Vec<(CoverageReport, String)>,
+}
+
+impl IstanbulCoverageReporter {
+  pub fn new() -> IstanbulCoverageReporter {
+    IstanbulCoverageReporter {
+      file_reports: Vec::new(),
+    }
+  }
+
+  fn file_coverage(report: &CoverageReport, file_text: &str) -> Value {
+    let position = |position: &SourcePosition| json!({ "line": position.line_index + 1, "column": position.column_index });
+    let location = |start: &SourcePosition, end: &SourcePosition| json!({ "start": position(start), "end": position(end) });
+    let lines = file_text.split('\n').collect::<Vec<_>>();
+
+    // every line is a statement
+    let mut statement_map = Map::new();
+    let mut statements = Map::new();
+    for (i, (index, count)) in report.found_lines.iter().enumerate() {
+      let line_length = lines
+        .get(*index)
+        .map(|line| line.trim_end_matches('\r').chars().count())
+        .unwrap_or(0);
+      statement_map.insert(
+        i.to_string(),
+        json!({
+          "start": { "line": index + 1, "column": 0 },
+          "end": { "line": index + 1, "column": line_length },
+        }),
+      );
+      statements.insert(i.to_string(), json!(count));
+    }
+
+    let mut fn_map = Map::new();
+    let mut functions = Map::new();
+    for (i, function) in report.named_functions.iter().enumerate() {
+      let loc = location(&function.start, &function.end);
+      fn_map.insert(
+        i.to_string(),
+        json!({
+          "name": function.name,
+          "decl": loc,
+          "loc": loc,
+          "line": function.line_index + 1,
+        }),
+      );
+      functions.insert(i.to_string(), json!(function.execution_count));
+    }
+
+    let mut branch_map = Map::new();
+    let mut branches = Map::new();
+    for (i, conditional) in report.conditionals.iter().enumerate() {
+      let r#type = match conditional.kind {
+        ConditionalKind::If => "if",
+        ConditionalKind::CondExpr => "cond-expr",
+      };
+      branch_map.insert(
+        i.to_string(),
+        json!({
+          "loc": location(&conditional.start, &conditional.end),
+          "type": r#type,
+          "locations": conditional
+            .arms
+            .iter()
+            .map(|arm| location(&arm.start, &arm.end))
+            .collect::<Vec<_>>(),
+          "line": conditional.line_index + 1,
+        }),
+      );
+      branches.insert(
+        i.to_string(),
+        json!(conditional
+          .arms
+          .iter()
+          .map(|arm| arm.count)
+          .collect::<Vec<_>>()),
+      );
+    }
+
+    let path = file_path_or_url(&report.url);
+    json!({
+      "path": path,
+      "statementMap": statement_map,
+      "fnMap": fn_map,
+      "branchMap": branch_map,
+      "s": statements,
+      "f": functions,
+      "b": branches,
+    })
+  }
+
+  /// Counts the arms of the conditionals of `reports` that were taken and
+  /// the ones that weren't, which are the branches of Istanbul.
+  fn count_arms<'a>(
+    reports: impl IntoIterator<Item = &'a CoverageReport>,
+  ) -> (usize, usize) {
+    let arms = reports
+      .into_iter()
+      .flat_map(|report| &report.conditionals)
+      .flat_map(|conditional| &conditional.arms);
+    let (mut hit, mut miss) = (0, 0);
+    for arm in arms {
+      if arm.count > 0 {
+        hit += 1;
+      } else {
+        miss += 1;
+      }
+    }
+    (hit, miss)
+  }
+
+  fn summary_metrics(
+    stats: &CoverageStats,
+    (arm_hit, arm_miss): (usize, usize),
+  ) -> Value {
+    let metric = |hit: usize, miss: usize| {
+      let (total, percent, _) = util::calc_coverage_display_info(hit, miss);
+      json!({
+        "total": total,
+        "covered": hit,
+        "skipped": 0,
+        "pct": (percent as f64 * 100.0).round() / 100.0,
+      })
+    };
+    let lines = metric(stats.line_hit, stats.line_miss);
+    json!({
+      "lines": lines,
+      "statements": lines,
+      "functions": metric(stats.function_hit, stats.function_miss),
+      "branches": metric(arm_hit, arm_miss),
+    })
+  }
+}
+
+impl CoverageReporter for IstanbulCoverageReporter {
+  fn report(
+    &mut self,
+    coverage_report: &CoverageReport,
+    file_text: &str,
+  ) -> Result<(), AnyError> {
+    self
+      .file_reports
+      .push((coverage_report.clone(), file_text.to_string()));
+    Ok(())
+  }
+
+  fn done(&mut self, _coverage_root: &Path) -> Result<(), AnyError> {
+    let Some((first_report, _)) = self.file_reports.first() else {
+      return Ok(());
+    };
+    let Some(output_dir) = first_report.output.clone() else {
+      bail!("The Istanbul reporter requires an --output directory");
+    };
+
+    let mut coverage_final = Map::new();
+    for (report, file_text) in &self.file_reports {
+      coverage_final.insert(
+        file_path_or_url(&report.url),
+        Self::file_coverage(report, file_text),
+      );
+    }
+
+    let summary = self.collect_summary(&self.file_reports);
+    let mut coverage_summary = Map::new();
+    coverage_summary.insert(
+      "total".to_string(),
+      Self::summary_metrics(
+        summary.get("").unwrap(),
+        Self::count_arms(self.file_reports.iter().map(|(report, _)| report)),
+      ),
+    );
+    for stats in summary.values() {
+      if let (Some(report), Some(_)) = (stats.report, &stats.file_text) {
+        coverage_summary.insert(
+          file_path_or_url(&report.url),
+          Self::summary_metrics(stats, Self::count_arms([report])),
+        );
+      }
+    }
+
+    fs::write(
+      output_dir.join("coverage-final.json"),
+      serde_json::to_string(&coverage_final)?,
+    )?;
+    fs::write(
+      output_dir.join("coverage-summary.json"),
+      serde_json::to_string(&coverage_summary)?,
+    )?;
+    log::info!(
+      "Istanbul coverage report has been generated at {}",
+      output_dir.display()
+    );
+    Ok(())
+  }
+}
+
+fn file_path_or_url(url: &Url) -> String {
+  url
+    .to_file_path()
+    .ok()
+    .and_then(|p| p.to_str().map(|p| p.to_string()))
+    .unwrap_or_else(|| url.to_string())
+}
+
 struct DetailedCoverageReporter {}
 
 impl DetailedCoverageReporter {
@@ -400,7 +915,7 @@ impl CoverageReporter for HtmlCoverageReporter {
     Ok(())
   }
 
-  fn done(&mut self, coverage_root: &Path) {
+  fn done(&mut self, coverage_root: &Path) -> Result<(), AnyError> {
     let summary = self.collect_summary(&self.file_reports);
     let now = chrono::Utc::now().to_rfc2822();
 
@@ -428,6 +943,7 @@ impl CoverageReporter for HtmlCoverageReporter {
     .unwrap();
 
     log::info!("HTML coverage report has been generated at {}", root_report);
+    Ok(())
   }
 }
 
@@ -672,6 +1188,27 @@ impl HtmlCoverageReporter {
       })
       .collect::<Vec<_>>()
       .join("\n");
+    // only collected with `deno test --coverage-per-test`
+    let line_tests = if report.covering_tests.is_empty() {
+      String::new()
+    } else {
+      let line_tests = (0..line_num)
+        .map(|i| match report.covering_tests.get(&i) {
+          Some(tests) => {
+            let count = tests.len();
+            let title = tests
+              .iter()
+              .map(|test| escape_attribute(test))
+              .collect::<Vec<_>>()
+              .join("&#10;");
+            format!("<span class='cline-tests' title='Covered by:&#10;{title}'>{count} test{}</span>", if count > 1 { "s" } else { "" })
+          }
+          None => "".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+      format!("<td class='line-tests quiet'><pre>{line_tests}</pre></td>")
+    };
 
     let file_text = file_text
       .replace('&', "&amp;")
@@ -685,6 +1222,7 @@ impl HtmlCoverageReporter {
           <td class='line-count quiet'><pre>{line_count}</pre></td>
           <td class='line-coverage quiet'><pre>{line_coverage}</pre></td>
           <td class='branch-coverage quiet'><pre>{branch_coverage}</pre></td>
+          {line_tests}
           <td class='text'><pre class='prettyprint'>{file_text}</pre></td>
         </tr>
       </table>"
@@ -734,3 +1272,154 @@ impl HtmlCoverageReporter {
     breadcrumbs_html.into_iter().collect::<Vec<_>>().join(" / ")
   }
 }
+
+/// Escapes text for an attribute value in single quotes.
+fn escape_attribute(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::tools::coverage::BranchCoverageItem;
+  use crate::tools::coverage::ConditionalArm;
+  use crate::tools::coverage::ConditionalCoverageItem;
+  use crate::tools::coverage::FunctionCoverageItem;
+
+  fn position(line_index: usize, column_index: usize) -> SourcePosition {
+    SourcePosition {
+      line_index,
+      column_index,
+    }
+  }
+
+  #[test]
+  fn istanbul_file_coverage() {
+    let url = Url::parse("file:///a/mod.ts").unwrap();
+    let report = CoverageReport {
+      url: url.clone(),
+      named_functions: vec![FunctionCoverageItem {
+        name: "add".to_string(),
+        line_index: 0,
+        execution_count: 2,
+        start: position(0, 0),
+        end: position(2, 1),
+      }],
+      branches: vec![BranchCoverageItem {
+        line_index: 1,
+        block_number: 0,
+        branch_number: 0,
+        taken: Some(1),
+        is_hit: true,
+        start: position(1, 2),
+        end: position(1, 20),
+      }],
+      conditionals: vec![ConditionalCoverageItem {
+        kind: ConditionalKind::CondExpr,
+        line_index: 1,
+        start: position(1, 9),
+        end: position(1, 19),
+        arms: vec![
+          ConditionalArm {
+            start: position(1, 13),
+            end: position(1, 14),
+            count: 2,
+          },
+          ConditionalArm {
+            start: position(1, 17),
+            end: position(1, 19),
+            count: 0,
+          },
+        ],
+      }],
+      found_lines: vec![(0, 2), (1, 2), (2, 0)],
+      covering_tests: HashMap::new(),
+      output: None,
+    };
+    let coverage = IstanbulCoverageReporter::file_coverage(
+      &report,
+      "function add() {\r\n  return a ? 1 : -1;\n}",
+    );
+    let function_loc = json!({
+      "start": { "line": 1, "column": 0 },
+      "end": { "line": 3, "column": 1 },
+    });
+    let loc = |start_column, end_column| {
+      json!({
+        "start": { "line": 2, "column": start_column },
+        "end": { "line": 2, "column": end_column },
+      })
+    };
+    assert_eq!(
+      coverage,
+      json!({
+        "path": file_path_or_url(&url),
+        "statementMap": {
+          "0": {
+            "start": { "line": 1, "column": 0 },
+            "end": { "line": 1, "column": 16 },
+          },
+          "1": {
+            "start": { "line": 2, "column": 0 },
+            "end": { "line": 2, "column": 20 },
+          },
+          "2": {
+            "start": { "line": 3, "column": 0 },
+            "end": { "line": 3, "column": 1 },
+          },
+        },
+        "fnMap": {
+          "0": {
+            "name": "add",
+            "decl": function_loc,
+            "loc": function_loc,
+            "line": 1,
+          },
+        },
+        "branchMap": {
+          "0": {
+            "loc": loc(9, 19),
+            "type": "cond-expr",
+            "locations": [loc(13, 14), loc(17, 19)],
+            "line": 2,
+          },
+        },
+        "s": { "0": 2, "1": 2, "2": 0 },
+        "f": { "0": 2 },
+        "b": { "0": [2, 0] },
+      })
+    );
+  }
+
+  #[test]
+  fn istanbul_summary_metrics() {
+    let stats = CoverageStats {
+      line_hit: 2,
+      line_miss: 1,
+      branch_hit: 1,
+      branch_miss: 1,
+      function_hit: 1,
+      ..Default::default()
+    };
+    let lines = json!({
+      "total": 3,
+      "covered": 2,
+      "skipped": 0,
+      "pct": 66.67,
+    });
+    assert_eq!(
+      IstanbulCoverageReporter::summary_metrics(&stats, (1, 1)),
+      json!({
+        "lines": lines,
+        "statements": lines,
+        "functions": { "total": 1, "covered": 1, "skipped": 0, "pct": 100.0 },
+        "branches": { "total": 2, "covered": 1, "skipped": 0, "pct": 50.0 },
+      })
+    );
+  }
+}
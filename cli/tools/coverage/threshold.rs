@@ -0,0 +1,165 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use super::reporter::CoverageStats;
+use super::util;
+use crate::args::CoverageThresholdTarget;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+  Lines,
+  Branches,
+  Functions,
+}
+
+impl fmt::Display for Metric {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Metric::Lines => write!(f, "lines"),
+      Metric::Branches => write!(f, "branches"),
+      Metric::Functions => write!(f, "functions"),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ThresholdResult {
+  pub target: String,
+  pub metric: Metric,
+  pub percent: f32,
+  pub threshold: f64,
+}
+
+impl ThresholdResult {
+  pub fn passed(&self) -> bool {
+    self.percent as f64 >= self.threshold
+  }
+}
+
+/// Compares the coverage of every target with its thresholds.
+pub fn check_thresholds(
+  targets: &[CoverageThresholdTarget],
+  summary: &HashMap<String, CoverageStats>,
+) -> Vec<ThresholdResult> {
+  let empty = CoverageStats::default();
+  let mut results = Vec::new();
+  for target in targets {
+    let within_dir;
+    let stats = match &target.dir {
+      Some(dir) => {
+        within_dir = sum_stats_within(summary, dir);
+        &within_dir
+      }
+      None => summary.get("").unwrap_or(&empty),
+    };
+    for (metric, threshold, hit, miss) in [
+      (
+        Metric::Lines,
+        target.thresholds.lines,
+        stats.line_hit,
+        stats.line_miss,
+      ),
+      (
+        Metric::Branches,
+        target.thresholds.branches,
+        stats.branch_hit,
+        stats.branch_miss,
+      ),
+      (
+        Metric::Functions,
+        target.thresholds.functions,
+        stats.function_hit,
+        stats.function_miss,
+      ),
+    ] {
+      let Some(threshold) = threshold else {
+        continue;
+      };
+      let (_, percent, _) = util::calc_coverage_display_info(hit, miss);
+      results.push(ThresholdResult {
+        target: target.name.clone(),
+        metric,
+        percent,
+        threshold: f64::from(threshold),
+      });
+    }
+  }
+  results
+}
+
+/// Adds up the stats of the files within `dir`.
+fn sum_stats_within<'a>(
+  summary: &HashMap<String, CoverageStats>,
+  dir: &Path,
+) -> CoverageStats<'a> {
+  let mut total = CoverageStats::default();
+  let files = summary.values().filter(|stats| {
+    // only files have their text in the summary
+    stats.file_text.is_some()
+      && stats
+        .report
+        .and_then(|report| report.url.to_file_path().ok())
+        .is_some_and(|path| path.starts_with(dir))
+  });
+  for stats in files {
+    total.line_hit += stats.line_hit;
+    total.line_miss += stats.line_miss;
+    total.branch_hit += stats.branch_hit;
+    total.branch_miss += stats.branch_miss;
+    total.function_hit += stats.function_hit;
+    total.function_miss += stats.function_miss;
+  }
+  total
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::args::CoverageThresholds;
+
+  #[test]
+  fn checks_thresholds() {
+    let summary = HashMap::from([(
+      "".to_string(),
+      CoverageStats {
+        line_hit: 3,
+        line_miss: 1,
+        branch_hit: 1,
+        branch_miss: 1,
+        ..Default::default()
+      },
+    )]);
+    let targets = [CoverageThresholdTarget {
+      dir: None,
+      name: "All files".to_string(),
+      thresholds: CoverageThresholds {
+        lines: Some(75),
+        branches: Some(60),
+        functions: None,
+      },
+    }];
+    let results = check_thresholds(&targets, &summary);
+    assert_eq!(
+      results,
+      vec![
+        ThresholdResult {
+          target: "All files".to_string(),
+          metric: Metric::Lines,
+          percent: 75.0,
+          threshold: 75.0,
+        },
+        ThresholdResult {
+          target: "All files".to_string(),
+          metric: Metric::Branches,
+          percent: 50.0,
+          threshold: 60.0,
+        },
+      ]
+    );
+    assert!(results[0].passed());
+    assert!(!results[1].passed());
+  }
+}
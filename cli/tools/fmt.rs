@@ -29,14 +29,17 @@ use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::futures;
 use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
 use deno_core::unsync::spawn_blocking;
 use deno_core::url::Url;
 use log::debug;
 use log::info;
 use log::warn;
+use serde::Serialize;
 
 use crate::args::CliOptions;
 use crate::args::Flags;
+use crate::args::FmtCheckOutput;
 use crate::args::FmtFlags;
 use crate::args::FmtOptions;
 use crate::args::FmtOptionsConfig;
@@ -49,8 +52,13 @@ use crate::colors;
 use crate::factory::CliFactory;
 use crate::sys::CliSys;
 use crate::util::diff::diff;
+use crate::util::diff::line_changes;
+use crate::util::diff::split_lines;
+use crate::util::diff::unified_diff;
 use crate::util::file_watcher;
 use crate::util::fs::canonicalize_path;
+use crate::util::git;
+use crate::util::git::ChangedPaths;
 use crate::util::path::get_extension;
 
 /// Format JavaScript/TypeScript files.
@@ -163,11 +171,19 @@ fn resolve_paths_with_options_batches(
 ) -> Result<Vec<PathsWithOptions>, AnyError> {
   let members_fmt_options =
     cli_options.resolve_fmt_options_for_members(fmt_flags)?;
+  let maybe_changed_paths = ChangedPaths::resolve(
+    cli_options.initial_cwd(),
+    fmt_flags.staged,
+    fmt_flags.changed_since.as_deref(),
+  )?;
   let mut paths_with_options_batches =
     Vec::with_capacity(members_fmt_options.len());
   for (_ctx, member_fmt_options) in members_fmt_options {
-    let files =
+    let mut files =
       collect_fmt_files(cli_options, member_fmt_options.files.clone());
+    if let Some(changed_paths) = &maybe_changed_paths {
+      files.retain(|path| changed_paths.contains(path));
+    }
     if !files.is_empty() {
       paths_with_options_batches.push(PathsWithOptions {
         base: member_fmt_options.files.base.clone(),
@@ -176,7 +192,8 @@ fn resolve_paths_with_options_batches(
       });
     }
   }
-  if paths_with_options_batches.is_empty() {
+  // having no changed files is fine, like in a pre-commit hook
+  if paths_with_options_batches.is_empty() && maybe_changed_paths.is_none() {
     return Err(anyhow!("No target files found."));
   }
   Ok(paths_with_options_batches)
@@ -189,7 +206,17 @@ async fn format_files(
   paths_with_options_batches: Vec<PathsWithOptions>,
 ) -> Result<(), AnyError> {
   let formatter: Box<dyn Formatter> = if fmt_flags.check {
-    Box::new(CheckFormatter::default())
+    Box::new(CheckFormatter {
+      staged: fmt_flags.staged,
+      output: fmt_flags.output,
+      base_dir: check_output_base_dir(
+        fmt_flags.output,
+        cli_options.initial_cwd(),
+      ),
+      ..Default::default()
+    })
+  } else if fmt_flags.staged {
+    Box::new(StagedFormatter::default())
   } else {
     Box::new(RealFormatter::default())
   };
@@ -276,6 +303,7 @@ fn format_markdown(
           | "yml"
           | "yaml"
           | "sql"
+          | "toml"
       ) {
         // It's important to tell dprint proper file extension, otherwise
         // it might parse the file twice.
@@ -305,6 +333,13 @@ fn format_markdown(
             }
           }
           "yml" | "yaml" => format_yaml(text, fmt_options),
+          "toml" => {
+            if unstable_options.toml {
+              format_toml(&fake_filename, text, fmt_options)
+            } else {
+              Ok(None)
+            }
+          }
           "sql" => {
             if unstable_options.sql {
               format_sql(text, fmt_options)
@@ -516,6 +551,30 @@ pub fn format_html(
   })
 }
 
+fn format_toml(
+  file_path: &Path,
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Option<String>, AnyError> {
+  let ignore_file = file_text
+    .lines()
+    .take_while(|line| line.starts_with('#'))
+    .any(|line| {
+      line
+        .strip_prefix('#')
+        .unwrap()
+        .trim()
+        .starts_with("deno-fmt-ignore-file")
+    });
+
+  if ignore_file {
+    return Ok(None);
+  }
+
+  let config = get_resolved_toml_config(fmt_options);
+  dprint_plugin_toml::format_text(file_path, file_text, &config)
+}
+
 pub fn format_sql(
   file_text: &str,
   fmt_options: &FmtOptionsConfig,
@@ -561,7 +620,8 @@ pub fn format_sql(
   })
 }
 
-/// Formats a single TS, TSX, JS, JSX, JSONC, JSON, MD, IPYNB or SQL file.
+/// Formats a single TS, TSX, JS, JSX, JSONC, JSON, MD, IPYNB, SQL or TOML
+/// file.
 pub fn format_file(
   file_path: &Path,
   file_text: &str,
@@ -590,6 +650,13 @@ pub fn format_file(
       }
     }
     "yml" | "yaml" => format_yaml(file_text, fmt_options),
+    "toml" => {
+      if unstable_options.toml {
+        format_toml(file_path, file_text, fmt_options)
+      } else {
+        Ok(None)
+      }
+    }
     "ipynb" => dprint_plugin_jupyter::format_text(
       file_text,
       |file_path: &Path, file_text: String| {
@@ -641,8 +708,119 @@ trait Formatter {
 
 #[derive(Default)]
 struct CheckFormatter {
+  /// Checks the staged contents of the files instead.
+  staged: bool,
+  output: FmtCheckOutput,
+  /// Paths in the diff and JSON output are relative to this directory.
+  base_dir: PathBuf,
   not_formatted_files_count: Arc<AtomicUsize>,
   checked_files_count: Arc<AtomicUsize>,
+  /// The files that aren't formatted, for the diff and JSON output.
+  unformatted_files: Arc<Mutex<Vec<UnformattedFile>>>,
+  /// The files that failed to be checked, for the JSON output.
+  failed_files: Arc<Mutex<Vec<FmtCheckJsonError>>>,
+}
+
+/// The diff output is relative to the root of the git repository, like the
+/// output of `git diff`, so that `git apply` accepts it from any directory
+/// of the repository. The JSON output and diffs outside of a repository are
+/// relative to the current directory.
+fn check_output_base_dir(output: FmtCheckOutput, cwd: &Path) -> PathBuf {
+  if output == FmtCheckOutput::Diff {
+    // walk up from the cwd instead of using the root that git prints, which
+    // is canonicalized while the formatted paths might not be
+    if let Ok(prefix) = git::repo_prefix(cwd) {
+      if let Some(root) = cwd.ancestors().nth(prefix.components().count()) {
+        return root.to_path_buf();
+      }
+    }
+  }
+  cwd.to_path_buf()
+}
+
+fn relative_path(base_dir: &Path, file_path: &Path) -> String {
+  let path = file_path.strip_prefix(base_dir).unwrap_or(file_path);
+  path.to_string_lossy().replace('\\', "/")
+}
+
+struct UnformattedFile {
+  path: String,
+  text: String,
+  formatted_text: String,
+}
+
+#[derive(Serialize)]
+struct FmtCheckJsonReport {
+  files: Vec<FmtCheckJsonFile>,
+  errors: Vec<FmtCheckJsonError>,
+}
+
+#[derive(Serialize)]
+struct FmtCheckJsonFile {
+  path: String,
+  changes: Vec<FmtCheckJsonChange>,
+}
+
+/// Lines `startLine` to `endLine` of the file are replaced by `newText`,
+/// with 1-based and inclusive line numbers. When `endLine` is before
+/// `startLine`, `newText` is inserted before `startLine`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FmtCheckJsonChange {
+  start_line: usize,
+  end_line: usize,
+  new_text: String,
+}
+
+#[derive(Serialize)]
+struct FmtCheckJsonError {
+  path: String,
+  message: String,
+}
+
+impl CheckFormatter {
+  fn print_output(&self) {
+    let mut unformatted_files =
+      std::mem::take(&mut *self.unformatted_files.lock());
+    unformatted_files.sort_by(|a, b| a.path.cmp(&b.path));
+    let output = match self.output {
+      FmtCheckOutput::Pretty => return,
+      FmtCheckOutput::Diff => unformatted_files
+        .iter()
+        .map(|file| unified_diff(&file.path, &file.text, &file.formatted_text))
+        .collect::<String>(),
+      FmtCheckOutput::Json => {
+        let mut errors = std::mem::take(&mut *self.failed_files.lock());
+        errors.sort_by(|a, b| a.path.cmp(&b.path));
+        let files = unformatted_files
+          .iter()
+          .map(|file| {
+            let edit_lines = split_lines(&file.formatted_text);
+            let changes = line_changes(&file.text, &file.formatted_text)
+              .into_iter()
+              .map(|change| FmtCheckJsonChange {
+                start_line: change.orig.start + 1,
+                end_line: change.orig.end,
+                new_text: edit_lines[change.edit].concat(),
+              })
+              .collect();
+            FmtCheckJsonFile {
+              path: file.path.clone(),
+              changes,
+            }
+          })
+          .collect();
+        let report = FmtCheckJsonReport { files, errors };
+        let mut json = serde_json::to_string_pretty(&report).unwrap();
+        json.push('\n');
+        json
+      }
+    };
+    #[allow(clippy::print_stdout)]
+    {
+      print!("{output}");
+    }
+  }
 }
 
 #[async_trait]
@@ -659,11 +837,23 @@ impl Formatter for CheckFormatter {
     let output_lock = Arc::new(Mutex::new(0));
 
     run_parallelized(paths, {
+      let staged = self.staged;
+      let output = self.output;
       let not_formatted_files_count = self.not_formatted_files_count.clone();
       let checked_files_count = self.checked_files_count.clone();
+      let unformatted_files = self.unformatted_files.clone();
+      let failed_files = self.failed_files.clone();
+      let base_dir = self.base_dir.clone();
       move |file_path| {
         checked_files_count.fetch_add(1, Ordering::Relaxed);
-        let file_text = read_file_contents(&file_path)?.text;
+        let file_text = if staged {
+          match read_index_entry(&file_path)? {
+            Some(entry) => deno_ast::strip_bom(entry.text),
+            None => return Ok(()),
+          }
+        } else {
+          read_file_contents(&file_path)?.text
+        };
 
         // skip checking the file if we know it's formatted
         if incremental_cache.is_file_same(&file_path, &file_text) {
@@ -679,6 +869,14 @@ impl Formatter for CheckFormatter {
         ) {
           Ok(Some(formatted_text)) => {
             not_formatted_files_count.fetch_add(1, Ordering::Relaxed);
+            if output != FmtCheckOutput::Pretty {
+              unformatted_files.lock().push(UnformattedFile {
+                path: relative_path(&base_dir, &file_path),
+                text: file_text,
+                formatted_text,
+              });
+              return Ok(());
+            }
             let _g = output_lock.lock();
             let diff = diff(&file_text, &formatted_text);
             info!("");
@@ -695,6 +893,12 @@ impl Formatter for CheckFormatter {
           }
           Err(e) => {
             not_formatted_files_count.fetch_add(1, Ordering::Relaxed);
+            if output == FmtCheckOutput::Json {
+              failed_files.lock().push(FmtCheckJsonError {
+                path: relative_path(&base_dir, &file_path),
+                message: e.to_string(),
+              });
+            }
             let _g = output_lock.lock();
             warn!("Error checking: {}", file_path.to_string_lossy());
             warn!(
@@ -722,6 +926,7 @@ impl Formatter for CheckFormatter {
   }
 
   fn finish(&self) -> Result<(), AnyError> {
+    self.print_output();
     let not_formatted_files_count =
       self.not_formatted_files_count.load(Ordering::Relaxed);
     let checked_files_count = self.checked_files_count.load(Ordering::Relaxed);
@@ -830,6 +1035,130 @@ impl Formatter for RealFormatter {
   }
 }
 
+/// Formats the staged contents of the files and stages the result. The
+/// working tree file is formatted on its own, so that the unstaged changes
+/// of a partially staged file stay unstaged.
+#[derive(Default)]
+struct StagedFormatter {
+  formatted_files_count: Arc<AtomicUsize>,
+  checked_files_count: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Formatter for StagedFormatter {
+  async fn handle_files(
+    &self,
+    paths: Vec<PathBuf>,
+    fmt_options: FmtOptionsConfig,
+    unstable_options: UnstableFmtOptions,
+    _incremental_cache: Arc<IncrementalCache>,
+    ext: Option<String>,
+  ) -> Result<(), AnyError> {
+    // prevent threads outputting at the same time
+    let output_lock = Arc::new(Mutex::new(0));
+    // git locks the index while updating it, so update it one file at a time
+    let index_lock = Arc::new(Mutex::new(()));
+
+    run_parallelized(paths, {
+      let formatted_files_count = self.formatted_files_count.clone();
+      let checked_files_count = self.checked_files_count.clone();
+      move |file_path| {
+        checked_files_count.fetch_add(1, Ordering::Relaxed);
+        let Some(mut entry) = read_index_entry(&file_path)? else {
+          return Ok(());
+        };
+        let format_text = |file_text: &str| {
+          format_ensure_stable(&file_path, file_text, |file_path, file_text| {
+            format_file(
+              file_path,
+              file_text,
+              &fmt_options,
+              &unstable_options,
+              ext.clone(),
+            )
+          })
+        };
+        let had_bom = entry.text.starts_with('\u{FEFF}');
+        let staged_text = deno_ast::strip_bom(std::mem::take(&mut entry.text));
+        let file_contents = read_file_contents(&file_path)?;
+        let result = format_text(&staged_text).and_then(|formatted_staged| {
+          let formatted_file = if file_contents.text == staged_text {
+            formatted_staged.clone()
+          } else {
+            format_text(&file_contents.text)?
+          };
+          Ok((formatted_staged, formatted_file))
+        });
+        match result {
+          Ok((None, None)) => {}
+          Ok((formatted_staged, formatted_file)) => {
+            if let Some(formatted_text) = formatted_staged {
+              entry.text = if had_bom {
+                format!("\u{FEFF}{formatted_text}")
+              } else {
+                formatted_text
+              };
+              let _g = index_lock.lock();
+              git::write_index_entry(index_dir(&file_path), &entry)?;
+            }
+            if let Some(formatted_text) = formatted_file {
+              write_file_contents(
+                &file_path,
+                FileContents {
+                  had_bom: file_contents.had_bom,
+                  text: formatted_text,
+                },
+              )?;
+            }
+            formatted_files_count.fetch_add(1, Ordering::Relaxed);
+            let _g = output_lock.lock();
+            info!("{}", file_path.to_string_lossy());
+          }
+          Err(e) => {
+            let _g = output_lock.lock();
+            log::error!("Error formatting: {}", file_path.to_string_lossy());
+            log::error!("   {e}");
+          }
+        }
+        Ok(())
+      }
+    })
+    .await?;
+    Ok(())
+  }
+
+  fn finish(&self) -> Result<(), AnyError> {
+    let formatted_files_count =
+      self.formatted_files_count.load(Ordering::Relaxed);
+    debug!(
+      "Formatted {} {}",
+      formatted_files_count,
+      files_str(formatted_files_count),
+    );
+
+    let checked_files_count = self.checked_files_count.load(Ordering::Relaxed);
+    info!(
+      "Checked {} {}",
+      checked_files_count,
+      files_str(checked_files_count)
+    );
+    Ok(())
+  }
+}
+
+fn index_dir(file_path: &Path) -> &Path {
+  file_path.parent().unwrap_or(file_path)
+}
+
+/// Reads the staged contents of a file, or `None` when it isn't in the index.
+fn read_index_entry(
+  file_path: &Path,
+) -> Result<Option<git::IndexEntry>, AnyError> {
+  git::read_index_entry(index_dir(file_path), file_path).with_context(|| {
+    format!("Error reading {} from the git index", file_path.display())
+  })
+}
+
 /// When storing any formatted text in the incremental cache, we want
 /// to ensure that anything stored when formatted will have itself as
 /// the output as well. This is to prevent "double format" issues where
@@ -1133,6 +1462,27 @@ fn get_resolved_markup_fmt_config(
   }
 }
 
+fn get_resolved_toml_config(
+  options: &FmtOptionsConfig,
+) -> dprint_plugin_toml::configuration::Configuration {
+  let mut builder =
+    dprint_plugin_toml::configuration::ConfigurationBuilder::new();
+
+  if let Some(use_tabs) = options.use_tabs {
+    builder.use_tabs(use_tabs);
+  }
+
+  if let Some(line_width) = options.line_width {
+    builder.line_width(line_width);
+  }
+
+  if let Some(indent_width) = options.indent_width {
+    builder.indent_width(indent_width);
+  }
+
+  builder.build()
+}
+
 fn get_resolved_yaml_config(
   options: &FmtOptionsConfig,
 ) -> pretty_yaml::config::FormatOptions {
@@ -1285,6 +1635,7 @@ fn is_supported_ext_fmt(path: &Path) -> bool {
         | "yaml"
         | "ipynb"
         | "sql"
+        | "toml"
     )
   })
 }
@@ -1350,6 +1701,8 @@ mod test {
     assert!(is_supported_ext_fmt(Path::new("foo.sQl")));
     assert!(is_supported_ext_fmt(Path::new("foo.sqL")));
     assert!(is_supported_ext_fmt(Path::new("foo.SQL")));
+    assert!(is_supported_ext_fmt(Path::new("foo.toml")));
+    assert!(is_supported_ext_fmt(Path::new("Cargo.TOML")));
   }
 
   #[test]
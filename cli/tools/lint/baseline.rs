@@ -0,0 +1,298 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_ast::SourceRanged;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::url::Url;
+use deno_lint::diagnostic::LintDiagnostic;
+use serde::Deserialize;
+use serde::Serialize;
+
+const BASELINE_VERSION: u8 = 1;
+
+/// Snippets are cut to this many characters, so that a diagnostic spanning
+/// a large node doesn't bloat the baseline.
+const MAX_SNIPPET_LEN: usize = 120;
+
+/// The diagnostics recorded with `--baseline=<file>`, which later runs don't
+/// report.
+#[derive(Debug, Serialize, Deserialize)]
+struct LintBaselineFile {
+  version: u8,
+  diagnostics: Vec<BaselineEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BaselineEntry {
+  rule: String,
+  /// The linted file, relative to the directory of the baseline file.
+  file: String,
+  snippet: String,
+  /// How many diagnostics of the rule have the same snippet in the file.
+  count: usize,
+}
+
+/// Identifies a diagnostic without its position, so that lines added or
+/// removed above it don't break the matching.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Fingerprint {
+  file: String,
+  rule: String,
+  snippet: String,
+}
+
+/// Leaves out the diagnostics recorded in a baseline file, or records the
+/// diagnostics when the file doesn't exist yet.
+///
+/// This works with the incremental lint cache, because the cache only skips
+/// files that had no diagnostics at all, so files with diagnostics of the
+/// baseline are linted again on every run.
+#[derive(Debug)]
+pub struct LintBaseline {
+  path: PathBuf,
+  base_dir: PathBuf,
+  update: bool,
+  /// Whether the baseline file is created by this run.
+  recording: bool,
+  /// The diagnostics of the baseline that weren't found yet.
+  remaining: BTreeMap<Fingerprint, usize>,
+  /// The diagnostics of the baseline that were found in this run.
+  found: BTreeMap<Fingerprint, usize>,
+  /// Files that were linted, with a relative path like the baseline.
+  linted_files: HashSet<String>,
+  /// Files that failed to be linted, whose entries can't be pruned.
+  failed_files: HashSet<String>,
+}
+
+impl LintBaseline {
+  pub fn load(path: PathBuf, update: bool) -> Result<Self, AnyError> {
+    let base_dir = path
+      .parent()
+      .map(|dir| dir.to_path_buf())
+      .unwrap_or_default();
+    let (recording, remaining) = match std::fs::read_to_string(&path) {
+      Ok(text) => {
+        let file: LintBaselineFile =
+          serde_json::from_str(&text).with_context(|| {
+            format!("Invalid lint baseline '{}'", path.display())
+          })?;
+        if file.version != BASELINE_VERSION {
+          bail!(
+            "Lint baseline '{}' has unsupported version {}. Delete it to record it again",
+            path.display(),
+            file.version
+          );
+        }
+        let mut remaining = BTreeMap::new();
+        for entry in file.diagnostics {
+          let fingerprint = Fingerprint {
+            file: entry.file,
+            rule: entry.rule,
+            snippet: entry.snippet,
+          };
+          *remaining.entry(fingerprint).or_default() += entry.count;
+        }
+        (false, remaining)
+      }
+      Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+        (true, BTreeMap::new())
+      }
+      Err(err) => {
+        return Err(err).with_context(|| {
+          format!("Failed to read lint baseline '{}'", path.display())
+        })
+      }
+    };
+    Ok(Self {
+      path,
+      base_dir,
+      update,
+      recording,
+      remaining,
+      found: BTreeMap::new(),
+      linted_files: HashSet::new(),
+      failed_files: HashSet::new(),
+    })
+  }
+
+  pub fn mark_linted(&mut self, file_path: &Path) {
+    let file = self.relative_path(file_path);
+    self.linted_files.insert(file);
+  }
+
+  pub fn mark_failed(&mut self, file_path: &Path) {
+    let file = self.relative_path(file_path);
+    self.failed_files.insert(file);
+  }
+
+  /// Removes the diagnostics that are in the baseline, returning the new
+  /// ones.
+  pub fn filter(
+    &mut self,
+    diagnostics: Vec<LintDiagnostic>,
+  ) -> Vec<LintDiagnostic> {
+    let mut new_diagnostics = Vec::new();
+    for diagnostic in diagnostics {
+      let fingerprint = self.fingerprint(&diagnostic);
+      let in_baseline = self.recording
+        || match self.remaining.get_mut(&fingerprint) {
+          Some(count) if *count > 0 => {
+            *count -= 1;
+            true
+          }
+          _ => false,
+        };
+      if in_baseline {
+        *self.found.entry(fingerprint).or_default() += 1;
+      } else {
+        new_diagnostics.push(diagnostic);
+      }
+    }
+    new_diagnostics
+  }
+
+  /// Writes the baseline when it was recorded or updated by this run.
+  pub fn finish(&self) -> Result<(), AnyError> {
+    let display_path = self.display_path();
+    if self.recording {
+      let count = self.found.values().sum::<usize>();
+      self.write(self.found.iter())?;
+      log::info!(
+        "Created lint baseline '{display_path}' with {count} {}",
+        if count == 1 {
+          "diagnostic"
+        } else {
+          "diagnostics"
+        }
+      );
+      return Ok(());
+    }
+
+    // entries of files that weren't linted successfully are kept, because
+    // it's unknown whether they were fixed
+    let is_unknown = |fingerprint: &Fingerprint| {
+      !self.linted_files.contains(&fingerprint.file)
+        || self.failed_files.contains(&fingerprint.file)
+    };
+    let fixed_count = self
+      .remaining
+      .iter()
+      .filter(|(fingerprint, _)| !is_unknown(fingerprint))
+      .map(|(_, count)| count)
+      .sum::<usize>();
+    if fixed_count == 0 {
+      return Ok(());
+    }
+    let diagnostics_text = if fixed_count == 1 {
+      "diagnostic"
+    } else {
+      "diagnostics"
+    };
+    if !self.update {
+      log::info!(
+        "{fixed_count} {diagnostics_text} of lint baseline '{display_path}' {} fixed. Run with --update-baseline to remove {}.",
+        if fixed_count == 1 { "was" } else { "were" },
+        if fixed_count == 1 { "it" } else { "them" },
+      );
+      return Ok(());
+    }
+    let mut kept = self.found.clone();
+    for (fingerprint, count) in &self.remaining {
+      if *count > 0 && is_unknown(fingerprint) {
+        *kept.entry(fingerprint.clone()).or_default() += count;
+      }
+    }
+    self.write(kept.iter())?;
+    log::info!(
+      "Removed {fixed_count} fixed {diagnostics_text} from lint baseline '{display_path}'"
+    );
+    Ok(())
+  }
+
+  fn write<'a>(
+    &self,
+    entries: impl Iterator<Item = (&'a Fingerprint, &'a usize)>,
+  ) -> Result<(), AnyError> {
+    let file = LintBaselineFile {
+      version: BASELINE_VERSION,
+      diagnostics: entries
+        .filter(|(_, count)| **count > 0)
+        .map(|(fingerprint, count)| BaselineEntry {
+          rule: fingerprint.rule.clone(),
+          file: fingerprint.file.clone(),
+          snippet: fingerprint.snippet.clone(),
+          count: *count,
+        })
+        .collect(),
+    };
+    let mut json = serde_json::to_string_pretty(&file)?;
+    json.push('\n');
+    std::fs::write(&self.path, json).with_context(|| {
+      format!("Failed to write lint baseline '{}'", self.path.display())
+    })
+  }
+
+  fn fingerprint(&self, diagnostic: &LintDiagnostic) -> Fingerprint {
+    let file = match diagnostic.specifier.to_file_path() {
+      Ok(path) => self.relative_path(&path),
+      Err(()) => diagnostic.specifier.to_string(),
+    };
+    let snippet = diagnostic
+      .range
+      .as_ref()
+      .map(|range| normalize_snippet(range.range.text_fast(&range.text_info)))
+      .unwrap_or_default();
+    Fingerprint {
+      file,
+      rule: diagnostic.details.code.to_string(),
+      snippet,
+    }
+  }
+
+  fn relative_path(&self, path: &Path) -> String {
+    let path = path.strip_prefix(&self.base_dir).unwrap_or(path);
+    path.to_string_lossy().replace('\\', "/")
+  }
+
+  fn display_path(&self) -> String {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    Url::from_file_path(&self.path)
+      .ok()
+      .and_then(|url| {
+        let cwd = Url::from_directory_path(cwd).ok()?;
+        cwd.make_relative(&url)
+      })
+      .unwrap_or_else(|| self.path.display().to_string())
+  }
+}
+
+/// Collapses whitespace, so that reindenting code keeps matching the
+/// baseline.
+fn normalize_snippet(text: &str) -> String {
+  let mut snippet = text.split_whitespace().collect::<Vec<_>>().join(" ");
+  if let Some((index, _)) = snippet.char_indices().nth(MAX_SNIPPET_LEN) {
+    snippet.truncate(index);
+  }
+  snippet
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn normalizes_snippets() {
+    assert_eq!(
+      normalize_snippet("function  foo() {\n    var a = 1;\n}"),
+      "function foo() { var a = 1; }"
+    );
+    assert_eq!(normalize_snippet(&"a".repeat(200)).len(), MAX_SNIPPET_LEN);
+  }
+}
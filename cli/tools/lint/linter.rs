@@ -31,6 +31,7 @@ use super::plugins::PluginHostProxy;
 use super::rules::FileOrPackageLintRule;
 use super::rules::PackageLintRule;
 use super::ConfiguredRules;
+use crate::cache::LazyGraphSourceParser;
 use crate::sys::CliSys;
 use crate::util::fs::specifier_from_file_path;
 use crate::util::text_encoding::Utf16Map;
@@ -87,11 +88,18 @@ impl CliLinter {
   pub fn lint_package(
     &self,
     graph: &ModuleGraph,
+    source_parser: LazyGraphSourceParser,
     entrypoints: &[ModuleSpecifier],
+    files: &HashSet<ModuleSpecifier>,
   ) -> Vec<LintDiagnostic> {
     let mut diagnostics = Vec::new();
     for rule in &self.package_rules {
-      diagnostics.extend(rule.lint_package(graph, entrypoints));
+      diagnostics.extend(rule.lint_package(
+        graph,
+        source_parser,
+        entrypoints,
+        files,
+      ));
     }
     diagnostics
   }
@@ -7,10 +7,12 @@ use std::collections::HashSet;
 use std::fs;
 use std::io::stdin;
 use std::io::Read;
+use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
 
+use baseline::LintBaseline;
 use deno_ast::ModuleSpecifier;
 use deno_ast::ParsedSource;
 use deno_config::deno_json::LintRulesConfig;
@@ -25,6 +27,7 @@ use deno_core::parking_lot::Mutex;
 use deno_core::serde_json;
 use deno_core::unsync::future::LocalFutureExt;
 use deno_core::unsync::future::SharedLocal;
+use deno_graph::GraphKind;
 use deno_graph::ModuleGraph;
 use deno_lib::util::hash::FastInsecureHasher;
 use deno_lint::diagnostic::LintDiagnostic;
@@ -42,19 +45,24 @@ use crate::args::WorkspaceLintOptions;
 use crate::cache::CacheDBHash;
 use crate::cache::Caches;
 use crate::cache::IncrementalCache;
+use crate::cache::LazyGraphSourceParser;
+use crate::cache::ParsedSourceCache;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::graph_util::ModuleGraphCreator;
+use crate::graph_util::NpmCachingStrategy;
 use crate::sys::CliSys;
 use crate::tools::fmt::run_parallelized;
 use crate::util::display;
 use crate::util::file_watcher;
 use crate::util::file_watcher::WatcherCommunicator;
 use crate::util::fs::canonicalize_path;
+use crate::util::git::ChangedPaths;
 use crate::util::path::is_script_ext;
 use crate::util::sync::AtomicFlag;
 
 mod ast_buffer;
+mod baseline;
 mod linter;
 mod plugins;
 mod reporters;
@@ -85,6 +93,9 @@ pub async fn lint(
 
     return lint_with_watch(flags, lint_flags).await;
   }
+  if lint_flags.baseline.is_some() && lint_flags.is_stdin() {
+    return Err(anyhow!("Lint baseline on standard input is not supported."));
+  }
 
   let factory = CliFactory::from_flags(flags);
   let cli_options = factory.cli_options()?;
@@ -106,10 +117,12 @@ pub async fn lint(
       factory.caches()?.clone(),
       lint_rule_provider,
       factory.module_graph_creator().await?.clone(),
+      factory.parsed_source_cache().clone(),
       tsconfig_resolver.clone(),
       cli_options.start_dir.clone(),
       &workspace_lint_options,
-    );
+      cli_options.initial_cwd(),
+    )?;
     let paths_with_options_batches =
       resolve_paths_with_options_batches(cli_options, &lint_flags)?;
     for paths_with_options in paths_with_options_batches {
@@ -122,7 +135,7 @@ pub async fn lint(
         )
         .await?;
     }
-    linter.finish()
+    linter.finish()?
   };
   if !success {
     deno_runtime::exit(1);
@@ -165,10 +178,12 @@ async fn lint_with_watch_inner(
     factory.caches()?.clone(),
     factory.lint_rule_provider().await?,
     factory.module_graph_creator().await?.clone(),
+    factory.parsed_source_cache().clone(),
     factory.tsconfig_resolver()?.clone(),
     cli_options.start_dir.clone(),
     &cli_options.resolve_workspace_lint_options(&lint_flags)?,
-  );
+    cli_options.initial_cwd(),
+  )?;
   for paths_with_options in paths_with_options_batches {
     linter
       .lint_files(
@@ -180,7 +195,7 @@ async fn lint_with_watch_inner(
       .await?;
   }
 
-  linter.finish();
+  linter.finish()?;
 
   Ok(())
 }
@@ -220,10 +235,18 @@ fn resolve_paths_with_options_batches(
 ) -> Result<Vec<PathsWithOptions>, AnyError> {
   let members_lint_options =
     cli_options.resolve_lint_options_for_members(lint_flags)?;
+  let maybe_changed_paths = ChangedPaths::resolve(
+    cli_options.initial_cwd(),
+    lint_flags.staged,
+    lint_flags.changed_since.as_deref(),
+  )?;
   let mut paths_with_options_batches =
     Vec::with_capacity(members_lint_options.len());
   for (dir, lint_options) in members_lint_options {
-    let files = collect_lint_files(cli_options, lint_options.files.clone());
+    let mut files = collect_lint_files(cli_options, lint_options.files.clone());
+    if let Some(changed_paths) = &maybe_changed_paths {
+      files.retain(|path| changed_paths.contains(path));
+    }
     if !files.is_empty() {
       paths_with_options_batches.push(PathsWithOptions {
         dir,
@@ -232,7 +255,8 @@ fn resolve_paths_with_options_batches(
       });
     }
   }
-  if paths_with_options_batches.is_empty() {
+  // having no changed files is fine, like in a pre-commit hook
+  if paths_with_options_batches.is_empty() && maybe_changed_paths.is_none() {
     return Err(anyhow!("No target files found."));
   }
   Ok(paths_with_options_batches)
@@ -245,9 +269,11 @@ struct WorkspaceLinter {
   caches: Arc<Caches>,
   lint_rule_provider: LintRuleProvider,
   module_graph_creator: Arc<ModuleGraphCreator>,
+  parsed_source_cache: Arc<ParsedSourceCache>,
   tsconfig_resolver: Arc<TsConfigResolver>,
   workspace_dir: Arc<WorkspaceDirectory>,
   reporter_lock: Arc<Mutex<Box<dyn LintReporter + Send>>>,
+  maybe_baseline: Option<Arc<Mutex<LintBaseline>>>,
   workspace_module_graph: Option<WorkspaceModuleGraphFuture>,
   has_error: Arc<AtomicFlag>,
   file_count: usize,
@@ -258,23 +284,36 @@ impl WorkspaceLinter {
     caches: Arc<Caches>,
     lint_rule_provider: LintRuleProvider,
     module_graph_creator: Arc<ModuleGraphCreator>,
+    parsed_source_cache: Arc<ParsedSourceCache>,
     tsconfig_resolver: Arc<TsConfigResolver>,
     workspace_dir: Arc<WorkspaceDirectory>,
     workspace_options: &WorkspaceLintOptions,
-  ) -> Self {
-    let reporter_lock =
-      Arc::new(Mutex::new(create_reporter(workspace_options.reporter_kind)));
-    Self {
+    initial_cwd: &Path,
+  ) -> Result<Self, AnyError> {
+    let reporter_lock = Arc::new(Mutex::new(create_reporter(
+      workspace_options.reporter_kind,
+      initial_cwd,
+    )));
+    let maybe_baseline = match &workspace_options.baseline {
+      Some(path) => Some(Arc::new(Mutex::new(LintBaseline::load(
+        path.clone(),
+        workspace_options.update_baseline,
+      )?))),
+      None => None,
+    };
+    Ok(Self {
       caches,
       lint_rule_provider,
       module_graph_creator,
+      parsed_source_cache,
       tsconfig_resolver,
       workspace_dir,
       reporter_lock,
+      maybe_baseline,
       workspace_module_graph: None,
       has_error: Default::default(),
       file_count: 0,
-    }
+    })
   }
 
   pub async fn lint_files(
@@ -291,26 +330,9 @@ impl WorkspaceLinter {
     let plugin_specifiers = lint_options.plugins.clone();
     let lint_rules = self.lint_rule_provider.resolve_lint_rules_err_empty(
       lint_options.rules,
+      lint_options.rule_options,
       member_dir.maybe_deno_json().map(|c| c.as_ref()),
     )?;
-    let mut maybe_incremental_cache = None;
-
-    // TODO(bartlomieju): for now we don't support incremental caching if plugins are being used.
-    // https://github.com/denoland/deno/issues/28025
-    if lint_rules.supports_incremental_cache() && plugin_specifiers.is_empty() {
-      let mut hasher = FastInsecureHasher::new_deno_versioned();
-      hasher.write_hashable(lint_rules.incremental_cache_state());
-      if !plugin_specifiers.is_empty() {
-        hasher.write_hashable(&plugin_specifiers);
-      }
-      let state_hash = hasher.finish();
-
-      maybe_incremental_cache = Some(Arc::new(IncrementalCache::new(
-        self.caches.lint_incremental_cache_db(),
-        CacheDBHash::new(state_hash),
-        &paths,
-      )));
-    }
 
     #[allow(clippy::print_stdout)]
     #[allow(clippy::print_stderr)]
@@ -326,14 +348,48 @@ impl WorkspaceLinter {
     if !plugin_specifiers.is_empty() {
       let logger = plugins::PluginLogger::new(logger_printer);
       let runner = plugins::create_runner_and_load_plugins(
-        plugin_specifiers,
+        plugin_specifiers.clone(),
         logger,
-        exclude,
+        exclude.clone(),
       )
       .await?;
       plugin_runner = Some(Arc::new(runner));
     }
 
+    // plugins can only use the incremental cache when all of them declared
+    // that their diagnostics only depend on the linted file
+    let plugins_support_incremental_cache = plugin_runner
+      .as_ref()
+      .map(|runner| runner.is_cacheable())
+      .unwrap_or(true);
+    let mut maybe_incremental_cache = None;
+    if lint_rules.supports_incremental_cache()
+      && plugins_support_incremental_cache
+    {
+      let mut hasher = FastInsecureHasher::new_deno_versioned();
+      hasher.write_hashable(lint_rules.incremental_cache_state());
+      if !plugin_specifiers.is_empty() {
+        hasher.write_hashable(&plugin_specifiers);
+        hasher.write_hashable(&exclude);
+        let plugin_graph = self
+          .module_graph_creator
+          .create_graph(
+            GraphKind::CodeOnly,
+            plugin_specifiers.clone(),
+            NpmCachingStrategy::Eager,
+          )
+          .await?;
+        hasher.write_hashable(plugins::hash_plugin_sources(&plugin_graph));
+      }
+      let state_hash = hasher.finish();
+
+      maybe_incremental_cache = Some(Arc::new(IncrementalCache::new(
+        self.caches.lint_incremental_cache_db(),
+        CacheDBHash::new(state_hash),
+        &paths,
+      )));
+    }
+
     let linter = Arc::new(CliLinter::new(CliLinterOptions {
       configured_rules: lint_rules,
       fix: lint_options.fix,
@@ -354,11 +410,15 @@ impl WorkspaceLinter {
     }
 
     let maybe_incremental_cache_ = maybe_incremental_cache.clone();
+    let maybe_baseline = self.maybe_baseline.clone();
     let linter = linter.clone();
     let cli_options = cli_options.clone();
     let fut = async move {
       let operation = move |file_path: PathBuf| {
         let file_text = deno_ast::strip_bom(fs::read_to_string(&file_path)?);
+        if let Some(baseline) = &maybe_baseline {
+          baseline.lock().mark_linted(&file_path);
+        }
 
         // don't bother rechecking this file if it didn't have any diagnostics before
         if let Some(incremental_cache) = &maybe_incremental_cache_ {
@@ -367,7 +427,7 @@ impl WorkspaceLinter {
           }
         }
 
-        let r = linter.lint_file(
+        let mut r = linter.lint_file(
           &file_path,
           file_text,
           cli_options.ext_flag().as_deref(),
@@ -384,6 +444,16 @@ impl WorkspaceLinter {
             }
           }
         }
+        if let Some(baseline) = &maybe_baseline {
+          let mut baseline = baseline.lock();
+          match &mut r {
+            Ok((_, file_diagnostics)) => {
+              *file_diagnostics =
+                baseline.filter(std::mem::take(file_diagnostics));
+            }
+            Err(_) => baseline.mark_failed(&file_path),
+          }
+        }
 
         let success = handle_lint_result(
           &file_path.to_string_lossy(),
@@ -448,7 +518,9 @@ impl WorkspaceLinter {
 
     let has_error = self.has_error.clone();
     let reporter_lock = self.reporter_lock.clone();
+    let maybe_baseline = self.maybe_baseline.clone();
     let linter = linter.clone();
+    let parsed_source_cache = self.parsed_source_cache.clone();
     let path_urls = paths
       .iter()
       .filter_map(|p| ModuleSpecifier::from_file_path(p).ok())
@@ -462,7 +534,15 @@ impl WorkspaceLinter {
       if !export_urls.iter().any(|url| path_urls.contains(url)) {
         return Ok(()); // entrypoint is not specified, so skip
       }
-      let diagnostics = linter.lint_package(&graph, &export_urls);
+      let mut diagnostics = linter.lint_package(
+        &graph,
+        LazyGraphSourceParser::new(&parsed_source_cache, &graph),
+        &export_urls,
+        &path_urls,
+      );
+      if let Some(baseline) = &maybe_baseline {
+        diagnostics = baseline.lock().filter(diagnostics);
+      }
       if !diagnostics.is_empty() {
         has_error.raise();
         let mut reporter = reporter_lock.lock();
@@ -476,10 +556,13 @@ impl WorkspaceLinter {
     Some(fut)
   }
 
-  pub fn finish(self) -> bool {
+  pub fn finish(self) -> Result<bool, AnyError> {
     debug!("Found {} files", self.file_count);
     self.reporter_lock.lock().close(self.file_count);
-    !self.has_error.is_raised() // success
+    if let Some(baseline) = &self.maybe_baseline {
+      baseline.lock().finish()?;
+    }
+    Ok(!self.has_error.is_raised()) // success
   }
 }
 
@@ -502,14 +585,17 @@ fn collect_lint_files(
 pub fn print_rules_list(json: bool, maybe_rules_tags: Option<Vec<String>>) {
   let rule_provider = LintRuleProvider::new(None);
   let mut all_rules = rule_provider.all_rules();
-  let configured_rules = rule_provider.resolve_lint_rules(
-    LintRulesConfig {
-      tags: maybe_rules_tags.clone(),
-      include: None,
-      exclude: None,
-    },
-    None,
-  );
+  let configured_rules = rule_provider
+    .resolve_lint_rules(
+      LintRulesConfig {
+        tags: maybe_rules_tags.clone(),
+        include: None,
+        exclude: None,
+      },
+      Default::default(),
+      None,
+    )
+    .unwrap();
   all_rules.sort_by_cached_key(|rule| rule.code().to_string());
 
   if json {
@@ -578,15 +664,21 @@ fn lint_stdin(
   let start_dir = &cli_options.start_dir;
   let reporter_lock = Arc::new(Mutex::new(create_reporter(
     workspace_lint_options.reporter_kind,
+    cli_options.initial_cwd(),
   )));
   let lint_config = start_dir
     .to_lint_config(FilePatterns::new_with_base(start_dir.dir_path()))?;
   let deno_lint_config =
     tsconfig_resolver.deno_lint_config(start_dir.dir_url())?;
-  let lint_options =
-    LintOptions::resolve(start_dir.dir_path(), lint_config, &lint_flags)?;
+  let lint_options = LintOptions::resolve(
+    start_dir.dir_path(),
+    lint_config,
+    &lint_flags,
+    start_dir.maybe_deno_json().map(|c| c.as_ref()),
+  )?;
   let configured_rules = lint_rule_provider.resolve_lint_rules_err_empty(
     lint_options.rules,
+    lint_options.rule_options,
     start_dir.maybe_deno_json().map(|c| c.as_ref()),
   )?;
   let mut file_path = cli_options.initial_cwd().join(STDIN_FILE_NAME);
@@ -688,8 +780,9 @@ mod tests {
 
   fn get_all_rules() -> Vec<String> {
     let rule_provider = LintRuleProvider::new(None);
-    let configured_rules =
-      rule_provider.resolve_lint_rules(Default::default(), None);
+    let configured_rules = rule_provider
+      .resolve_lint_rules(Default::default(), Default::default(), None)
+      .unwrap();
     let mut all_rules = configured_rules
       .all_rule_codes
       .into_iter()
@@ -18,6 +18,9 @@ use deno_core::parking_lot::Mutex;
 use deno_core::resolve_url_or_path;
 use deno_core::v8;
 use deno_core::PollEventLoopOptions;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_lib::util::hash::FastInsecureHasher;
 use deno_lint::diagnostic::LintDiagnostic;
 use deno_path_util::url_from_file_path;
 use deno_runtime::deno_permissions::Permissions;
@@ -121,6 +124,12 @@ impl PluginHostProxy {
 
     all_names
   }
+
+  /// Whether all the plugins declared that they can use the incremental
+  /// cache.
+  pub fn is_cacheable(&self) -> bool {
+    self.plugin_info.lock().iter().all(|info| info.cacheable)
+  }
 }
 
 pub struct PluginHost {
@@ -209,6 +218,9 @@ async fn create_plugin_runner_inner(
 pub struct PluginInfo {
   pub name: String,
   pub rule_names: Vec<String>,
+  /// Whether the plugin declared that it can use the incremental cache.
+  #[serde(default)]
+  pub cacheable: bool,
 }
 
 impl PluginInfo {
@@ -511,6 +523,29 @@ impl PluginHostProxy {
   }
 }
 
+/// Hashes the sources of the modules that the plugins import, so that the
+/// incremental cache is busted when a plugin or one of its dependencies
+/// changes. npm packages are keyed by their resolved version.
+pub fn hash_plugin_sources(plugin_graph: &ModuleGraph) -> u64 {
+  let mut hasher = FastInsecureHasher::new_without_deno_version();
+  for module in plugin_graph.modules() {
+    hasher.write_str(module.specifier().as_str());
+    match module {
+      Module::Js(module) => {
+        hasher.write_str(&module.source);
+      }
+      Module::Json(module) => {
+        hasher.write_str(&module.source);
+      }
+      Module::Wasm(module) => {
+        hasher.write(&module.source);
+      }
+      Module::Npm(_) | Module::Node(_) | Module::External(_) => {}
+    }
+  }
+  hasher.finish()
+}
+
 pub async fn create_runner_and_load_plugins(
   plugin_specifiers: Vec<ModuleSpecifier>,
   logger: PluginLogger,
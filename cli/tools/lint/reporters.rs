@@ -1,25 +1,37 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
+use std::collections::HashMap;
+use std::path::Path;
+
 use deno_ast::diagnostics::Diagnostic;
+use deno_ast::SourceRange;
+use deno_ast::SourceTextInfo;
 use deno_core::error::AnyError;
 use deno_core::error::CoreError;
 use deno_core::serde_json;
 use deno_lint::diagnostic::LintDiagnostic;
+use deno_lint::diagnostic::LintDocsUrl;
 use deno_runtime::colors;
 use deno_runtime::fmt_errors::format_js_error;
 use log::info;
 use serde::Serialize;
 
 use super::LintError;
+use super::LintRuleProvider;
 use crate::args::LintReporterKind;
+use crate::util::sarif;
 
 const JSON_SCHEMA_VERSION: u8 = 1;
 
-pub fn create_reporter(kind: LintReporterKind) -> Box<dyn LintReporter + Send> {
+pub fn create_reporter(
+  kind: LintReporterKind,
+  initial_cwd: &Path,
+) -> Box<dyn LintReporter + Send> {
   match kind {
     LintReporterKind::Pretty => Box::new(PrettyLintReporter::new()),
     LintReporterKind::Json => Box::new(JsonLintReporter::new()),
     LintReporterKind::Compact => Box::new(CompactLintReporter::new()),
+    LintReporterKind::Sarif => Box::new(SarifLintReporter::new(initial_cwd)),
   }
 }
 
@@ -259,6 +271,151 @@ impl LintReporter for JsonLintReporter {
   }
 }
 
+struct SarifLintReporter {
+  run: sarif::Run,
+  /// The docs URL and tags of the built-in rules, by code.
+  rules: HashMap<&'static str, (String, Vec<String>)>,
+  notifications: Vec<sarif::Notification>,
+}
+
+impl SarifLintReporter {
+  fn new(initial_cwd: &Path) -> SarifLintReporter {
+    let rules = LintRuleProvider::new(None)
+      .all_rules()
+      .into_iter()
+      .map(|rule| {
+        let tags = rule.tags().iter().map(|t| t.display().to_string());
+        (
+          rule.code(),
+          (rule.help_docs_url().into_owned(), tags.collect()),
+        )
+      })
+      .collect();
+    SarifLintReporter {
+      run: sarif::Run::new(
+        "deno lint",
+        "https://docs.deno.com/go/lint",
+        sarif::ColumnKind::UnicodeCodePoints,
+        initial_cwd,
+      ),
+      rules,
+      notifications: Vec::new(),
+    }
+  }
+}
+
+fn sarif_region(
+  text_info: &SourceTextInfo,
+  range: SourceRange,
+) -> sarif::Region {
+  let start = text_info.line_and_column_index(range.start);
+  let end = text_info.line_and_column_index(range.end);
+  sarif::Region::from_indexes(
+    (start.line_index, start.column_index),
+    Some((end.line_index, end.column_index)),
+  )
+}
+
+impl LintReporter for SarifLintReporter {
+  fn visit_diagnostic(&mut self, d: &LintDiagnostic) {
+    let code = d.code();
+    let rule_index = self.run.rule_index(code, || {
+      let (help_uri, tags) = match self.rules.get(code) {
+        Some((docs_url, tags)) => (Some(docs_url.clone()), tags.clone()),
+        None => match &d.details.custom_docs_url {
+          LintDocsUrl::Custom(url) => (Some(url.clone()), Vec::new()),
+          _ => (None, Vec::new()),
+        },
+      };
+      sarif::ReportingDescriptor {
+        help_uri,
+        properties: (!tags.is_empty()).then_some(sarif::PropertyBag { tags }),
+        ..Default::default()
+      }
+    });
+    let artifact_location = self.run.artifact_location(d.specifier.as_str());
+    let fixes = match &d.range {
+      Some(range) => d
+        .details
+        .fixes
+        .iter()
+        .map(|fix| sarif::Fix {
+          description: sarif::Message::new(fix.description.to_string()),
+          artifact_changes: vec![sarif::ArtifactChange {
+            artifact_location: artifact_location.clone(),
+            replacements: fix
+              .changes
+              .iter()
+              .map(|change| sarif::Replacement {
+                deleted_region: sarif_region(&range.text_info, change.range),
+                inserted_content: sarif::ArtifactContent {
+                  text: change.new_text.to_string(),
+                },
+              })
+              .collect(),
+          }],
+        })
+        .collect(),
+      None => Vec::new(),
+    };
+    let message = match d.hint() {
+      Some(hint) => format!("{}\n\nhint: {}", d.message(), hint),
+      None => d.message().to_string(),
+    };
+    self.run.results.push(sarif::SarifResult {
+      rule_id: code.to_string(),
+      rule_index,
+      level: sarif::Level::Error,
+      message: sarif::Message::new(message),
+      locations: vec![sarif::Location {
+        physical_location: sarif::PhysicalLocation {
+          artifact_location,
+          region: d
+            .range
+            .as_ref()
+            .map(|range| sarif_region(&range.text_info, range.range)),
+        },
+        message: None,
+      }],
+      related_locations: Vec::new(),
+      fixes,
+    });
+  }
+
+  fn visit_error(&mut self, file_path: &str, err: &AnyError) {
+    let locations = deno_path_util::url_from_file_path(file_path.as_ref())
+      .map(|url| {
+        vec![sarif::Location {
+          physical_location: sarif::PhysicalLocation {
+            artifact_location: self.run.artifact_location(url.as_str()),
+            region: None,
+          },
+          message: None,
+        }]
+      })
+      .unwrap_or_default();
+    self.notifications.push(sarif::Notification {
+      level: sarif::Level::Error,
+      message: sarif::Message::new(err.to_string()),
+      locations,
+    });
+  }
+
+  fn close(&mut self, _check_count: usize) {
+    let notifications = std::mem::take(&mut self.notifications);
+    self.run.invocations.push(sarif::Invocation {
+      execution_successful: notifications.is_empty(),
+      tool_execution_notifications: notifications,
+    });
+    self.run.sort_results();
+    let json = serde_json::to_string_pretty(&sarif::SarifLog::new(&self.run));
+    #[allow(clippy::print_stdout)]
+    {
+      println!("{}", json.unwrap());
+    }
+  }
+}
+
 fn sort_diagnostics(diagnostics: &mut [JsonLintDiagnostic]) {
   // Sort so that we guarantee a deterministic output which is useful for tests
   diagnostics.sort_by(|a, b| {
@@ -0,0 +1,578 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use deno_ast::ModuleSpecifier;
+use deno_ast::SourceRange;
+use deno_config::glob::GlobPattern;
+use deno_config::glob::PathGlobMatch;
+use deno_config::glob::PathOrPatternSet;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::specifier_has_uri_scheme;
+use deno_error::JsErrorBox;
+use deno_graph::source::ResolutionKind;
+use deno_graph::source::ResolveError;
+use deno_graph::Range;
+use deno_lint::diagnostic::LintDiagnosticDetails;
+use deno_lint::diagnostic::LintDiagnosticRange;
+use deno_lint::diagnostic::LintDocsUrl;
+use deno_lint::rules::LintRule;
+use deno_lint::tags;
+use deno_package_json::PackageJsonDepValue;
+use deno_path_util::url_from_file_path;
+use deno_path_util::url_to_file_path;
+use deno_resolver::workspace::MappedResolution;
+use deno_resolver::workspace::WorkspaceResolver;
+use deno_semver::jsr::JsrPackageReqReference;
+use deno_semver::npm::NpmPackageReqReference;
+use text_lines::LineAndColumnIndex;
+
+use super::ExtendedLintRule;
+use crate::args::ImportBoundariesConfig;
+use crate::args::ImportBoundaryConfig;
+use crate::graph_util::CliJsrUrlProvider;
+use crate::sys::CliSys;
+
+const CODE: &str = "import-boundaries";
+const DOCS_URL: &str = "https://docs.deno.com/lint/rules/import-boundaries";
+
+/// What the patterns of a boundary are matched with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PatternTarget {
+  /// A local module.
+  Path(PathBuf),
+  /// A package like `npm:express`, or a remote module.
+  Name(String),
+}
+
+/// The patterns of a boundary, where the ones with a scheme like `npm:*`
+/// are globs of package names or remote modules, and the others are globs
+/// of paths relative to the config file. As in the `include` and `exclude`
+/// of a config file, a later pattern starting with `!` excludes what the
+/// earlier ones match.
+#[derive(Debug, Default)]
+struct PatternList {
+  paths: Option<PathOrPatternSet>,
+  names: Vec<GlobPattern>,
+}
+
+impl PatternList {
+  fn new(base: &Path, patterns: &[String]) -> Result<Self, AnyError> {
+    let mut path_patterns = Vec::new();
+    let mut names = Vec::new();
+    for pattern in patterns {
+      if specifier_has_uri_scheme(pattern.strip_prefix('!').unwrap_or(pattern))
+      {
+        names.push(GlobPattern::new(pattern)?);
+      } else {
+        path_patterns.push(pattern.clone());
+      }
+    }
+    let paths = if path_patterns.is_empty() {
+      None
+    } else {
+      Some(PathOrPatternSet::from_include_relative_path_or_patterns(
+        base,
+        &path_patterns,
+      )?)
+    };
+    Ok(Self { paths, names })
+  }
+
+  /// Whether one of the targets matches.
+  fn matches(&self, targets: &[PatternTarget]) -> bool {
+    targets.iter().any(|target| match target {
+      PatternTarget::Path(path) => self
+        .paths
+        .as_ref()
+        .map(|paths| paths.matches_path(path))
+        .unwrap_or(false),
+      PatternTarget::Name(name) => {
+        for pattern in self.names.iter().rev() {
+          match pattern.matches_path(Path::new(name)) {
+            PathGlobMatch::Matched => return true,
+            PathGlobMatch::MatchedNegated => return false,
+            PathGlobMatch::NotMatched => {}
+          }
+        }
+        false
+      }
+    })
+  }
+}
+
+#[derive(Debug)]
+struct Boundary {
+  from: PatternList,
+  deny: PatternList,
+  allow: PatternList,
+  message: Option<String>,
+}
+
+impl Boundary {
+  fn new(base: &Path, config: &ImportBoundaryConfig) -> Result<Self, AnyError> {
+    let from = if config.from.is_empty() {
+      PatternList::new(base, &["**".to_string()])?
+    } else {
+      PatternList::new(base, &config.from)?
+    };
+    Ok(Self {
+      from,
+      deny: PatternList::new(base, &config.deny)?,
+      allow: PatternList::new(base, &config.allow)?,
+      message: config.message.clone(),
+    })
+  }
+}
+
+/// Reports the imports that cross a boundary configured in the
+/// `lint.rules` of the config file. Imports are checked after the
+/// workspace resolver resolved them, so that an import map entry or a
+/// package.json dependency is matched by the package it points to.
+#[derive(Debug)]
+pub struct ImportBoundariesRule {
+  // None for making printing out the lint rules easy
+  workspace_resolver: Option<Arc<WorkspaceResolver<CliSys>>>,
+  /// The directory of the config file, which paths are relative to.
+  base_url: Option<ModuleSpecifier>,
+  boundaries: Vec<Boundary>,
+}
+
+impl ImportBoundariesRule {
+  pub fn new(
+    workspace_resolver: Option<Arc<WorkspaceResolver<CliSys>>>,
+    options: ImportBoundariesConfig,
+    maybe_config_specifier: Option<&ModuleSpecifier>,
+  ) -> Result<Self, AnyError> {
+    let base_url =
+      maybe_config_specifier.and_then(|specifier| specifier.join("./").ok());
+    let base_path = base_url
+      .as_ref()
+      .and_then(|base_url| url_to_file_path(base_url).ok());
+    let boundaries = match (maybe_config_specifier, &base_path) {
+      (Some(config_specifier), Some(base_path)) => options
+        .boundaries
+        .iter()
+        .map(|boundary| Boundary::new(base_path, boundary))
+        .collect::<Result<_, _>>()
+        .with_context(|| {
+          format!(
+            "Invalid \"import-boundaries\" pattern in '{}'",
+            config_specifier
+          )
+        })?,
+      _ => Vec::new(),
+    };
+    Ok(Self {
+      workspace_resolver,
+      base_url,
+      boundaries,
+    })
+  }
+
+  /// The first boundary that denies importing `target` from `referrer`.
+  fn denying_boundary(
+    &self,
+    referrer: &[PatternTarget],
+    target: &[PatternTarget],
+  ) -> Option<&Boundary> {
+    self.boundaries.iter().find(|boundary| {
+      boundary.from.matches(referrer)
+        && boundary.deny.matches(target)
+        && !boundary.allow.matches(target)
+    })
+  }
+
+  fn path_name(&self, specifier: &ModuleSpecifier) -> String {
+    self
+      .base_url
+      .as_ref()
+      .and_then(|base_url| base_url.make_relative(specifier))
+      .unwrap_or_else(|| specifier.to_string())
+  }
+
+  fn display_name(&self, target: &PatternTarget) -> String {
+    match target {
+      PatternTarget::Path(path) => match url_from_file_path(path) {
+        Ok(specifier) => self.path_name(&specifier),
+        Err(_) => path.display().to_string(),
+      },
+      PatternTarget::Name(name) => name.clone(),
+    }
+  }
+
+  /// What patterns are matched with, where the first one is displayed.
+  fn targets(&self, resolution: &MappedResolution) -> Vec<PatternTarget> {
+    match resolution {
+      MappedResolution::Normal { specifier, .. } => {
+        if let Ok(req_ref) = NpmPackageReqReference::from_specifier(specifier) {
+          package_targets("npm", &req_ref.req().name, req_ref.sub_path())
+        } else if let Ok(req_ref) =
+          JsrPackageReqReference::from_specifier(specifier)
+        {
+          package_targets("jsr", &req_ref.req().name, req_ref.sub_path())
+        } else {
+          vec![module_target(specifier)]
+        }
+      }
+      MappedResolution::WorkspaceJsrPackage {
+        specifier,
+        pkg_req_ref,
+      } => {
+        let mut targets = package_targets(
+          "jsr",
+          &pkg_req_ref.req().name,
+          pkg_req_ref.sub_path(),
+        );
+        targets.push(module_target(specifier));
+        targets
+      }
+      MappedResolution::WorkspaceNpmPackage {
+        target_pkg_json,
+        pkg_name,
+        sub_path,
+      } => {
+        let mut targets = package_targets("npm", pkg_name, sub_path.as_deref());
+        let dir_path = target_pkg_json.dir_path();
+        targets.push(PatternTarget::Path(match sub_path {
+          Some(sub_path) => dir_path.join(sub_path),
+          None => dir_path.to_path_buf(),
+        }));
+        targets
+      }
+      MappedResolution::PackageJson {
+        alias,
+        sub_path,
+        dep_result,
+        ..
+      } => {
+        // match an aliased dependency like `"foo": "npm:bar@1"` by the
+        // package it installs, and by its alias
+        let mut targets = Vec::new();
+        if let Ok(PackageJsonDepValue::Req(req)) = dep_result {
+          if req.name.as_str() != *alias {
+            targets = package_targets("npm", &req.name, sub_path.as_deref());
+          }
+        }
+        targets.extend(package_targets("npm", alias, sub_path.as_deref()));
+        targets
+      }
+    }
+  }
+}
+
+fn module_target(specifier: &ModuleSpecifier) -> PatternTarget {
+  match url_to_file_path(specifier) {
+    Ok(path) => PatternTarget::Path(path),
+    Err(_) => PatternTarget::Name(specifier.to_string()),
+  }
+}
+
+/// The package with its sub path, then the package alone, so that a
+/// pattern like `npm:express` also matches `npm:express/router`.
+fn package_targets(
+  scheme: &str,
+  name: &str,
+  sub_path: Option<&str>,
+) -> Vec<PatternTarget> {
+  let package = format!("{scheme}:{name}");
+  let names = match sub_path {
+    Some(sub_path) if !sub_path.is_empty() => {
+      vec![format!("{package}/{sub_path}"), package]
+    }
+    _ => vec![package],
+  };
+  names.into_iter().map(PatternTarget::Name).collect()
+}
+
+impl ExtendedLintRule for ImportBoundariesRule {
+  fn supports_incremental_cache(&self) -> bool {
+    // the cache isn't busted when the boundaries or the import map
+    // change, so only allow it when there's nothing to check
+    self.boundaries.is_empty()
+  }
+
+  fn help_docs_url(&self) -> Cow<'static, str> {
+    Cow::Borrowed(DOCS_URL)
+  }
+
+  fn into_base(self: Box<Self>) -> Box<dyn LintRule> {
+    self
+  }
+}
+
+impl LintRule for ImportBoundariesRule {
+  fn lint_program_with_ast_view<'view>(
+    &self,
+    context: &mut deno_lint::context::Context<'view>,
+    _program: deno_lint::Program<'view>,
+  ) {
+    let Some(workspace_resolver) = &self.workspace_resolver else {
+      return;
+    };
+    if self.boundaries.is_empty() {
+      return;
+    }
+    let Ok(referrer_path) = url_to_file_path(context.specifier()) else {
+      return;
+    };
+    let referrer = [PatternTarget::Path(referrer_path)];
+    if !self
+      .boundaries
+      .iter()
+      .any(|boundary| boundary.from.matches(&referrer))
+    {
+      return;
+    }
+
+    let resolver = ImportCaptureResolver {
+      rule: self,
+      workspace_resolver,
+      captures: Default::default(),
+    };
+
+    // fill this and capture the resolved imports in the resolver
+    deno_graph::parse_module_from_ast(deno_graph::ParseModuleFromAstOptions {
+      graph_kind: deno_graph::GraphKind::All,
+      specifier: context.specifier().clone(),
+      maybe_headers: None,
+      parsed_source: context.parsed_source(),
+      // ignore resolving dynamic imports like import(`./dir/${something}`)
+      file_system: &deno_graph::source::NullFileSystem,
+      jsr_url_provider: &CliJsrUrlProvider,
+      maybe_resolver: Some(&resolver),
+      // don't bother resolving npm specifiers
+      maybe_npm_resolver: None,
+    });
+
+    let mut captures = resolver.captures.take().into_iter().collect::<Vec<_>>();
+    captures.sort_by_key(|(range, _)| {
+      (range.range.start.line, range.range.start.character)
+    });
+    for (range, targets) in captures {
+      let Some(boundary) = self.denying_boundary(&referrer, &targets) else {
+        continue;
+      };
+      let start_pos =
+        context.text_info().loc_to_source_pos(LineAndColumnIndex {
+          line_index: range.range.start.line,
+          column_index: range.range.start.character,
+        });
+      let end_pos = context.text_info().loc_to_source_pos(LineAndColumnIndex {
+        line_index: range.range.end.line,
+        column_index: range.range.end.character,
+      });
+      let target = self.display_name(&targets[0]);
+      context.add_diagnostic_details(
+        Some(LintDiagnosticRange {
+          range: SourceRange::new(start_pos, end_pos),
+          description: None,
+          text_info: context.text_info().clone(),
+        }),
+        LintDiagnosticDetails {
+          message: boundary.message.clone().unwrap_or_else(|| {
+            format!(
+              "Importing \"{}\" from \"{}\" crosses an import boundary.",
+              target,
+              self.path_name(context.specifier())
+            )
+          }),
+          code: CODE.to_string(),
+          custom_docs_url: LintDocsUrl::Custom(DOCS_URL.to_string()),
+          fixes: vec![],
+          hint: Some(
+            "Move the code to a module that may import it, or change the \"import-boundaries\" options of \"lint.rules\" in the config file."
+              .to_string(),
+          ),
+          info: vec![Cow::Owned(format!("The import resolves to {target}"))],
+        },
+      );
+    }
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn tags(&self) -> tags::Tags {
+    &[]
+  }
+}
+
+#[derive(Debug)]
+struct ImportCaptureResolver<'a> {
+  rule: &'a ImportBoundariesRule,
+  workspace_resolver: &'a WorkspaceResolver<CliSys>,
+  captures: RefCell<HashMap<Range, Vec<PatternTarget>>>,
+}
+
+impl<'a> deno_graph::source::Resolver for ImportCaptureResolver<'a> {
+  fn resolve(
+    &self,
+    specifier_text: &str,
+    referrer_range: &Range,
+    resolution_kind: ResolutionKind,
+  ) -> Result<deno_ast::ModuleSpecifier, deno_graph::source::ResolveError> {
+    let resolution = self
+      .workspace_resolver
+      .resolve(
+        specifier_text,
+        &referrer_range.specifier,
+        match resolution_kind {
+          ResolutionKind::Execution => {
+            deno_resolver::workspace::ResolutionKind::Execution
+          }
+          ResolutionKind::Types => {
+            deno_resolver::workspace::ResolutionKind::Types
+          }
+        },
+      )
+      .map_err(|err| ResolveError::Other(JsErrorBox::from_err(err)))?;
+    let targets = self.rule.targets(&resolution);
+    self
+      .captures
+      .borrow_mut()
+      .entry(referrer_range.clone())
+      .or_insert(targets);
+    match resolution {
+      MappedResolution::Normal { specifier, .. }
+      | MappedResolution::WorkspaceJsrPackage { specifier, .. } => {
+        Ok(specifier)
+      }
+      MappedResolution::WorkspaceNpmPackage { .. }
+      | MappedResolution::PackageJson { .. } => {
+        // this error is ignored
+        Err(ResolveError::Other(JsErrorBox::generic("")))
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn base_path() -> PathBuf {
+    if cfg!(windows) {
+      PathBuf::from("C:\\project")
+    } else {
+      PathBuf::from("/project")
+    }
+  }
+
+  fn target(text: &str) -> PatternTarget {
+    if specifier_has_uri_scheme(text) {
+      PatternTarget::Name(text.to_string())
+    } else {
+      PatternTarget::Path(base_path().join(text))
+    }
+  }
+
+  fn matches(patterns: &[&str], text: &str) -> bool {
+    let patterns = patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+    PatternList::new(&base_path(), &patterns)
+      .unwrap()
+      .matches(&[target(text)])
+  }
+
+  #[test]
+  fn matches_globs() {
+    assert!(matches(&["src/domain/**"], "src/domain/user.ts"));
+    assert!(matches(&["src/domain/**"], "src/domain/a/b/user.ts"));
+    assert!(matches(&["src/domain"], "src/domain/user.ts"));
+    assert!(matches(&["./src/*.ts"], "src/mod.ts"));
+    assert!(!matches(&["src/*.ts"], "src/domain/mod.ts"));
+    assert!(matches(&["src/**/*.ts"], "src/mod.ts"));
+    assert!(matches(&["src/?.ts"], "src/a.ts"));
+    assert!(!matches(&["src/?.ts"], "src/ab.ts"));
+    assert!(matches(&["src/**", "!src/db/**"], "src/mod.ts"));
+    assert!(!matches(&["src/**", "!src/db/**"], "src/db/client.ts"));
+    assert!(matches(&["npm:express"], "npm:express"));
+    assert!(!matches(&["npm:express"], "npm:express-session"));
+    assert!(matches(&["jsr:@std/*"], "jsr:@std/path"));
+    assert!(!matches(&["npm:*", "!npm:zod"], "npm:zod"));
+    assert!(matches(
+      &["https://deno.land/**"],
+      "https://deno.land/x/mod.ts"
+    ));
+    assert!(!matches(&["**"], "https://deno.land/x/mod.ts"));
+    assert!(!matches(&["npm:*"], "src/mod.ts"));
+  }
+
+  #[test]
+  fn finds_denying_boundary() {
+    let rule = ImportBoundariesRule::new(
+      None,
+      ImportBoundariesConfig {
+        boundaries: vec![
+          ImportBoundaryConfig {
+            from: vec!["src/domain/**".to_string()],
+            deny: vec!["src/http/**".to_string(), "npm:*".to_string()],
+            allow: vec!["npm:zod".to_string()],
+            message: None,
+          },
+          ImportBoundaryConfig {
+            from: vec!["**".to_string(), "!src/db/**".to_string()],
+            deny: vec!["npm:pg".to_string()],
+            allow: vec![],
+            message: Some("Use the db module.".to_string()),
+          },
+        ],
+      },
+      Some(&url_from_file_path(&base_path().join("deno.json")).unwrap()),
+    )
+    .unwrap();
+    let denied = |referrer: &str, targets: &[&str]| {
+      rule
+        .denying_boundary(
+          &[target(referrer)],
+          &targets.iter().map(|t| target(t)).collect::<Vec<_>>(),
+        )
+        .is_some()
+    };
+    assert!(denied("src/domain/user.ts", &["src/http/server.ts"]));
+    assert!(!denied("src/domain/user.ts", &["src/domain/id.ts"]));
+    assert!(!denied("src/http/server.ts", &["src/http/routes.ts"]));
+    assert!(denied(
+      "src/domain/user.ts",
+      &["npm:express/router", "npm:express"]
+    ));
+    assert!(!denied("src/domain/user.ts", &["npm:zod"]));
+    assert!(denied("src/http/server.ts", &["npm:pg"]));
+    assert!(!denied("src/db/client.ts", &["npm:pg"]));
+    assert_eq!(
+      package_targets("npm", "express", Some("router")),
+      vec![target("npm:express/router"), target("npm:express")]
+    );
+    assert_eq!(
+      rule.display_name(&target("src/http/server.ts")),
+      "src/http/server.ts"
+    );
+  }
+
+  #[test]
+  fn errors_for_invalid_pattern() {
+    let err = ImportBoundariesRule::new(
+      None,
+      ImportBoundariesConfig {
+        boundaries: vec![ImportBoundaryConfig {
+          from: vec![],
+          deny: vec!["npm:[a-".to_string()],
+          allow: vec![],
+          message: None,
+        }],
+      },
+      Some(&url_from_file_path(&base_path().join("deno.json")).unwrap()),
+    )
+    .unwrap_err();
+    assert!(err
+      .to_string()
+      .starts_with("Invalid \"import-boundaries\" pattern in 'file:///"));
+  }
+}
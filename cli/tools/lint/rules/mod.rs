@@ -15,10 +15,15 @@ use deno_lint::rules::LintRule;
 use deno_lint::tags;
 use deno_resolver::workspace::WorkspaceResolver;
 
+use crate::args::LintRuleOptionsConfig;
+use crate::cache::LazyGraphSourceParser;
 use crate::sys::CliSys;
 
+mod import_boundaries;
+mod no_import_cycles;
 mod no_sloppy_imports;
 mod no_slow_types;
+mod no_unused_exports;
 
 // used for publishing
 pub use no_slow_types::collect_no_slow_type_diagnostics;
@@ -32,10 +37,15 @@ pub trait PackageLintRule: std::fmt::Debug + Send + Sync {
 
   fn help_docs_url(&self) -> Cow<'static, str>;
 
+  /// Lints the package with the `entrypoints`, where `files` are the files
+  /// of the package that are linted. The modules of the graph are parsed
+  /// with `source_parser`, which reuses the sources parsed when building it.
   fn lint_package(
     &self,
     graph: &ModuleGraph,
+    source_parser: LazyGraphSourceParser,
     entrypoints: &[ModuleSpecifier],
+    files: &HashSet<ModuleSpecifier>,
   ) -> Vec<LintDiagnostic>;
 }
 
@@ -119,7 +129,7 @@ impl CliLintRule {
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ConfiguredRules {
   pub all_rule_codes: HashSet<Cow<'static, str>>,
   pub rules: Vec<CliLintRule>,
@@ -153,9 +163,11 @@ impl LintRuleProvider {
   pub fn resolve_lint_rules_err_empty(
     &self,
     rules: LintRulesConfig,
+    rule_options: LintRuleOptionsConfig,
     maybe_config_file: Option<&ConfigFile>,
   ) -> Result<ConfiguredRules, AnyError> {
-    let lint_rules = self.resolve_lint_rules(rules, maybe_config_file);
+    let lint_rules =
+      self.resolve_lint_rules(rules, rule_options, maybe_config_file)?;
     if lint_rules.rules.is_empty() {
       bail!("No rules have been configured")
     }
@@ -163,29 +175,65 @@ impl LintRuleProvider {
   }
 
   pub fn all_rules(&self) -> Vec<CliLintRule> {
+    // the default options are always valid
+    self
+      .all_rules_with_options(Default::default(), None)
+      .unwrap()
+  }
+
+  fn all_rules_with_options(
+    &self,
+    options: LintRuleOptionsConfig,
+    maybe_config_file: Option<&ConfigFile>,
+  ) -> Result<Vec<CliLintRule>, AnyError> {
     let deno_lint_rules = deno_lint::rules::get_all_rules();
-    let cli_lint_rules = vec![CliLintRule(CliLintRuleKind::Extended(
-      Box::new(no_sloppy_imports::NoSloppyImportsRule::new(
-        self.workspace_resolver.clone(),
-      )),
-    ))];
-    let cli_graph_rules = vec![CliLintRule(CliLintRuleKind::Package(
-      Box::new(no_slow_types::NoSlowTypesRule),
-    ))];
-    deno_lint_rules
-      .into_iter()
-      .map(|rule| CliLintRule(CliLintRuleKind::DenoLint(rule)))
-      .chain(cli_lint_rules)
-      .chain(cli_graph_rules)
-      .collect()
+    let maybe_config_specifier = maybe_config_file.map(|c| &c.specifier);
+    let cli_lint_rules = vec![
+      CliLintRule(CliLintRuleKind::Extended(Box::new(
+        no_sloppy_imports::NoSloppyImportsRule::new(
+          self.workspace_resolver.clone(),
+        ),
+      ))),
+      CliLintRule(CliLintRuleKind::Extended(Box::new(
+        import_boundaries::ImportBoundariesRule::new(
+          self.workspace_resolver.clone(),
+          options.import_boundaries,
+          maybe_config_specifier,
+        )?,
+      ))),
+    ];
+    let cli_graph_rules = vec![
+      CliLintRule(CliLintRuleKind::Package(Box::new(
+        no_slow_types::NoSlowTypesRule,
+      ))),
+      CliLintRule(CliLintRuleKind::Package(Box::new(
+        no_unused_exports::NoUnusedExportsRule,
+      ))),
+      CliLintRule(CliLintRuleKind::Package(Box::new(
+        no_import_cycles::NoImportCyclesRule::new(
+          options.no_import_cycles,
+          maybe_config_specifier,
+        ),
+      ))),
+    ];
+    Ok(
+      deno_lint_rules
+        .into_iter()
+        .map(|rule| CliLintRule(CliLintRuleKind::DenoLint(rule)))
+        .chain(cli_lint_rules)
+        .chain(cli_graph_rules)
+        .collect(),
+    )
   }
 
   pub fn resolve_lint_rules(
     &self,
     rules: LintRulesConfig,
+    rule_options: LintRuleOptionsConfig,
     maybe_config_file: Option<&ConfigFile>,
-  ) -> ConfiguredRules {
-    let all_rules = self.all_rules();
+  ) -> Result<ConfiguredRules, AnyError> {
+    let all_rules =
+      self.all_rules_with_options(rule_options, maybe_config_file)?;
     let mut all_rule_names = HashSet::with_capacity(all_rules.len());
     for rule in &all_rules {
       all_rule_names.insert(rule.code().into());
@@ -198,10 +246,10 @@ impl LintRuleProvider {
       rules.exclude,
       rules.include,
     );
-    ConfiguredRules {
+    Ok(ConfiguredRules {
       rules,
       all_rule_codes: all_rule_names,
-    }
+    })
   }
 }
 
@@ -268,7 +316,9 @@ mod test {
       tags: None,
     };
     let rules_provider = LintRuleProvider::new(None);
-    let rules = rules_provider.resolve_lint_rules(rules_config, None);
+    let rules = rules_provider
+      .resolve_lint_rules(rules_config, Default::default(), None)
+      .unwrap();
     let mut rule_names = rules
       .rules
       .into_iter()
@@ -276,7 +326,8 @@ mod test {
       .collect::<Vec<_>>();
     rule_names.sort();
     let mut recommended_rule_names = rules_provider
-      .resolve_lint_rules(Default::default(), None)
+      .resolve_lint_rules(Default::default(), Default::default(), None)
+      .unwrap()
       .rules
       .into_iter()
       .filter(|r| r.tags().iter().any(|t| *t == tags::RECOMMENDED))
@@ -0,0 +1,418 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use deno_ast::ModuleSpecifier;
+use deno_ast::SourceRange;
+use deno_ast::SourceTextInfo;
+use deno_graph::ImportKind;
+use deno_graph::JsModule;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_lint::diagnostic::LintDiagnostic;
+use deno_lint::diagnostic::LintDiagnosticDetails;
+use deno_lint::diagnostic::LintDiagnosticRange;
+use deno_lint::diagnostic::LintDocsUrl;
+use text_lines::LineAndColumnIndex;
+
+use super::PackageLintRule;
+use crate::args::NoImportCyclesConfig;
+use crate::cache::LazyGraphSourceParser;
+
+const CODE: &str = "no-import-cycles";
+
+/// Reports the cycles of static imports between the modules that are
+/// reachable from the exports of a package. Each strongly connected
+/// component of the graph is reported once, with its shortest cycle.
+#[derive(Debug, Default)]
+pub struct NoImportCyclesRule {
+  ignore_type_imports: bool,
+  allowed_cycles: Vec<HashSet<ModuleSpecifier>>,
+}
+
+impl NoImportCyclesRule {
+  pub fn new(
+    options: NoImportCyclesConfig,
+    maybe_config_specifier: Option<&ModuleSpecifier>,
+  ) -> Self {
+    let allowed_cycles = match maybe_config_specifier {
+      Some(config_specifier) => options
+        .allow
+        .iter()
+        .map(|cycle| {
+          cycle
+            .iter()
+            .filter_map(|path| config_specifier.join(path).ok())
+            .collect()
+        })
+        .collect(),
+      None => Vec::new(),
+    };
+    Self {
+      ignore_type_imports: options.ignore_type_imports,
+      allowed_cycles,
+    }
+  }
+
+  fn is_allowed(&self, modules: &[&ModuleSpecifier]) -> bool {
+    self
+      .allowed_cycles
+      .iter()
+      .any(|allowed| modules.iter().all(|module| allowed.contains(*module)))
+  }
+}
+
+impl PackageLintRule for NoImportCyclesRule {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn help_docs_url(&self) -> Cow<'static, str> {
+    Cow::Owned(format!("https://docs.deno.com/lint/rules/{CODE}"))
+  }
+
+  fn lint_package(
+    &self,
+    graph: &ModuleGraph,
+    _source_parser: LazyGraphSourceParser,
+    entrypoints: &[ModuleSpecifier],
+    files: &HashSet<ModuleSpecifier>,
+  ) -> Vec<LintDiagnostic> {
+    let import_graph =
+      ImportGraph::build(graph, entrypoints, self.ignore_type_imports);
+    let mut diagnostics = Vec::new();
+    for component in strongly_connected_components(&import_graph.edges) {
+      let is_cycle = component.len() > 1
+        || import_graph.edges[component[0]]
+          .iter()
+          .any(|edge| edge.to == component[0]);
+      if !is_cycle {
+        continue;
+      }
+      let specifiers = component
+        .iter()
+        .map(|index| &import_graph.modules[*index].specifier)
+        .collect::<Vec<_>>();
+      if self.is_allowed(&specifiers) {
+        continue;
+      }
+      // report the cycle in the first linted module, so that it's reported
+      // once and in a stable place
+      let Some(start) = component
+        .iter()
+        .copied()
+        .filter(|index| files.contains(&import_graph.modules[*index].specifier))
+        .min_by_key(|index| &import_graph.modules[*index].specifier)
+      else {
+        continue;
+      };
+      let cycle = shortest_cycle(&import_graph.edges, &component, start);
+      diagnostics.push(import_graph.cycle_diagnostic(&cycle, component.len()));
+    }
+    diagnostics.sort_by_cached_key(|d| {
+      (d.specifier.clone(), d.range.as_ref().map(|r| r.range.start))
+    });
+    diagnostics
+  }
+}
+
+struct ImportGraphModule {
+  specifier: ModuleSpecifier,
+  text_info: SourceTextInfo,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ImportEdge {
+  to: usize,
+  /// The range of the specifier of the import.
+  range: SourceRange,
+}
+
+/// The static imports between the local modules of a package.
+struct ImportGraph {
+  modules: Vec<ImportGraphModule>,
+  edges: Vec<Vec<ImportEdge>>,
+}
+
+impl ImportGraph {
+  fn build(
+    graph: &ModuleGraph,
+    entrypoints: &[ModuleSpecifier],
+    ignore_type_imports: bool,
+  ) -> Self {
+    let mut modules = Vec::new();
+    let mut imports = Vec::new();
+    let mut indexes = HashMap::new();
+    let mut pending = entrypoints
+      .iter()
+      .map(|specifier| graph.resolve(specifier))
+      .collect::<VecDeque<_>>();
+    while let Some(specifier) = pending.pop_front() {
+      if specifier.scheme() != "file" || indexes.contains_key(specifier) {
+        continue;
+      }
+      let Some(Module::Js(module)) = graph.get(specifier) else {
+        continue;
+      };
+      let text_info = SourceTextInfo::new(module.source.clone());
+      let module_imports =
+        static_imports(graph, module, &text_info, ignore_type_imports);
+      pending.extend(module_imports.iter().map(|(specifier, _)| *specifier));
+      indexes.insert(specifier, modules.len());
+      modules.push(ImportGraphModule {
+        specifier: specifier.clone(),
+        text_info,
+      });
+      imports.push(module_imports);
+    }
+    let edges = imports
+      .into_iter()
+      .map(|module_imports| {
+        let mut seen = HashSet::new();
+        module_imports
+          .into_iter()
+          .filter_map(|(specifier, range)| {
+            let to = *indexes.get(specifier)?;
+            seen.insert(to).then_some(ImportEdge { to, range })
+          })
+          .collect()
+      })
+      .collect();
+    Self { modules, edges }
+  }
+
+  fn cycle_diagnostic(
+    &self,
+    cycle: &[ImportEdge],
+    component_len: usize,
+  ) -> LintDiagnostic {
+    let start = &self.modules[cycle.last().unwrap().to];
+    // show the modules relative to the directory of the start
+    let start_dir = start.specifier.join("./").ok();
+    let display_name = |index: usize| {
+      let specifier = &self.modules[index].specifier;
+      start_dir
+        .as_ref()
+        .and_then(|dir| dir.make_relative(specifier))
+        .unwrap_or_else(|| specifier.to_string())
+    };
+    let mut path = vec![display_name(cycle.last().unwrap().to)];
+    path.extend(cycle.iter().map(|edge| display_name(edge.to)));
+    let mut info = Vec::with_capacity(cycle.len() + 1);
+    let mut from = cycle.last().unwrap().to;
+    for edge in cycle {
+      let module = &self.modules[from];
+      let position = module.text_info.line_and_column_display(edge.range.start);
+      info.push(Cow::Owned(format!(
+        "{} imports {} at line {}:{}",
+        display_name(from),
+        display_name(edge.to),
+        position.line_number,
+        position.column_number
+      )));
+      from = edge.to;
+    }
+    if component_len > cycle.len() {
+      info.push(Cow::Owned(format!(
+        "the cycle is part of a group of {component_len} modules that import each other"
+      )));
+    }
+    let first_edge = cycle[0];
+    LintDiagnostic {
+      specifier: start.specifier.clone(),
+      range: Some(LintDiagnosticRange {
+        text_info: start.text_info.clone(),
+        range: first_edge.range,
+        description: Some("this import starts the cycle".to_string()),
+      }),
+      details: LintDiagnosticDetails {
+        message: format!("Import cycle: {}", path.join(" -> ")),
+        code: CODE.to_string(),
+        hint: Some(
+          "Move the code that the modules share to a module that doesn't import any of them"
+            .to_string(),
+        ),
+        info,
+        fixes: vec![],
+        custom_docs_url: LintDocsUrl::Default,
+      },
+    }
+  }
+}
+
+/// The static imports and re-exports of the module from the dependencies
+/// that deno_graph analyzed, in the order of the source. Dynamic imports are
+/// left out, because they don't run the imported module before the importer.
+fn static_imports<'a>(
+  graph: &'a ModuleGraph,
+  module: &'a JsModule,
+  text_info: &SourceTextInfo,
+  ignore_type_imports: bool,
+) -> Vec<(&'a ModuleSpecifier, SourceRange)> {
+  let mut imports = Vec::new();
+  for dep in module.dependencies.values() {
+    for import in &dep.imports {
+      if import.is_dynamic {
+        continue;
+      }
+      match import.kind {
+        ImportKind::Es => {}
+        ImportKind::TsType if !ignore_type_imports => {}
+        _ => continue,
+      }
+      let range = &import.specifier_range.range;
+      let start = text_info.loc_to_source_pos(LineAndColumnIndex {
+        line_index: range.start.line,
+        column_index: range.start.character,
+      });
+      let end = text_info.loc_to_source_pos(LineAndColumnIndex {
+        line_index: range.end.line,
+        column_index: range.end.character,
+      });
+      for specifier in dep.get_code().into_iter().chain(dep.get_type()) {
+        imports.push((graph.resolve(specifier), SourceRange::new(start, end)));
+      }
+    }
+  }
+  imports.sort_by_key(|(_, range)| range.start);
+  imports
+}
+
+/// Tarjan's algorithm, without recursion so that long import chains don't
+/// overflow the stack.
+fn strongly_connected_components(edges: &[Vec<ImportEdge>]) -> Vec<Vec<usize>> {
+  const UNVISITED: usize = usize::MAX;
+  let len = edges.len();
+  let mut index = vec![UNVISITED; len];
+  let mut low_link = vec![0; len];
+  let mut on_stack = vec![false; len];
+  let mut stack = Vec::new();
+  let mut next_index = 0;
+  let mut components = Vec::new();
+  for root in 0..len {
+    if index[root] != UNVISITED {
+      continue;
+    }
+    // the visited nodes with the position of their next edge
+    let mut call_stack = vec![(root, 0)];
+    index[root] = next_index;
+    low_link[root] = next_index;
+    next_index += 1;
+    stack.push(root);
+    on_stack[root] = true;
+    while let Some((node, edge_position)) = call_stack.last_mut() {
+      let node = *node;
+      if let Some(edge) = edges[node].get(*edge_position) {
+        *edge_position += 1;
+        let next = edge.to;
+        if index[next] == UNVISITED {
+          index[next] = next_index;
+          low_link[next] = next_index;
+          next_index += 1;
+          stack.push(next);
+          on_stack[next] = true;
+          call_stack.push((next, 0));
+        } else if on_stack[next] {
+          low_link[node] = low_link[node].min(index[next]);
+        }
+        continue;
+      }
+      call_stack.pop();
+      if let Some((parent, _)) = call_stack.last() {
+        low_link[*parent] = low_link[*parent].min(low_link[node]);
+      }
+      if low_link[node] == index[node] {
+        let mut component = Vec::new();
+        while let Some(member) = stack.pop() {
+          on_stack[member] = false;
+          component.push(member);
+          if member == node {
+            break;
+          }
+        }
+        component.sort_unstable();
+        components.push(component);
+      }
+    }
+  }
+  components
+}
+
+/// The edges of the shortest cycle from `start` back to it, within the
+/// component.
+fn shortest_cycle(
+  edges: &[Vec<ImportEdge>],
+  component: &[usize],
+  start: usize,
+) -> Vec<ImportEdge> {
+  let component = component.iter().copied().collect::<HashSet<_>>();
+  let mut parents = HashMap::<usize, (usize, ImportEdge)>::new();
+  let mut pending = VecDeque::from([start]);
+  while let Some(node) = pending.pop_front() {
+    for edge in &edges[node] {
+      if !component.contains(&edge.to) || parents.contains_key(&edge.to) {
+        continue;
+      }
+      parents.insert(edge.to, (node, *edge));
+      if edge.to == start {
+        let mut cycle = Vec::new();
+        let mut current = start;
+        loop {
+          let (parent, edge) = parents[&current];
+          cycle.push(edge);
+          current = parent;
+          if current == start {
+            break;
+          }
+        }
+        cycle.reverse();
+        return cycle;
+      }
+      pending.push_back(edge.to);
+    }
+  }
+  Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+  use deno_ast::SourceTextInfo;
+
+  use super::*;
+
+  fn edges(adjacency: &[&[usize]]) -> Vec<Vec<ImportEdge>> {
+    let range = SourceTextInfo::new("".into()).range();
+    adjacency
+      .iter()
+      .map(|targets| {
+        targets
+          .iter()
+          .map(|to| ImportEdge { to: *to, range })
+          .collect()
+      })
+      .collect()
+  }
+
+  #[test]
+  fn finds_strongly_connected_components() {
+    // 0 -> 1 -> 2 -> 0, 2 -> 3, 3 -> 3, 4 -> 0
+    let edges = edges(&[&[1], &[2], &[0, 3], &[3], &[0]]);
+    let mut components = strongly_connected_components(&edges);
+    components.sort();
+    assert_eq!(components, vec![vec![0, 1, 2], vec![3], vec![4]]);
+  }
+
+  #[test]
+  fn finds_shortest_cycle() {
+    // 0 -> 1 -> 2 -> 3 -> 0 and 0 -> 2
+    let edges = edges(&[&[1, 2], &[2], &[3], &[0]]);
+    let cycle = shortest_cycle(&edges, &[0, 1, 2, 3], 0);
+    assert_eq!(
+      cycle.iter().map(|edge| edge.to).collect::<Vec<_>>(),
+      vec![2, 3, 0]
+    );
+  }
+}
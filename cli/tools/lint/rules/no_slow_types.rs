@@ -1,6 +1,7 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
 use std::borrow::Cow;
+use std::collections::HashSet;
 
 use deno_ast::diagnostics::Diagnostic;
 use deno_ast::ModuleSpecifier;
@@ -13,6 +14,7 @@ use deno_lint::diagnostic::LintDocsUrl;
 use deno_lint::tags;
 
 use super::PackageLintRule;
+use crate::cache::LazyGraphSourceParser;
 
 const CODE: &str = "no-slow-types";
 
@@ -35,7 +37,9 @@ impl PackageLintRule for NoSlowTypesRule {
   fn lint_package(
     &self,
     graph: &ModuleGraph,
+    _source_parser: LazyGraphSourceParser,
     entrypoints: &[ModuleSpecifier],
+    _files: &HashSet<ModuleSpecifier>,
   ) -> Vec<LintDiagnostic> {
     collect_no_slow_type_diagnostics(graph, entrypoints)
       .into_iter()
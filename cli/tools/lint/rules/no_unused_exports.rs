@@ -0,0 +1,495 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use deno_ast::swc::ast;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
+use deno_ast::ModuleSpecifier;
+use deno_ast::ParsedSource;
+use deno_ast::SourceRange;
+use deno_ast::SourceRangedForSpanned;
+use deno_graph::JsModule;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_lint::diagnostic::LintDiagnostic;
+use deno_lint::diagnostic::LintDiagnosticDetails;
+use deno_lint::diagnostic::LintDiagnosticRange;
+use deno_lint::diagnostic::LintDocsUrl;
+
+use super::PackageLintRule;
+use crate::cache::LazyGraphSourceParser;
+use crate::tools::bench::has_supported_bench_path_name;
+use crate::tools::test::is_supported_test_path;
+
+const CODE: &str = "no-unused-exports";
+
+/// Reports the exports of a package's modules that no module reachable from
+/// the package's exports imports, and the files that can't be reached from
+/// them at all. The exports of the entrypoints are the public API, so they
+/// are always used. Test and bench modules aren't reachable from the
+/// exports, so what they import doesn't count as used.
+#[derive(Debug)]
+pub struct NoUnusedExportsRule;
+
+impl PackageLintRule for NoUnusedExportsRule {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn help_docs_url(&self) -> Cow<'static, str> {
+    Cow::Owned(format!("https://docs.deno.com/lint/rules/{CODE}"))
+  }
+
+  fn lint_package(
+    &self,
+    graph: &ModuleGraph,
+    source_parser: LazyGraphSourceParser,
+    entrypoints: &[ModuleSpecifier],
+    files: &HashSet<ModuleSpecifier>,
+  ) -> Vec<LintDiagnostic> {
+    let reachable = reachable_js_modules(graph, entrypoints);
+    let entrypoints = entrypoints
+      .iter()
+      .map(|specifier| graph.resolve(specifier))
+      .collect::<HashSet<_>>();
+
+    let mut analyses = Vec::with_capacity(reachable.len());
+    let mut used_exports = HashMap::<&ModuleSpecifier, UsedExports>::new();
+    for module in &reachable {
+      let Some(analysis) = ModuleAnalysis::analyze(module, source_parser)
+      else {
+        continue;
+      };
+      for (specifier, usage) in &analysis.imports {
+        for dependency in resolve_dependency(graph, module, specifier) {
+          let used = used_exports.entry(dependency).or_default();
+          match usage {
+            Usage::All => used.all = true,
+            Usage::Names(names) => used.names.extend(names.iter().cloned()),
+          }
+        }
+      }
+      analyses.push((module, analysis));
+    }
+
+    let mut diagnostics = Vec::new();
+    for (module, analysis) in &analyses {
+      if !files.contains(&module.specifier)
+        || entrypoints.contains(&module.specifier)
+      {
+        continue;
+      }
+      let used = used_exports.get(&module.specifier);
+      if used.is_some_and(|used| used.all) {
+        continue;
+      }
+      for (name, range) in &analysis.exports {
+        if used.is_some_and(|used| used.names.contains(name)) {
+          continue;
+        }
+        diagnostics.push(create_diagnostic(
+          &module.specifier,
+          Some(LintDiagnosticRange {
+            text_info: analysis.parsed_source.text_info_lazy().clone(),
+            range: *range,
+            description: None,
+          }),
+          format!("`{name}` is exported, but never imported by a module that's reachable from the package's exports."),
+          "Remove the export, or import it in a module that's reachable from the package's exports. Imports in test and bench modules don't count, as they're not part of the package.",
+        ));
+      }
+    }
+
+    let reachable_specifiers = reachable
+      .iter()
+      .map(|module| &module.specifier)
+      .collect::<HashSet<_>>();
+    for specifier in files {
+      if reachable_specifiers.contains(specifier) || is_test_or_bench(specifier)
+      {
+        continue;
+      }
+      diagnostics.push(create_diagnostic(
+        specifier,
+        None,
+        "This file is not reachable from the exports of the package."
+          .to_string(),
+        "Remove the file, or import it in a module that's reachable from the package's exports.",
+      ));
+    }
+
+    diagnostics.sort_by_cached_key(|d| {
+      (d.specifier.clone(), d.range.as_ref().map(|r| r.range.start))
+    });
+    diagnostics
+  }
+}
+
+fn create_diagnostic(
+  specifier: &ModuleSpecifier,
+  range: Option<LintDiagnosticRange>,
+  message: String,
+  hint: &str,
+) -> LintDiagnostic {
+  LintDiagnostic {
+    specifier: specifier.clone(),
+    range,
+    details: LintDiagnosticDetails {
+      message,
+      code: CODE.to_string(),
+      hint: Some(hint.to_string()),
+      info: vec![],
+      fixes: vec![],
+      custom_docs_url: LintDocsUrl::Default,
+    },
+  }
+}
+
+/// Test and bench files are only run, so they're not expected to be
+/// reachable from the exports.
+fn is_test_or_bench(specifier: &ModuleSpecifier) -> bool {
+  let Ok(path) = specifier.to_file_path() else {
+    return false;
+  };
+  is_supported_test_path(&path) || has_supported_bench_path_name(&path)
+}
+
+/// The local JS modules that the entrypoints import, including the
+/// entrypoints.
+fn reachable_js_modules<'a>(
+  graph: &'a ModuleGraph,
+  entrypoints: &[ModuleSpecifier],
+) -> Vec<&'a JsModule> {
+  let mut modules = Vec::new();
+  let mut seen = HashSet::new();
+  let mut pending = entrypoints
+    .iter()
+    .map(|specifier| graph.resolve(specifier))
+    .collect::<VecDeque<_>>();
+  while let Some(specifier) = pending.pop_front() {
+    if specifier.scheme() != "file" || !seen.insert(specifier) {
+      continue;
+    }
+    let Some(Module::Js(module)) = graph.get(specifier) else {
+      continue;
+    };
+    if let Some(types_dep) = &module.maybe_types_dependency {
+      pending.extend(types_dep.dependency.maybe_specifier());
+    }
+    for dep in module.dependencies.values() {
+      pending.extend(dep.get_code());
+      pending.extend(dep.get_type());
+    }
+    modules.push(module);
+  }
+  modules
+}
+
+fn resolve_dependency<'a>(
+  graph: &'a ModuleGraph,
+  module: &'a JsModule,
+  specifier: &str,
+) -> Vec<&'a ModuleSpecifier> {
+  let Some(dep) = module.dependencies.get(specifier) else {
+    return Vec::new();
+  };
+  dep
+    .get_code()
+    .into_iter()
+    .chain(dep.get_type())
+    .map(|specifier| graph.resolve(specifier))
+    .collect()
+}
+
+#[derive(Debug, Default)]
+struct UsedExports {
+  /// Whether the module is imported as a namespace, dynamically or
+  /// re-exported with `export *`, in which case any export could be used.
+  all: bool,
+  names: HashSet<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Usage {
+  All,
+  Names(Vec<String>),
+}
+
+/// The exports of a module and what it uses of the modules it imports.
+struct ModuleAnalysis {
+  parsed_source: ParsedSource,
+  exports: Vec<(String, SourceRange)>,
+  /// The used exports by the specifier of the imported module.
+  imports: Vec<(String, Usage)>,
+}
+
+impl ModuleAnalysis {
+  fn analyze(
+    module: &JsModule,
+    source_parser: LazyGraphSourceParser,
+  ) -> Option<Self> {
+    let parsed_source = source_parser
+      .get_or_parse_source(&module.specifier)
+      .ok()??;
+    let collector = ExportsAndImportsCollector::collect(&parsed_source);
+    Some(Self {
+      exports: collector.exports,
+      imports: collector.imports,
+      parsed_source,
+    })
+  }
+}
+
+#[derive(Default)]
+struct ExportsAndImportsCollector {
+  exports: Vec<(String, SourceRange)>,
+  imports: Vec<(String, Usage)>,
+}
+
+impl ExportsAndImportsCollector {
+  fn collect(parsed_source: &ParsedSource) -> Self {
+    let mut collector = Self::default();
+    if let deno_ast::ProgramRef::Module(program) = parsed_source.program_ref() {
+      for item in &program.body {
+        if let ast::ModuleItem::ModuleDecl(decl) = item {
+          collector.visit_top_level_decl(decl);
+        }
+      }
+    }
+    parsed_source.program_ref().visit_with(&mut collector);
+    collector
+  }
+
+  fn visit_top_level_decl(&mut self, decl: &ast::ModuleDecl) {
+    match decl {
+      ast::ModuleDecl::Import(import) => {
+        let mut names = Vec::with_capacity(import.specifiers.len());
+        let mut usage = None;
+        for specifier in &import.specifiers {
+          match specifier {
+            ast::ImportSpecifier::Named(named) => names.push(
+              named
+                .imported
+                .as_ref()
+                .map(export_name)
+                .unwrap_or_else(|| named.local.sym.to_string()),
+            ),
+            ast::ImportSpecifier::Default(_) => {
+              names.push("default".to_string())
+            }
+            ast::ImportSpecifier::Namespace(_) => usage = Some(Usage::All),
+          }
+        }
+        self
+          .add_import(&import.src.value, usage.unwrap_or(Usage::Names(names)));
+      }
+      ast::ModuleDecl::ExportDecl(export) => {
+        for ident in decl_idents(&export.decl) {
+          self.exports.push((ident.sym.to_string(), ident.range()));
+        }
+      }
+      ast::ModuleDecl::ExportNamed(export) => {
+        let mut names = Vec::with_capacity(export.specifiers.len());
+        let mut usage = None;
+        for specifier in &export.specifiers {
+          match specifier {
+            ast::ExportSpecifier::Named(named) => {
+              let exported = named.exported.as_ref().unwrap_or(&named.orig);
+              self.exports.push((export_name(exported), exported.range()));
+              names.push(export_name(&named.orig));
+            }
+            ast::ExportSpecifier::Namespace(namespace) => {
+              self
+                .exports
+                .push((export_name(&namespace.name), namespace.name.range()));
+              usage = Some(Usage::All);
+            }
+            ast::ExportSpecifier::Default(default) => {
+              self.exports.push((
+                default.exported.sym.to_string(),
+                default.exported.range(),
+              ));
+              names.push("default".to_string());
+            }
+          }
+        }
+        if let Some(src) = &export.src {
+          self.add_import(&src.value, usage.unwrap_or(Usage::Names(names)));
+        }
+      }
+      ast::ModuleDecl::ExportDefaultDecl(export) => {
+        self.exports.push(("default".to_string(), export.range()));
+      }
+      ast::ModuleDecl::ExportDefaultExpr(export) => {
+        self.exports.push(("default".to_string(), export.range()));
+      }
+      ast::ModuleDecl::ExportAll(export) => {
+        self.add_import(&export.src.value, Usage::All);
+      }
+      ast::ModuleDecl::TsImportEquals(import) => {
+        if let ast::TsModuleRef::TsExternalModuleRef(module_ref) =
+          &import.module_ref
+        {
+          self.add_import(&module_ref.expr.value, Usage::All);
+        }
+      }
+      ast::ModuleDecl::TsExportAssignment(_)
+      | ast::ModuleDecl::TsNamespaceExport(_) => {}
+    }
+  }
+
+  fn add_import(&mut self, specifier: &str, usage: Usage) {
+    self.imports.push((specifier.to_string(), usage));
+  }
+}
+
+/// Collects the dynamic imports and import types, which can be anywhere in
+/// a module.
+impl Visit for ExportsAndImportsCollector {
+  fn visit_call_expr(&mut self, call_expr: &ast::CallExpr) {
+    if let ast::Callee::Import(_) = &call_expr.callee {
+      if let Some(ast::Expr::Lit(ast::Lit::Str(specifier))) =
+        call_expr.args.first().map(|arg| &*arg.expr)
+      {
+        self.add_import(&specifier.value, Usage::All);
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+
+  fn visit_ts_import_type(&mut self, import_type: &ast::TsImportType) {
+    self.add_import(&import_type.arg.value, Usage::All);
+    import_type.visit_children_with(self);
+  }
+}
+
+fn export_name(name: &ast::ModuleExportName) -> String {
+  match name {
+    ast::ModuleExportName::Ident(ident) => ident.sym.to_string(),
+    ast::ModuleExportName::Str(s) => s.value.to_string(),
+  }
+}
+
+/// The identifiers declared by an exported declaration.
+fn decl_idents(decl: &ast::Decl) -> Vec<&ast::Ident> {
+  fn pat_idents<'a>(pat: &'a ast::Pat, idents: &mut Vec<&'a ast::Ident>) {
+    match pat {
+      ast::Pat::Ident(binding_ident) => idents.push(&binding_ident.id),
+      ast::Pat::Array(array_pat) => {
+        for elem in array_pat.elems.iter().flatten() {
+          pat_idents(elem, idents);
+        }
+      }
+      ast::Pat::Rest(rest_pat) => pat_idents(&rest_pat.arg, idents),
+      ast::Pat::Object(object_pat) => {
+        for prop in &object_pat.props {
+          match prop {
+            ast::ObjectPatProp::Assign(assign_pat_prop) => {
+              idents.push(&assign_pat_prop.key.id)
+            }
+            ast::ObjectPatProp::KeyValue(key_value_pat_prop) => {
+              pat_idents(&key_value_pat_prop.value, idents)
+            }
+            ast::ObjectPatProp::Rest(rest_pat) => {
+              pat_idents(&rest_pat.arg, idents)
+            }
+          }
+        }
+      }
+      ast::Pat::Assign(assign_pat) => pat_idents(&assign_pat.left, idents),
+      ast::Pat::Invalid(_) | ast::Pat::Expr(_) => {}
+    }
+  }
+
+  match decl {
+    ast::Decl::Class(class) => vec![&class.ident],
+    ast::Decl::Fn(func) => vec![&func.ident],
+    ast::Decl::Var(var) => {
+      let mut idents = Vec::new();
+      for var_decl in &var.decls {
+        pat_idents(&var_decl.name, &mut idents);
+      }
+      idents
+    }
+    ast::Decl::TsEnum(ts_enum) => vec![&ts_enum.id],
+    ast::Decl::TsModule(ts_module) => match &ts_module.id {
+      ast::TsModuleName::Ident(ident) => vec![ident],
+      ast::TsModuleName::Str(_) => vec![],
+    },
+    ast::Decl::TsTypeAlias(ts_type_alias) => vec![&ts_type_alias.id],
+    ast::Decl::TsInterface(ts_interface) => vec![&ts_interface.id],
+    ast::Decl::Using(_) => vec![],
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use deno_ast::MediaType;
+
+  use super::*;
+
+  fn collect(source: &str) -> ExportsAndImportsCollector {
+    let parsed_source = deno_ast::parse_program(deno_ast::ParseParams {
+      specifier: ModuleSpecifier::parse("file:///mod.ts").unwrap(),
+      text: source.into(),
+      media_type: MediaType::TypeScript,
+      capture_tokens: false,
+      scope_analysis: false,
+      maybe_syntax: None,
+    })
+    .unwrap();
+    ExportsAndImportsCollector::collect(&parsed_source)
+  }
+
+  #[test]
+  fn collects_exports_and_imports() {
+    let collector = collect(
+      r#"import { a, b as c } from "./a.ts";
+import d from "./d.ts";
+import * as e from "./e.ts";
+import type { F } from "./f.ts";
+export { g } from "./g.ts";
+export * from "./h.ts";
+export const [i, { j }] = [1, { j: 2 }];
+export function k() {
+  return import("./l.ts");
+}
+export interface M {}
+export type N = typeof import("./n.ts");
+const o = 1;
+export { o as p };
+export default o;
+namespace Q {
+  export const r = 1;
+}
+"#,
+    );
+    let exports = collector
+      .exports
+      .iter()
+      .map(|(name, _)| name.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(exports, ["g", "i", "j", "k", "M", "N", "p", "default"]);
+    assert_eq!(
+      collector.imports,
+      [
+        (
+          "./a.ts",
+          Usage::Names(vec!["a".to_string(), "b".to_string()])
+        ),
+        ("./d.ts", Usage::Names(vec!["default".to_string()])),
+        ("./e.ts", Usage::All),
+        ("./f.ts", Usage::Names(vec!["F".to_string()])),
+        ("./g.ts", Usage::Names(vec!["g".to_string()])),
+        ("./h.ts", Usage::All),
+        ("./l.ts", Usage::All),
+        ("./n.ts", Usage::All),
+      ]
+      .map(|(specifier, usage)| (specifier.to_string(), usage))
+    );
+  }
+}
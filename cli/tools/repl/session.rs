@@ -475,6 +475,7 @@ impl ReplSession {
         &self.main_module,
         &Default::default(),
         &Default::default(),
+        None,
       )
       .await
       .unwrap();
@@ -0,0 +1,325 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::url::Url;
+use deno_core::ModuleSpecifier;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_path_util::url_to_file_path;
+
+use super::fmt::to_relative_path_or_remote_url;
+use super::TestMode;
+use crate::args::jsr_url;
+use crate::colors;
+use crate::graph_container::MainModuleGraphContainer;
+use crate::util::fs::canonicalize_path;
+use crate::util::git;
+
+const LOCKFILE_NAME: &str = "deno.lock";
+
+/// Files and packages that changed since a git ref.
+#[derive(Debug, Default)]
+struct Changes {
+  /// Canonicalized paths of the changed files that still exist.
+  paths: HashSet<PathBuf>,
+  /// `npm:<name>` and `jsr:<name>` of the packages whose lockfile entries
+  /// differ.
+  packages: HashSet<String>,
+}
+
+impl Changes {
+  fn since(cwd: &Path, git_ref: &str) -> Result<Self, AnyError> {
+    let mut changes = Changes::default();
+    for path in git::changed_files_since(cwd, git_ref)? {
+      if path.file_name().is_some_and(|name| name == LOCKFILE_NAME) {
+        let old = git::file_at_ref(cwd, git_ref, &path)?;
+        let new = std::fs::read_to_string(&path).ok();
+        changes
+          .packages
+          .extend(changed_lockfile_packages(old.as_deref(), new.as_deref()));
+      }
+      if let Ok(path) = canonicalize_path(&path) {
+        changes.paths.insert(path);
+      }
+    }
+    Ok(changes)
+  }
+
+  fn find(&self, graph: &ModuleGraph, specifier: &Url) -> Option<Change> {
+    if let Ok(path) = url_to_file_path(specifier) {
+      let path = canonicalize_path(&path).ok()?;
+      return self.paths.contains(&path).then_some(Change::File);
+    }
+    let package = match graph.get(specifier) {
+      Some(Module::Npm(module)) => {
+        format!("npm:{}", module.nv_reference.nv().name)
+      }
+      _ => format!("jsr:{}", jsr_package_name(specifier)?),
+    };
+    self
+      .packages
+      .contains(&package)
+      .then_some(Change::Package(package))
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Change {
+  File,
+  Package(String),
+}
+
+/// Gets the name of the JSR package that `specifier` belongs to, if any.
+fn jsr_package_name(specifier: &Url) -> Option<String> {
+  let path = specifier.as_str().strip_prefix(jsr_url().as_str())?;
+  let mut parts = path.split('/');
+  let scope = parts.next()?;
+  let name = parts.next()?;
+  Some(format!("{scope}/{name}"))
+}
+
+/// `@scope/name@1.0.0` or `name@1.0.0_peer@2.0.0` -> the package name.
+fn package_name(key: &str) -> &str {
+  let key = key
+    .strip_prefix("npm:")
+    .or_else(|| key.strip_prefix("jsr:"))
+    .unwrap_or(key);
+  match key.get(1..).and_then(|rest| rest.find('@')) {
+    Some(index) => &key[..index + 1],
+    None => key,
+  }
+}
+
+/// Compares the package resolutions of two versions of a lockfile and gets
+/// the packages whose entries were added, removed or changed.
+fn changed_lockfile_packages(
+  old: Option<&str>,
+  new: Option<&str>,
+) -> HashSet<String> {
+  let parse = |text: Option<&str>| {
+    text
+      .and_then(|text| serde_json::from_str::<serde_json::Value>(text).ok())
+      .unwrap_or_default()
+  };
+  let old = parse(old);
+  let new = parse(new);
+  let mut packages = HashSet::new();
+  for (section, prefix) in [
+    ("specifiers", None),
+    ("jsr", Some("jsr")),
+    ("npm", Some("npm")),
+  ] {
+    let empty = serde_json::Map::new();
+    let old = old
+      .get(section)
+      .and_then(|v| v.as_object())
+      .unwrap_or(&empty);
+    let new = new
+      .get(section)
+      .and_then(|v| v.as_object())
+      .unwrap_or(&empty);
+    for key in old.keys().chain(new.keys()) {
+      if old.get(key) == new.get(key) {
+        continue;
+      }
+      let prefix = match prefix {
+        Some(prefix) => prefix,
+        // specifiers are keyed like `npm:chalk@5`
+        None => match key.split_once(':') {
+          Some((prefix @ ("npm" | "jsr"), _)) => prefix,
+          _ => continue,
+        },
+      };
+      packages.insert(format!("{}:{}", prefix, package_name(key)));
+    }
+  }
+  packages
+}
+
+/// Finds the shortest import chain from `root` to a changed module.
+fn find_changed_dependency(
+  graph: &ModuleGraph,
+  root: &ModuleSpecifier,
+  changes: &Changes,
+) -> Option<(Vec<ModuleSpecifier>, Change)> {
+  let root = graph.resolve(root);
+  let mut parents = HashMap::<&ModuleSpecifier, &ModuleSpecifier>::new();
+  let mut seen = HashSet::from([root]);
+  let mut pending = VecDeque::from([root]);
+  while let Some(specifier) = pending.pop_front() {
+    if let Some(change) = changes.find(graph, specifier) {
+      let mut chain = vec![specifier.clone()];
+      let mut current = specifier;
+      while let Some(parent) = parents.get(current) {
+        chain.push((*parent).clone());
+        current = *parent;
+      }
+      chain.reverse();
+      return Some((chain, change));
+    }
+    let mut dependencies = Vec::new();
+    match graph.get(specifier) {
+      Some(Module::Js(module)) => {
+        if let Some(types_dep) = &module.maybe_types_dependency {
+          dependencies.extend(types_dep.dependency.maybe_specifier());
+        }
+        for dep in module.dependencies.values() {
+          dependencies.extend(dep.get_code());
+          dependencies.extend(dep.get_type());
+        }
+      }
+      Some(Module::Wasm(module)) => {
+        for dep in module.dependencies.values() {
+          dependencies.extend(dep.get_code());
+          dependencies.extend(dep.get_type());
+        }
+      }
+      _ => {}
+    }
+    for dependency in dependencies {
+      let dependency = graph.resolve(dependency);
+      if seen.insert(dependency) {
+        parents.insert(dependency, specifier);
+        pending.push_back(dependency);
+      }
+    }
+  }
+  None
+}
+
+/// Keeps only the test modules that are affected by the changes since
+/// `git_ref` and logs why each of them was selected.
+///
+/// The test modules are loaded into a copy of the main graph, so that only
+/// the selected ones are type checked afterwards.
+pub async fn select_changed_test_modules(
+  specifiers_with_mode: Vec<(ModuleSpecifier, TestMode)>,
+  git_ref: &str,
+  cwd: &Url,
+  main_graph_container: &MainModuleGraphContainer,
+) -> Result<Vec<(ModuleSpecifier, TestMode)>, AnyError> {
+  let changes = Changes::since(&url_to_file_path(cwd)?, git_ref)?;
+  let roots = specifiers_with_mode
+    .iter()
+    .filter(|(_, mode)| !matches!(mode, TestMode::Documentation))
+    .map(|(specifier, _)| specifier.clone())
+    .collect::<Vec<_>>();
+  let graph = main_graph_container.load_specifiers(&roots).await?;
+
+  let total = specifiers_with_mode.len();
+  let mut selected = Vec::new();
+  let mut reasons = Vec::new();
+  for (specifier, mode) in specifiers_with_mode {
+    let found = if matches!(mode, TestMode::Documentation) {
+      changes
+        .find(&graph, &specifier)
+        .map(|change| (vec![specifier.clone()], change))
+    } else {
+      find_changed_dependency(&graph, &specifier, &changes)
+    };
+    if let Some((chain, change)) = found {
+      reasons.push(format_reason(cwd, &chain, &change));
+      selected.push((specifier, mode));
+    }
+  }
+
+  if selected.is_empty() {
+    log::info!("No test modules are affected by changes since {git_ref}.");
+  } else {
+    log::info!(
+      "Running {} of {} test modules affected by changes since {}:",
+      selected.len(),
+      total,
+      git_ref
+    );
+    for reason in reasons {
+      log::info!("  {reason}");
+    }
+  }
+  Ok(selected)
+}
+
+fn format_reason(
+  cwd: &Url,
+  chain: &[ModuleSpecifier],
+  change: &Change,
+) -> String {
+  let mut parts = chain
+    .iter()
+    .map(|specifier| to_relative_path_or_remote_url(cwd, specifier.as_str()))
+    .collect::<Vec<_>>();
+  let note = match change {
+    Change::File => "changed".to_string(),
+    Change::Package(package) => {
+      // show the package rather than the url of the module within it
+      parts.pop();
+      parts.push(package.clone());
+      "lockfile entry changed".to_string()
+    }
+  };
+  let first = parts.remove(0);
+  let mut text = first;
+  for part in parts {
+    text.push_str(&colors::gray(format!(" -> {part}")).to_string());
+  }
+  format!("{text} {}", colors::gray(format!("({note})")))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn package_names() {
+    assert_eq!(package_name("chalk@5.3.0"), "chalk");
+    assert_eq!(package_name("@std/assert@1.0.0"), "@std/assert");
+    assert_eq!(package_name("npm:chalk@5"), "chalk");
+    assert_eq!(package_name("jsr:@std/assert@1"), "@std/assert");
+    assert_eq!(package_name("preact@10.0.0_react@18.0.0"), "preact");
+  }
+
+  #[test]
+  fn lockfile_package_changes() {
+    let old = r#"{
+      "version": "4",
+      "specifiers": { "npm:chalk@5": "5.3.0", "jsr:@std/assert@1": "1.0.0" },
+      "jsr": { "@std/assert@1.0.0": { "integrity": "a" } },
+      "npm": { "chalk@5.3.0": { "integrity": "b" } }
+    }"#;
+    let new = r#"{
+      "version": "4",
+      "specifiers": { "npm:chalk@5": "5.4.0", "jsr:@std/assert@1": "1.0.0" },
+      "jsr": { "@std/assert@1.0.0": { "integrity": "a" } },
+      "npm": { "chalk@5.4.0": { "integrity": "c" } }
+    }"#;
+    assert_eq!(
+      changed_lockfile_packages(Some(old), Some(new)),
+      HashSet::from(["npm:chalk".to_string()])
+    );
+    assert_eq!(
+      changed_lockfile_packages(None, Some(new)),
+      HashSet::from(["npm:chalk".to_string(), "jsr:@std/assert".to_string()])
+    );
+    assert!(changed_lockfile_packages(Some(old), Some(old)).is_empty());
+  }
+
+  #[test]
+  fn jsr_package_names() {
+    let specifier = jsr_url().join("@std/assert/1.0.0/mod.ts").unwrap();
+    assert_eq!(
+      jsr_package_name(&specifier),
+      Some("@std/assert".to_string())
+    );
+    assert_eq!(
+      jsr_package_name(&Url::parse("https://deno.land/x/mod.ts").unwrap()),
+      None
+    );
+  }
+}
@@ -2,6 +2,7 @@
 
 use std::fmt::Display;
 use std::future::Future;
+use std::io::BufRead;
 use std::io::Write;
 use std::pin::Pin;
 use std::sync::atomic::AtomicUsize;
@@ -10,15 +11,20 @@ use std::task::ready;
 use std::task::Poll;
 use std::time::Duration;
 
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
 use deno_core::futures::future::poll_fn;
 use deno_core::parking_lot;
 use deno_core::parking_lot::lock_api::RawMutex;
 use deno_core::parking_lot::lock_api::RawMutexTimed;
+use deno_core::serde_json;
 use deno_runtime::deno_io::pipe;
 use deno_runtime::deno_io::AsyncPipeRead;
 use deno_runtime::deno_io::PipeRead;
 use deno_runtime::deno_io::PipeWrite;
 use memmem::Searcher;
+use serde::Deserialize;
+use serde::Serialize;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
 use tokio::io::ReadBuf;
@@ -34,6 +40,11 @@ use super::TestEvent;
 const SYNC_MARKER: &[u8; 8] = &[226, 128, 139, 0, 226, 128, 139, 0];
 const HALF_SYNC_MARKER: &[u8; 4] = &[226, 128, 139, 0];
 
+/// 8-byte marker that precedes every message a test process writes to its
+/// parent, see [`send_test_events_to_parent`]. Equivalent to the string
+/// `"\u{200B}\u{1}\u{200B}\u{1}"`.
+const PROCESS_MESSAGE_MARKER: &[u8; 8] = &[226, 128, 139, 1, 226, 128, 139, 1];
+
 const BUFFER_SIZE: usize = 4096;
 
 /// The test channel has been closed and cannot be used to send further messages.
@@ -436,6 +447,80 @@ impl TestEventSender {
   }
 }
 
+/// A message of a child process of `--isolate=process` to its parent.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProcessMessage {
+  /// An event of the child, with the test and step ids of the child.
+  Event(TestEvent),
+  /// The child sent all of its events. A child that exits without sending
+  /// this crashed.
+  Done,
+}
+
+fn write_process_message(
+  writer: &mut impl Write,
+  message: &ProcessMessage,
+) -> Result<(), AnyError> {
+  let mut bytes = PROCESS_MESSAGE_MARKER.to_vec();
+  serde_json::to_writer(&mut bytes, message)?;
+  bytes.push(b'\n');
+  // a single write so that the message can't be interleaved with output of
+  // native code writing to the same file descriptor
+  writer.write_all(&bytes)?;
+  writer.flush()?;
+  Ok(())
+}
+
+/// Sends the events of a child process of `--isolate=process` to its parent
+/// over stdout, in place of reporting them.
+pub async fn send_test_events_to_parent(
+  mut receiver: TestEventReceiver,
+) -> Result<(), AnyError> {
+  let mut stdout = std::io::stdout();
+  while let Some((_, event)) = receiver.recv().await {
+    match event {
+      // the parent receives the signal as well and reports it
+      TestEvent::Sigint => {
+        #[allow(clippy::disallowed_methods)]
+        std::process::exit(130);
+      }
+      TestEvent::ForceEndReport => break,
+      event => {
+        write_process_message(&mut stdout, &ProcessMessage::Event(event))?
+      }
+    }
+  }
+  write_process_message(&mut stdout, &ProcessMessage::Done)
+}
+
+/// Reads the next message that a child process sent with
+/// [`send_test_events_to_parent`] from its stdout, or `None` once it closed
+/// its stdout. Output in between, e.g. written to the file descriptor by
+/// native code, goes to `output`.
+pub fn read_process_message(
+  reader: &mut impl BufRead,
+  output: &mut impl Write,
+) -> Result<Option<ProcessMessage>, AnyError> {
+  let mut line = Vec::new();
+  loop {
+    line.clear();
+    if reader.read_until(b'\n', &mut line)? == 0 {
+      return Ok(None);
+    }
+    let searcher = memmem::TwoWaySearcher::new(PROCESS_MESSAGE_MARKER);
+    let Some(offset) = searcher.search_in(&line) else {
+      _ = output.write_all(&line);
+      continue;
+    };
+    _ = output.write_all(&line[..offset]);
+    let message =
+      serde_json::from_slice(&line[offset + PROCESS_MESSAGE_MARKER.len()..])
+        .context("Failed to parse a test event of a child process")?;
+    return Ok(Some(message));
+  }
+}
+
 #[allow(clippy::print_stdout)]
 #[allow(clippy::print_stderr)]
 #[cfg(test)]
@@ -444,6 +529,7 @@ mod tests {
   use deno_core::unsync::spawn_blocking;
 
   use super::*;
+  use crate::tools::test::TestFailure;
   use crate::tools::test::TestResult;
 
   /// Test that output is correctly interleaved with messages.
@@ -672,6 +758,35 @@ mod tests {
     }
   }
 
+  /// Test that process messages are read back with the output in between.
+  #[test]
+  fn process_message_round_trip() {
+    let mut bytes = b"native output\npartial ".to_vec();
+    let event =
+      TestEvent::Result(1, TestResult::Failed(TestFailure::FailedSteps(2)), 5);
+    write_process_message(&mut bytes, &ProcessMessage::Event(event)).unwrap();
+    write_process_message(&mut bytes, &ProcessMessage::Done).unwrap();
+
+    let mut reader = bytes.as_slice();
+    let mut output = Vec::new();
+    assert!(matches!(
+      read_process_message(&mut reader, &mut output).unwrap(),
+      Some(ProcessMessage::Event(TestEvent::Result(
+        1,
+        TestResult::Failed(TestFailure::FailedSteps(2)),
+        5
+      )))
+    ));
+    assert_eq!(output.as_slice(), b"native output\npartial ");
+    assert!(matches!(
+      read_process_message(&mut reader, &mut output).unwrap(),
+      Some(ProcessMessage::Done)
+    ));
+    assert!(read_process_message(&mut reader, &mut output)
+      .unwrap()
+      .is_none());
+  }
+
   /// Ensure nothing panics if we're racing the runtime shutdown.
   #[test]
   fn test_runtime_shutdown() {
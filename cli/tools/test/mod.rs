@@ -12,6 +12,8 @@ use std::future::poll_fn;
 use std::io::Write;
 use std::num::NonZeroUsize;
 use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
@@ -66,19 +68,24 @@ use rand::seq::SliceRandom;
 use rand::SeedableRng;
 use regex::Regex;
 use serde::Deserialize;
+use serde::Serialize;
 use tokio::signal;
 
 use crate::args::CliOptions;
 use crate::args::Flags;
 use crate::args::TestFlags;
+use crate::args::TestIsolation;
 use crate::args::TestReporterConfig;
+use crate::args::TypeCheckMode;
 use crate::colors;
 use crate::display;
 use crate::factory::CliFactory;
 use crate::file_fetcher::CliFileFetcher;
 use crate::graph_util::has_graph_root_local_dependent_changed;
 use crate::ops;
+use crate::ops::testing::abort_timed_out_test;
 use crate::sys::CliSys;
+use crate::util::diff::diff;
 use crate::util::extract::extract_doc_tests;
 use crate::util::file_watcher;
 use crate::util::fs::collect_specifiers;
@@ -89,23 +96,41 @@ use crate::worker::CliMainWorkerFactory;
 use crate::worker::CoverageCollector;
 use crate::worker::CreateCustomWorkerError;
 
+mod changed;
 mod channel;
 pub mod fmt;
+mod process;
 pub mod reporters;
+mod shard;
+mod snapshot;
+mod timeout;
 
+use changed::select_changed_test_modules;
 pub use channel::create_single_test_event_channel;
 pub use channel::create_test_event_channel;
+use channel::send_test_events_to_parent;
 pub use channel::TestEventReceiver;
 pub use channel::TestEventSender;
 pub use channel::TestEventWorkerSender;
 use fmt::format_sanitizer_diff;
 pub use fmt::format_test_error;
+use process::stop_on_closed_stdin;
+use process::TestProcessSpawner;
 use reporters::CompoundTestReporter;
 use reporters::DotTestReporter;
+use reporters::JsonTestReporter;
 use reporters::JunitTestReporter;
 use reporters::PrettyTestReporter;
 use reporters::TapTestReporter;
 use reporters::TestReporter;
+use shard::select_shard;
+pub use shard::TestShardSummary;
+use shard::TestShardTimings;
+pub use snapshot::TestSnapshotMismatch;
+pub use snapshot::TestSnapshotSummary;
+pub use snapshot::TestSnapshots;
+pub use timeout::TestTimedOut;
+pub use timeout::TestTimeouts;
 
 use crate::tools::test::channel::ChannelClosedError;
 
@@ -218,7 +243,7 @@ impl TestFilter {
   }
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct TestLocation {
   pub file_name: String,
@@ -247,7 +272,7 @@ impl TestContainer {
   }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Deserialize, Serialize)]
 pub struct TestDescriptions {
   tests: IndexMap<usize, TestDescription>,
 }
@@ -271,7 +296,7 @@ impl<'a> IntoIterator for &'a TestDescriptions {
   }
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct TestDescription {
   pub id: usize,
@@ -282,6 +307,10 @@ pub struct TestDescription {
   pub location: TestLocation,
   pub sanitize_ops: bool,
   pub sanitize_resources: bool,
+  /// Overrides the `--retry` count for this test.
+  pub retries: Option<usize>,
+  /// Overrides the `--timeout` for this test, in milliseconds.
+  pub timeout: Option<u64>,
 }
 
 /// May represent a failure of a test or test step.
@@ -311,13 +340,15 @@ pub struct TestFailureFormatOptions {
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TestFailure {
   JsError(Box<JsError>),
   FailedSteps(usize),
   IncompleteSteps,
   Leaked(Vec<String>, Vec<String>), // Details, trailer notes
+  SnapshotMismatch(TestSnapshotMismatch),
+  TimedOut(TestTimedOut),
   // The rest are for steps only.
   Incomplete,
   OverlapsWithSanitizers(IndexSet<String>), // Long names of overlapped tests
@@ -354,6 +385,31 @@ impl TestFailure {
         }
         Cow::Owned(f)
       }
+      TestFailure::SnapshotMismatch(mismatch) => {
+        let mut f = String::new();
+        match &mismatch.expected {
+          Some(expected) => {
+            writeln!(f, "Snapshot \"{}\" does not match:\n", mismatch.name)
+              .unwrap();
+            write!(f, "{}", diff(expected, &mismatch.actual)).unwrap();
+            write!(f, "\nRun with `--update-snapshots` to update it.").unwrap();
+          }
+          None => {
+            write!(f, "Snapshot \"{}\" does not exist. Run with `--update-snapshots` to create it.", mismatch.name).unwrap();
+          }
+        }
+        Cow::Owned(f)
+      }
+      TestFailure::TimedOut(timed_out) => {
+        let mut f = String::new();
+        write!(f, "{}.", timed_out_message(timed_out)).unwrap();
+        match &timed_out.stack {
+          Some(stack) => write!(f, " It was terminated at:\n{}", stack),
+          None => write!(f, " It was waiting for a promise that didn't settle."),
+        }
+        .unwrap();
+        Cow::Owned(f)
+      }
       TestFailure::OverlapsWithSanitizers(long_names) => {
         let mut f = String::new();
         write!(f, "Started test step while another test step with sanitizers was running:").unwrap();
@@ -383,6 +439,11 @@ impl TestFailure {
       }
       TestFailure::Incomplete => "Didn't complete before parent".to_string(),
       TestFailure::Leaked(_, _) => "Leaks detected".to_string(),
+      TestFailure::SnapshotMismatch(mismatch) => match mismatch.expected {
+        Some(_) => format!("Snapshot \"{}\" does not match", mismatch.name),
+        None => format!("Snapshot \"{}\" does not exist", mismatch.name),
+      },
+      TestFailure::TimedOut(timed_out) => timed_out_message(timed_out),
       TestFailure::OverlapsWithSanitizers(_) => {
         "Started test step while another test step with sanitizers was running"
           .to_string()
@@ -422,17 +483,29 @@ impl TestFailure {
   }
 }
 
+fn timed_out_message(timed_out: &TestTimedOut) -> String {
+  match &timed_out.step {
+    Some(step) => {
+      format!("Step \"{step}\" timed out after {}ms", timed_out.timeout)
+    }
+    None => format!("Timed out after {}ms", timed_out.timeout),
+  }
+}
+
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TestResult {
   Ok,
   Ignored,
   Failed(TestFailure),
   Cancelled,
+  /// Passed after failing at least once. Holds the failures of the previous
+  /// attempts.
+  Flaky(Vec<TestFailure>),
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TestStepDescription {
   pub id: usize,
@@ -446,15 +519,18 @@ pub struct TestStepDescription {
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TestStepResult {
   Ok,
   Ignored,
   Failed(TestFailure),
+  /// Passed after failing at least once. Holds the failures of the previous
+  /// attempts.
+  Flaky(Vec<TestFailure>),
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TestPlan {
   pub origin: String,
@@ -469,9 +545,10 @@ pub enum TestStdioStream {
   Stderr,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub enum TestEvent {
-  Register(Arc<TestDescriptions>),
+  Register(#[serde(with = "shared_test_descriptions")] Arc<TestDescriptions>),
   Plan(TestPlan),
   Wait(usize),
   Output(Vec<u8>),
@@ -481,16 +558,45 @@ pub enum TestEvent {
   StepRegister(TestStepDescription),
   StepWait(usize),
   StepResult(usize, TestStepResult, u64),
+  Snapshots(TestSnapshotSummary),
   /// Indicates that this worker has completed running tests.
   Completed,
   /// Indicates that the user has cancelled the test run with Ctrl+C and
   /// the run should be aborted.
+  #[serde(skip)]
   Sigint,
   /// Used by the REPL to force a report to end without closing the worker
   /// or receiver.
+  #[serde(skip)]
   ForceEndReport,
 }
 
+/// (De)serializes the descriptions of [`TestEvent::Register`], which are
+/// shared with the reporters.
+mod shared_test_descriptions {
+  use std::sync::Arc;
+
+  use serde::Deserialize;
+  use serde::Deserializer;
+  use serde::Serialize;
+  use serde::Serializer;
+
+  use super::TestDescriptions;
+
+  pub fn serialize<S: Serializer>(
+    descriptions: &Arc<TestDescriptions>,
+    serializer: S,
+  ) -> Result<S::Ok, S::Error> {
+    descriptions.as_ref().serialize(serializer)
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+  ) -> Result<Arc<TestDescriptions>, D::Error> {
+    TestDescriptions::deserialize(deserializer).map(Arc::new)
+  }
+}
+
 impl TestEvent {
   // Certain messages require us to ensure that all output has been drained to ensure proper
   // interleaving of output messages.
@@ -520,7 +626,11 @@ pub struct TestSummary {
   pub filtered_out: usize,
   pub measured: usize,
   pub failures: Vec<(TestFailureDescription, TestFailure)>,
+  /// Tests and steps that passed on retry, with their failed attempt count.
+  pub flaky: Vec<(TestFailureDescription, usize)>,
   pub uncaught_errors: Vec<(String, Box<JsError>)>,
+  pub shard: Option<TestShardSummary>,
+  pub snapshots: Vec<TestSnapshotSummary>,
 }
 
 #[derive(Debug, Clone)]
@@ -534,6 +644,12 @@ struct TestSpecifiersOptions {
   reporter: TestReporterConfig,
   junit_path: Option<String>,
   hide_stacktraces: bool,
+  shard: Option<TestShardSummary>,
+  shard_timings_path: Option<PathBuf>,
+  /// Runs every module in a child process for `--isolate=process`.
+  process_spawner: Option<TestProcessSpawner>,
+  /// Whether this is a child process of `--isolate=process`.
+  isolated_child: bool,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -541,6 +657,12 @@ pub struct TestSpecifierOptions {
   pub shuffle: Option<u64>,
   pub filter: TestFilter,
   pub trace_leaks: bool,
+  pub retries: usize,
+  pub update_snapshots: bool,
+  /// Default timeout of the tests, in milliseconds.
+  pub timeout: Option<u64>,
+  /// Whether to write a coverage profile per test.
+  pub coverage_per_test: bool,
 }
 
 impl TestSummary {
@@ -556,7 +678,10 @@ impl TestSummary {
       filtered_out: 0,
       measured: 0,
       failures: Vec::new(),
+      flaky: Vec::new(),
       uncaught_errors: Vec::new(),
+      shard: None,
+      snapshots: Vec::new(),
     }
   }
 
@@ -593,6 +718,9 @@ fn get_test_reporter(options: &TestSpecifiersOptions) -> Box<dyn TestReporter> {
       options.concurrent_jobs > NonZeroUsize::new(1).unwrap(),
       failure_format_options,
     )),
+    TestReporterConfig::Json => {
+      Box::new(JsonTestReporter::new(failure_format_options))
+    }
   };
 
   if let Some(junit_path) = &options.junit_path {
@@ -727,8 +855,24 @@ async fn test_specifier_inner(
 
   worker.dispatch_load_event().map_err(CoreError::Js)?;
 
-  run_tests_for_worker(worker, &specifier, &options, &fail_fast_tracker)
-    .await?;
+  worker
+    .js_runtime
+    .op_state()
+    .borrow_mut()
+    .put(TestSnapshots::new(&specifier, options.update_snapshots));
+  let per_test_coverage_collector = if options.coverage_per_test {
+    coverage_collector.as_deref_mut()
+  } else {
+    None
+  };
+  run_tests_for_worker(
+    worker,
+    &specifier,
+    &options,
+    &fail_fast_tracker,
+    per_test_coverage_collector,
+  )
+  .await?;
 
   // Ignore `defaultPrevented` of the `beforeunload` event. We don't allow the
   // event loop to continue beyond what's needed to await results.
@@ -809,11 +953,14 @@ pub enum RunTestsForWorkerErr {
   SerdeV8(#[from] serde_v8::Error),
 }
 
+/// Runs the tests registered by the module of `worker`. Coverage is written
+/// after every test to `coverage_collector` when it's set.
 pub async fn run_tests_for_worker(
   worker: &mut MainWorker,
   specifier: &ModuleSpecifier,
   options: &TestSpecifierOptions,
   fail_fast_tracker: &FailFastTracker,
+  coverage_collector: Option<&mut dyn CoverageCollector>,
 ) -> Result<(), RunTestsForWorkerErr> {
   let state_rc = worker.js_runtime.op_state();
   // Take whatever tests have been registered
@@ -822,6 +969,8 @@ pub async fn run_tests_for_worker(
 
   let tests: Arc<TestDescriptions> = tests.into();
   send_test_event(&state_rc, TestEvent::Register(tests.clone()))?;
+  let isolate_handle = worker.js_runtime.v8_isolate().thread_safe_handle();
+  state_rc.borrow_mut().put(TestTimeouts::new(isolate_handle));
   let res = run_tests_for_worker_inner(
     worker,
     specifier,
@@ -829,13 +978,35 @@ pub async fn run_tests_for_worker(
     test_functions,
     options,
     fail_fast_tracker,
+    coverage_collector,
   )
-  .await;
+  .await
+  .and_then(|()| finish_snapshots(&state_rc, specifier));
+  // Stops the thread that watches the timeouts.
+  state_rc.borrow_mut().try_take::<TestTimeouts>();
 
   _ = send_test_event(&state_rc, TestEvent::Completed);
   res
 }
 
+/// Writes the snapshot file of the module, if snapshots are supported for
+/// this worker, and reports what changed.
+fn finish_snapshots(
+  state_rc: &RefCell<OpState>,
+  specifier: &ModuleSpecifier,
+) -> Result<(), RunTestsForWorkerErr> {
+  let Some(snapshots) = state_rc.borrow_mut().try_take::<TestSnapshots>()
+  else {
+    return Ok(());
+  };
+  if let Some(summary) =
+    snapshots.finish(specifier).map_err(CoreError::JsBox)?
+  {
+    send_test_event(state_rc, TestEvent::Snapshots(summary))?;
+  }
+  Ok(())
+}
+
 async fn run_tests_for_worker_inner(
   worker: &mut MainWorker,
   specifier: &ModuleSpecifier,
@@ -843,6 +1014,7 @@ async fn run_tests_for_worker_inner(
   test_functions: Vec<v8::Global<v8::Function>>,
   options: &TestSpecifierOptions,
   fail_fast_tracker: &FailFastTracker,
+  mut coverage_collector: Option<&mut dyn CoverageCollector>,
 ) -> Result<(), RunTestsForWorkerErr> {
   let unfiltered = tests.len();
   let state_rc = worker.js_runtime.op_state();
@@ -883,6 +1055,9 @@ async fn run_tests_for_worker_inner(
     }),
   )?;
 
+  // Whether every test of the module ran and passed, see
+  // `TestSnapshots::mark_incomplete()`.
+  let mut ran_all_tests = tests_to_run.len() == unfiltered;
   let mut had_uncaught_error = false;
   let stats = worker.js_runtime.runtime_activity_stats_factory();
   let ops = worker.js_runtime.op_names();
@@ -920,8 +1095,11 @@ async fn run_tests_for_worker_inner(
       .or_insert(1);
   }
 
+  // The test whose coverage is being collected.
+  let mut covered_test: Option<String> = None;
   for (desc, function) in tests_to_run.into_iter() {
     if fail_fast_tracker.should_stop() {
+      ran_all_tests = false;
       break;
     }
 
@@ -936,6 +1114,7 @@ async fn run_tests_for_worker_inner(
       .try_take::<deno_runtime::deno_fetch::Client>();
 
     if desc.ignore {
+      ran_all_tests = false;
       send_test_event(
         &state_rc,
         TestEvent::Result(desc.id, TestResult::Ignored, 0),
@@ -943,12 +1122,25 @@ async fn run_tests_for_worker_inner(
       continue;
     }
     if had_uncaught_error {
+      ran_all_tests = false;
       send_test_event(
         &state_rc,
         TestEvent::Result(desc.id, TestResult::Cancelled, 0),
       )?;
       continue;
     }
+    if let Some(coverage_collector) = coverage_collector.as_deref_mut() {
+      // attributes the coverage since the previous test, or of the top-level
+      // code before the first one
+      take_test_coverage(
+        worker,
+        coverage_collector,
+        specifier,
+        covered_test.as_deref(),
+      )
+      .await?;
+      covered_test = Some(desc.name.clone());
+    }
     send_test_event(&state_rc, TestEvent::Wait(desc.id))?;
 
     // Poll event loop once, to allow all ops that are already resolved, but haven't
@@ -961,72 +1153,47 @@ async fn run_tests_for_worker_inner(
     let before = stats.clone().capture(&filter);
 
     let earlier = Instant::now();
-    let call = worker.js_runtime.call(&function);
-
-    let slow_state_rc = state_rc.clone();
-    let slow_test_id = desc.id;
-    let slow_test_warning = spawn(async move {
-      // The slow test warning should pop up every DENO_SLOW_TEST_TIMEOUT*(2**n) seconds,
-      // with a duration that is doubling each time. So for a warning time of 60s,
-      // we should get a warning at 60s, 120s, 240s, etc.
-      let base_timeout = env::var("DENO_SLOW_TEST_TIMEOUT").unwrap_or_default();
-      let base_timeout = base_timeout.parse().unwrap_or(60).max(1);
-      let mut multiplier = 1;
-      let mut elapsed = 0;
-      loop {
-        tokio::time::sleep(Duration::from_secs(
-          base_timeout * (multiplier - elapsed),
-        ))
-        .await;
-        if send_test_event(
-          &slow_state_rc,
-          TestEvent::Slow(
-            slow_test_id,
-            Duration::from_secs(base_timeout * multiplier).as_millis() as _,
-          ),
-        )
-        .is_err()
-        {
-          break;
+    let retries = desc.retries.unwrap_or(options.retries);
+    let timeout = desc.timeout.or(options.timeout).map(Duration::from_millis);
+    let mut failed_attempts = Vec::new();
+    let result = loop {
+      let may_retry = failed_attempts.len() < retries;
+      match call_test_function(
+        worker, &state_rc, desc.id, &function, may_retry, timeout,
+      )
+      .await?
+      {
+        Ok(TestResult::Failed(failure)) if may_retry => {
+          failed_attempts.push(failure);
         }
-        multiplier *= 2;
-        elapsed += 1;
+        result => break result,
       }
-    });
-
-    let result = worker
-      .js_runtime
-      .with_event_loop_promise(call, PollEventLoopOptions::default())
-      .await;
-    slow_test_warning.abort();
+    };
     let result = match result {
-      Ok(r) => r,
-      Err(error) => {
-        if let CoreError::Js(js_error) = error {
-          send_test_event(
-            &state_rc,
-            TestEvent::UncaughtError(specifier.to_string(), Box::new(js_error)),
-          )?;
-          fail_fast_tracker.add_failure();
-          send_test_event(
-            &state_rc,
-            TestEvent::Result(desc.id, TestResult::Cancelled, 0),
-          )?;
-          had_uncaught_error = true;
-          continue;
-        } else {
-          return Err(error.into());
-        }
+      Ok(result) => result,
+      Err(js_error) => {
+        send_test_event(
+          &state_rc,
+          TestEvent::UncaughtError(specifier.to_string(), Box::new(js_error)),
+        )?;
+        fail_fast_tracker.add_failure();
+        send_test_event(
+          &state_rc,
+          TestEvent::Result(desc.id, TestResult::Cancelled, 0),
+        )?;
+        had_uncaught_error = true;
+        ran_all_tests = false;
+        continue;
       }
     };
-
-    // Check the result before we check for leaks
-    let result = {
-      let scope = &mut worker.js_runtime.handle_scope();
-      let result = v8::Local::new(scope, result);
-      serde_v8::from_v8::<TestResult>(scope, result)?
+    let result = match result {
+      TestResult::Ok if !failed_attempts.is_empty() => {
+        TestResult::Flaky(failed_attempts)
+      }
+      result => result,
     };
     if matches!(result, TestResult::Failed(_)) {
+      ran_all_tests = false;
       fail_fast_tracker.add_failure();
       let elapsed = earlier.elapsed().as_millis();
       send_test_event(
@@ -1071,9 +1238,146 @@ async fn run_tests_for_worker_inner(
       TestEvent::Result(desc.id, result, elapsed as u64),
     )?;
   }
+
+  if let Some(coverage_collector) = coverage_collector {
+    if covered_test.is_some() {
+      take_test_coverage(
+        worker,
+        coverage_collector,
+        specifier,
+        covered_test.as_deref(),
+      )
+      .await?;
+    }
+  }
+
+  if !ran_all_tests {
+    if let Some(snapshots) =
+      state_rc.borrow_mut().try_borrow_mut::<TestSnapshots>()
+    {
+      snapshots.mark_incomplete();
+    }
+  }
   Ok(())
 }
 
+async fn take_test_coverage(
+  worker: &mut MainWorker,
+  coverage_collector: &mut dyn CoverageCollector,
+  specifier: &ModuleSpecifier,
+  test: Option<&str>,
+) -> Result<(), CoreError> {
+  worker
+    .js_runtime
+    .with_event_loop_future(
+      coverage_collector
+        .take_test_coverage(specifier.as_str(), test)
+        .boxed_local(),
+      PollEventLoopOptions::default(),
+    )
+    .await
+}
+
+/// Calls a registered test function once. `may_retry` tells the JS side that
+/// a failure of this attempt will be retried, so the step events it produces
+/// should be held back until the outcome of the attempt is known.
+///
+/// Returns the uncaught error as `Err` if the call threw outside of the
+/// test's own error handling.
+///
+/// A call that exceeds `timeout` is terminated, even if it's stuck in
+/// synchronous code, and fails with `TestFailure::TimedOut`.
+async fn call_test_function(
+  worker: &mut MainWorker,
+  state_rc: &Rc<RefCell<OpState>>,
+  test_id: usize,
+  function: &v8::Global<v8::Function>,
+  may_retry: bool,
+  timeout: Option<Duration>,
+) -> Result<Result<TestResult, JsError>, RunTestsForWorkerErr> {
+  let args = {
+    let scope = &mut worker.js_runtime.handle_scope();
+    let may_retry_local =
+      v8::Local::<v8::Value>::from(v8::Boolean::new(scope, may_retry));
+    [v8::Global::new(scope, may_retry_local)]
+  };
+  let call = worker.js_runtime.call_with_args(function, &args);
+
+  let timeout_expired = {
+    let mut state = state_rc.borrow_mut();
+    let timeouts = state.borrow_mut::<TestTimeouts>();
+    if let Some(timeout) = timeout {
+      timeouts.start(timeout, None);
+    }
+    timeouts.expired()
+  };
+
+  let slow_state_rc = state_rc.clone();
+  let slow_test_warning = spawn(async move {
+    // The slow test warning should pop up every DENO_SLOW_TEST_TIMEOUT*(2**n) seconds,
+    // with a duration that is doubling each time. So for a warning time of 60s,
+    // we should get a warning at 60s, 120s, 240s, etc.
+    let base_timeout = env::var("DENO_SLOW_TEST_TIMEOUT").unwrap_or_default();
+    let base_timeout = base_timeout.parse().unwrap_or(60).max(1);
+    let mut multiplier = 1;
+    let mut elapsed = 0;
+    loop {
+      tokio::time::sleep(Duration::from_secs(
+        base_timeout * (multiplier - elapsed),
+      ))
+      .await;
+      if send_test_event(
+        &slow_state_rc,
+        TestEvent::Slow(
+          test_id,
+          Duration::from_secs(base_timeout * multiplier).as_millis() as _,
+        ),
+      )
+      .is_err()
+      {
+        break;
+      }
+      multiplier *= 2;
+      elapsed += 1;
+    }
+  });
+
+  let result = tokio::select! {
+    biased;
+    result = worker
+      .js_runtime
+      .with_event_loop_promise(call, PollEventLoopOptions::default()) => {
+      Some(result)
+    }
+    _ = timeout_expired => None,
+  };
+  slow_test_warning.abort();
+  let timed_out = state_rc
+    .borrow()
+    .borrow::<TestTimeouts>()
+    .finish(worker.js_runtime.v8_isolate());
+  if let Some(timed_out) = timed_out {
+    let reported_on_step =
+      abort_timed_out_test(&mut state_rc.borrow_mut(), &timed_out);
+    let failure = if reported_on_step {
+      TestFailure::FailedSteps(1)
+    } else {
+      TestFailure::TimedOut(timed_out)
+    };
+    return Ok(Ok(TestResult::Failed(failure)));
+  }
+  let result = match result {
+    Some(Ok(r)) => r,
+    Some(Err(CoreError::Js(js_error))) => return Ok(Err(js_error)),
+    Some(Err(error)) => return Err(error.into()),
+    None => unreachable!("timeouts always expire with a timed out test"),
+  };
+
+  let scope = &mut worker.js_runtime.handle_scope();
+  let result = v8::Local::new(scope, result);
+  Ok(Ok(serde_v8::from_v8::<TestResult>(scope, result)?))
+}
+
 /// The sanitizer must ignore ops, resources and timers that were started at the top-level, but
 /// completed and restarted, replacing themselves with the same "thing". For example, if you run a
 /// `Deno.serve` server at the top level and make fetch requests to it during the test, those ops
@@ -1232,8 +1536,17 @@ async fn test_specifiers(
     cancel_sender.send(TestEvent::Sigint).ok();
   });
   HAS_TEST_RUN_SIGINT_HANDLER.store(true, Ordering::Relaxed);
-  let reporter = get_test_reporter(&options);
+  let mut reporter = get_test_reporter(&options);
+  if let Some(shard) = &options.shard {
+    reporter.report_shard(shard);
+  }
   let fail_fast_tracker = FailFastTracker::new(options.fail_fast);
+  if options.isolated_child && fail_fast_tracker.is_enabled() {
+    stop_on_closed_stdin(fail_fast_tracker.clone());
+  }
+  let cwd = options.cwd.clone();
+  let shard_timings_path = options.shard_timings_path.clone();
+  let isolated_child = options.isolated_child;
 
   let join_handles = specifiers.into_iter().map(move |specifier| {
     let worker_factory = worker_factory.clone();
@@ -1244,29 +1557,52 @@ async fn test_specifiers(
     let worker_sender = test_event_sender_factory.worker();
     let fail_fast_tracker = fail_fast_tracker.clone();
     let specifier_options = options.specifier.clone();
+    let process_spawner = options.process_spawner.clone();
     spawn_blocking(move || {
-      create_and_run_current_thread(test_specifier(
-        worker_factory,
-        permissions_container,
-        specifier,
-        worker_sender,
-        fail_fast_tracker,
-        specifier_options,
-      ))
+      let started = Instant::now();
+      let result = match process_spawner {
+        Some(spawner) => {
+          spawner.run(&specifier, worker_sender, fail_fast_tracker)
+        }
+        None => create_and_run_current_thread(test_specifier(
+          worker_factory,
+          permissions_container,
+          specifier.clone(),
+          worker_sender,
+          fail_fast_tracker,
+          specifier_options,
+        )),
+      };
+      (specifier, started.elapsed(), result)
     })
   });
 
   let join_stream = stream::iter(join_handles)
     .buffer_unordered(concurrent_jobs.get())
-    .collect::<Vec<Result<Result<(), AnyError>, tokio::task::JoinError>>>();
+    .collect::<Vec<_>>();
 
-  let handler = spawn(async move { report_tests(receiver, reporter).await.0 });
+  let handler = spawn(async move {
+    if isolated_child {
+      // the parent process reports the events
+      send_test_events_to_parent(receiver).await
+    } else {
+      report_tests(receiver, reporter).await.0
+    }
+  });
 
   let (join_results, result) = future::join(join_stream, handler).await;
   sigint_handler_handle.abort();
   HAS_TEST_RUN_SIGINT_HANDLER.store(false, Ordering::Relaxed);
-  for join_result in join_results {
-    join_result??;
+  let join_results = join_results.into_iter().collect::<Result<Vec<_>, _>>()?;
+  if let Some(path) = shard_timings_path {
+    let mut timings = TestShardTimings::read(&path)?;
+    for (specifier, elapsed, _) in &join_results {
+      timings.insert(&cwd, specifier, *elapsed);
+    }
+    timings.write(&path)?;
+  }
+  for (_, _, result) in join_results {
+    result?;
   }
   result??;
 
@@ -1341,6 +1677,9 @@ pub async fn report_tests(
           reporter.report_step_wait(test_steps.get(&id).unwrap());
         }
       }
+      TestEvent::Snapshots(summary) => {
+        reporter.report_snapshots(&summary);
+      }
       TestEvent::StepResult(id, result, duration) => {
         if tests_with_result.insert(id) {
           reporter.report_step_result(
@@ -1556,7 +1895,15 @@ pub async fn run_tests(
   flags: Arc<Flags>,
   test_flags: TestFlags,
 ) -> Result<(), AnyError> {
-  let factory = CliFactory::from_flags(flags);
+  let flags = if test_flags.isolated_module.is_some() {
+    // the parent process already type checked all modules
+    let mut flags = Arc::unwrap_or_clone(flags);
+    flags.type_check_mode = TypeCheckMode::None;
+    Arc::new(flags)
+  } else {
+    flags
+  };
+  let factory = CliFactory::from_flags(flags.clone());
   let cli_options = factory.cli_options()?;
   let workspace_test_options =
     cli_options.resolve_workspace_test_options(&test_flags);
@@ -1571,21 +1918,59 @@ pub async fn run_tests(
   )?;
   let log_level = cli_options.log_level();
 
+  let isolated_module = workspace_test_options
+    .isolated_module
+    .as_deref()
+    .map(ModuleSpecifier::parse)
+    .transpose()?;
+
   let members_with_test_options =
     cli_options.resolve_test_options_for_members(&test_flags)?;
-  let specifiers_with_mode = fetch_specifiers_with_test_mode(
+  let mut specifiers_with_mode = fetch_specifiers_with_test_mode(
     cli_options,
     file_fetcher,
     members_with_test_options.into_iter().map(|(_, v)| v.files),
     &workspace_test_options.doc,
   )
   .await?;
+  if let Some(module) = &isolated_module {
+    // keep the doc tests of the module, which are named `<module>$<lines>`
+    specifiers_with_mode.retain(|(specifier, _)| {
+      module == specifier
+        || module.as_str().starts_with(&format!("{specifier}$"))
+    });
+  }
 
   if !workspace_test_options.permit_no_files && specifiers_with_mode.is_empty()
   {
     return Err(anyhow!("No test modules found"));
   }
 
+  let cwd =
+    Url::from_directory_path(cli_options.initial_cwd()).map_err(|_| {
+      anyhow!(
+        "Unable to construct URL from the path of cwd: {}",
+        cli_options.initial_cwd().to_string_lossy(),
+      )
+    })?;
+  let specifiers_with_mode = if isolated_module.is_some() {
+    specifiers_with_mode
+  } else if let Some(git_ref) = &workspace_test_options.changed_since {
+    let specifiers_with_mode = select_changed_test_modules(
+      specifiers_with_mode,
+      git_ref,
+      &cwd,
+      factory.main_module_graph_container().await?,
+    )
+    .await?;
+    if specifiers_with_mode.is_empty() {
+      return Ok(());
+    }
+    specifiers_with_mode
+  } else {
+    specifiers_with_mode
+  };
+
   let doc_tests = get_doc_tests(&specifiers_with_mode, file_fetcher).await?;
   let specifiers_for_typecheck_and_test =
     get_target_specifiers(specifiers_with_mode, &doc_tests);
@@ -1593,6 +1978,30 @@ pub async fn run_tests(
     file_fetcher.insert_memory_files(doc_test);
   }
 
+  let shard_timings_path = workspace_test_options
+    .shard_timings
+    .as_ref()
+    .filter(|_| isolated_module.is_none())
+    .map(|path| cli_options.initial_cwd().join(path));
+  let (specifiers_for_typecheck_and_test, shard) =
+    if let Some(module) = &isolated_module {
+      let specifiers = specifiers_for_typecheck_and_test
+        .into_iter()
+        .filter(|specifier| specifier == module)
+        .collect();
+      (specifiers, None)
+    } else if let Some(shard) = workspace_test_options.shard {
+      let timings = match &shard_timings_path {
+        Some(path) => TestShardTimings::read(path)?,
+        None => TestShardTimings::default(),
+      };
+      let (specifiers, summary) =
+        select_shard(specifiers_for_typecheck_and_test, shard, &timings, &cwd);
+      (specifiers, Some(summary))
+    } else {
+      (specifiers_for_typecheck_and_test, None)
+    };
+
   let main_graph_container = factory.main_module_graph_container().await?;
 
   // Typecheck
@@ -1617,14 +2026,7 @@ pub async fn run_tests(
     permission_desc_parser,
     specifiers_for_typecheck_and_test,
     TestSpecifiersOptions {
-      cwd: Url::from_directory_path(cli_options.initial_cwd()).map_err(
-        |_| {
-          anyhow!(
-            "Unable to construct URL from the path of cwd: {}",
-            cli_options.initial_cwd().to_string_lossy(),
-          )
-        },
-      )?,
+      cwd,
       concurrent_jobs: workspace_test_options.concurrent_jobs,
       fail_fast: workspace_test_options.fail_fast,
       log_level,
@@ -1632,10 +2034,24 @@ pub async fn run_tests(
       reporter: workspace_test_options.reporter,
       junit_path: workspace_test_options.junit_path,
       hide_stacktraces: workspace_test_options.hide_stacktraces,
+      shard,
+      shard_timings_path,
+      // the child processes run their module in a worker
+      process_spawner: match workspace_test_options.isolate {
+        TestIsolation::Process if isolated_module.is_none() => {
+          Some(TestProcessSpawner::new(&flags, &test_flags)?)
+        }
+        _ => None,
+      },
+      isolated_child: isolated_module.is_some(),
       specifier: TestSpecifierOptions {
         filter: TestFilter::from_flag(&workspace_test_options.filter),
         shuffle: workspace_test_options.shuffle,
         trace_leaks: workspace_test_options.trace_leaks,
+        retries: workspace_test_options.retry,
+        update_snapshots: workspace_test_options.update_snapshots,
+        timeout: workspace_test_options.timeout,
+        coverage_per_test: workspace_test_options.coverage_per_test,
       },
     },
   )
@@ -1814,10 +2230,18 @@ pub async fn run_tests_with_watch(
             reporter: workspace_test_options.reporter,
             junit_path: workspace_test_options.junit_path,
             hide_stacktraces: workspace_test_options.hide_stacktraces,
+            shard: None,
+            shard_timings_path: None,
+            process_spawner: None,
+            isolated_child: false,
             specifier: TestSpecifierOptions {
               filter: TestFilter::from_flag(&workspace_test_options.filter),
               shuffle: workspace_test_options.shuffle,
               trace_leaks: workspace_test_options.trace_leaks,
+              retries: workspace_test_options.retry,
+              update_snapshots: workspace_test_options.update_snapshots,
+              timeout: workspace_test_options.timeout,
+              coverage_per_test: workspace_test_options.coverage_per_test,
             },
           },
         )
@@ -1898,6 +2322,20 @@ impl FailFastTracker {
       false
     }
   }
+
+  pub fn is_enabled(&self) -> bool {
+    self.max_count.is_some()
+  }
+
+  /// Makes `should_stop()` return `true` regardless of the failures tracked
+  /// here, for failures tracked by another process.
+  pub fn stop(&self) {
+    if let Some(max_count) = &self.max_count {
+      self
+        .failure_count
+        .fetch_max(*max_count, std::sync::atomic::Ordering::SeqCst);
+    }
+  }
 }
 
 #[cfg(test)]
@@ -0,0 +1,533 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! Runs test modules in child processes for `--isolate=process`.
+//!
+//! The parent runs `deno test --isolated-module=<url>` with the flags of its
+//! own run for every test module. The child runs that module only and sends
+//! its test events to its stdout instead of reporting them, which the parent
+//! forwards into its own test event channel.
+
+use std::collections::HashMap;
+use std::io::BufReader;
+use std::io::Read;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use deno_config::deno_json::NodeModulesDirMode;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_lib::args::CaData;
+
+use super::channel::read_process_message;
+use super::channel::ProcessMessage;
+use super::*;
+use crate::args::ConfigFlag;
+
+/// Stops the tests of a child process once the parent closes its stdin,
+/// which it does when `--fail-fast` was triggered by another module.
+pub fn stop_on_closed_stdin(fail_fast_tracker: FailFastTracker) {
+  std::thread::spawn(move || {
+    _ = std::io::stdin().read_to_end(&mut Vec::new());
+    fail_fast_tracker.stop();
+  });
+}
+
+/// Spawns a child process per test module and forwards its events.
+#[derive(Clone, Debug)]
+pub struct TestProcessSpawner {
+  exe: PathBuf,
+  /// The arguments of the children before `--isolated-module`.
+  args: Vec<String>,
+  /// The script arguments of the tests.
+  script_args: Vec<String>,
+  next_id: Arc<AtomicUsize>,
+}
+
+impl TestProcessSpawner {
+  pub fn new(flags: &Flags, test_flags: &TestFlags) -> Result<Self, AnyError> {
+    Ok(Self {
+      exe: env::current_exe()?,
+      args: child_args(flags, test_flags),
+      script_args: flags.argv.clone(),
+      next_id: Default::default(),
+    })
+  }
+
+  /// Runs the tests of `specifier` in a child process. Blocks until it
+  /// exited.
+  pub fn run(
+    &self,
+    specifier: &ModuleSpecifier,
+    worker_sender: TestEventWorkerSender,
+    fail_fast_tracker: FailFastTracker,
+  ) -> Result<(), AnyError> {
+    if fail_fast_tracker.should_stop() {
+      return Ok(());
+    }
+    let TestEventWorkerSender {
+      mut sender,
+      mut stdout,
+      mut stderr,
+    } = worker_sender;
+    let mut child = Command::new(&self.exe)
+      .args(&self.args)
+      .arg(format!("--isolated-module={specifier}"))
+      .arg("--")
+      .args(&self.script_args)
+      // only needed to tell the child to stop for `--fail-fast`, so leave it
+      // to the tests otherwise
+      .stdin(if fail_fast_tracker.is_enabled() {
+        Stdio::piped()
+      } else {
+        Stdio::inherit()
+      })
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .spawn()
+      .with_context(|| format!("Failed to spawn a process for {specifier}"))?;
+    let mut child_stdin = child.stdin.take();
+    let mut child_stderr = child.stderr.take().unwrap();
+    let stderr_thread = std::thread::spawn(move || {
+      _ = std::io::copy(&mut child_stderr, &mut stderr);
+    });
+
+    let mut events = ProcessEvents {
+      ids: HashMap::new(),
+      running: IndexSet::new(),
+      next_id: &self.next_id,
+      completed: false,
+      done: false,
+    };
+    let mut reader = BufReader::new(child.stdout.take().unwrap());
+    let result = loop {
+      let message = match read_process_message(&mut reader, &mut stdout) {
+        Ok(Some(message)) => message,
+        Ok(None) => break Ok(()),
+        Err(err) => break Err(err),
+      };
+      match events.map(message, &fail_fast_tracker) {
+        Ok(Some(event)) => sender.send(event)?,
+        Ok(None) => {}
+        Err(err) => break Err(err),
+      }
+      if child_stdin.is_some() && fail_fast_tracker.should_stop() {
+        // closing stdin stops the child before its next test
+        child_stdin = None;
+      }
+    };
+    if result.is_err() {
+      // the events of the child can't be trusted anymore
+      _ = child.kill();
+    }
+    drop(child_stdin);
+    let status = child.wait()?;
+    _ = stderr_thread.join();
+
+    if !events.done {
+      let message = match &result {
+        Ok(()) => format!("Test process crashed ({status})"),
+        Err(err) => format!("{err:#}"),
+      };
+      let error = serde_json::from_value(json!({
+        "name": "Error",
+        "message": message,
+        "exceptionMessage": format!("Uncaught Error: {message}"),
+        "frames": [],
+        "additionalProperties": [],
+      }))?;
+      sender.send(TestEvent::UncaughtError(specifier.to_string(), error))?;
+      fail_fast_tracker.add_failure();
+      for id in events.running.drain(..) {
+        sender.send(TestEvent::Result(id, TestResult::Cancelled, 0))?;
+      }
+      if !events.completed {
+        sender.send(TestEvent::Completed)?;
+      }
+    }
+    sender.flush()?;
+    Ok(())
+  }
+}
+
+/// Builds the arguments of the child processes from the flags of this
+/// process, the same way `deno install` does for the scripts it installs.
+fn child_args(flags: &Flags, test_flags: &TestFlags) -> Vec<String> {
+  let mut args = vec!["test".to_string()];
+  args.extend(flags.to_permission_args());
+  if let Some(url) = &flags.location {
+    args.push(format!("--location={url}"));
+  }
+  if let Some(CaData::File(ca_file)) = &flags.ca_data {
+    args.push(format!("--cert={ca_file}"));
+  }
+  match flags.log_level {
+    Some(Level::Error) => args.push("--quiet".to_string()),
+    Some(Level::Debug) => args.push("--log-level=debug".to_string()),
+    Some(Level::Info) => args.push("--log-level=info".to_string()),
+    _ => {}
+  }
+  // these change how modules are resolved, so they're passed on for the
+  // children to load the same modules as the parent
+  let unstable_config = &flags.unstable_config;
+  if unstable_config.legacy_flag_enabled {
+    args.push("--unstable".to_string());
+  }
+  if unstable_config.bare_node_builtins {
+    args.push("--unstable-bare-node-builtins".to_string());
+  }
+  if unstable_config.detect_cjs {
+    args.push("--unstable-detect-cjs".to_string());
+  }
+  if unstable_config.sloppy_imports {
+    args.push("--unstable-sloppy-imports".to_string());
+  }
+  if unstable_config.npm_lazy_caching {
+    args.push("--unstable-npm-lazy-caching".to_string());
+  }
+  for feature in &unstable_config.features {
+    args.push(format!("--unstable-{feature}"));
+  }
+  if flags.no_remote {
+    args.push("--no-remote".to_string());
+  }
+  if flags.no_npm {
+    args.push("--no-npm".to_string());
+  }
+  if flags.cached_only {
+    args.push("--cached-only".to_string());
+  }
+  if let Some(frozen) = flags.frozen_lockfile {
+    args.push(format!("--frozen={frozen}"));
+  }
+  if let Some(mode) = flags.node_modules_dir {
+    let mode = match mode {
+      NodeModulesDirMode::Auto => "auto",
+      NodeModulesDirMode::Manual => "manual",
+      NodeModulesDirMode::None => "none",
+    };
+    args.push(format!("--node-modules-dir={mode}"));
+  }
+  if let Some(vendor) = flags.vendor {
+    args.push(format!("--vendor={vendor}"));
+  }
+  if !flags.v8_flags.is_empty() {
+    args.push(format!("--v8-flags={}", flags.v8_flags.join(",")));
+  }
+  if let Some(seed) = flags.seed {
+    args.push(format!("--seed={seed}"));
+  }
+  if let Some(import_map_path) = &flags.import_map_path {
+    args.push(format!("--import-map={import_map_path}"));
+  }
+  match &flags.config_flag {
+    ConfigFlag::Discover => {}
+    ConfigFlag::Path(config_path) => {
+      args.push(format!("--config={config_path}"))
+    }
+    ConfigFlag::Disabled => args.push("--no-config".to_string()),
+  }
+  if flags.no_lock {
+    args.push("--no-lock".to_string());
+  } else if let Some(lock) = &flags.lock {
+    args.push(format!("--lock={lock}"));
+  }
+  for env_file in flags.env_file.iter().flatten() {
+    args.push(format!("--env-file={env_file}"));
+  }
+  if let Some(ext) = &flags.ext {
+    args.push(format!("--ext={ext}"));
+  }
+  // the parent process already type checked all modules
+  args.push("--no-check".to_string());
+
+  if test_flags.doc {
+    args.push("--doc".to_string());
+  }
+  if let Some(filter) = &test_flags.filter {
+    args.push(format!("--filter={filter}"));
+  }
+  if let Some(fail_fast) = test_flags.fail_fast {
+    args.push(format!("--fail-fast={fail_fast}"));
+  }
+  if let Some(seed) = test_flags.shuffle {
+    args.push(format!("--shuffle={seed}"));
+  }
+  if test_flags.trace_leaks {
+    args.push("--trace-leaks".to_string());
+  }
+  if let Some(coverage_dir) = &test_flags.coverage_dir {
+    // the parent already cleaned the directory for `--clean`
+    args.push(format!("--coverage={coverage_dir}"));
+    if test_flags.coverage_per_test {
+      args.push("--coverage-per-test".to_string());
+    }
+  }
+  if test_flags.retry > 0 {
+    args.push(format!("--retry={}", test_flags.retry));
+  }
+  if let Some(timeout) = test_flags.timeout {
+    args.push(format!("--timeout={timeout}"));
+  }
+  if test_flags.update_snapshots {
+    args.push("--update-snapshots".to_string());
+  }
+  if !test_flags.files.ignore.is_empty() {
+    args.push(format!("--ignore={}", test_flags.files.ignore.join(",")));
+  }
+  // the child finds the same modules as this process and keeps the one it
+  // runs
+  args.extend(test_flags.files.include.iter().cloned());
+  args
+}
+
+/// Turns the events of one child into events of this process.
+struct ProcessEvents<'a> {
+  /// Child test and step ids to the ones of this process.
+  ids: HashMap<usize, usize>,
+  /// Tests that started but have no result yet.
+  running: IndexSet<usize>,
+  next_id: &'a AtomicUsize,
+  completed: bool,
+  done: bool,
+}
+
+impl ProcessEvents<'_> {
+  fn register(&mut self, child_id: usize) -> usize {
+    let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+    self.ids.insert(child_id, id);
+    id
+  }
+
+  fn id(&self, child_id: usize) -> Result<usize, AnyError> {
+    // the child registers everything before reporting on it
+    match self.ids.get(&child_id) {
+      Some(id) => Ok(*id),
+      None => bail!("Test process sent an event for the unknown id {child_id}"),
+    }
+  }
+
+  fn map(
+    &mut self,
+    message: ProcessMessage,
+    fail_fast_tracker: &FailFastTracker,
+  ) -> Result<Option<TestEvent>, AnyError> {
+    let event = match message {
+      ProcessMessage::Event(event) => event,
+      ProcessMessage::Done => {
+        self.done = true;
+        return Ok(None);
+      }
+    };
+    let event = match event {
+      TestEvent::Register(descriptions) => {
+        let mut tests = IndexMap::new();
+        for description in descriptions.tests.values() {
+          let mut description = description.clone();
+          description.id = self.register(description.id);
+          tests.insert(description.id, description);
+        }
+        TestEvent::Register(Arc::new(TestDescriptions { tests }))
+      }
+      TestEvent::Wait(id) => {
+        let id = self.id(id)?;
+        self.running.insert(id);
+        TestEvent::Wait(id)
+      }
+      TestEvent::Slow(id, elapsed) => TestEvent::Slow(self.id(id)?, elapsed),
+      TestEvent::Result(id, result, elapsed) => {
+        let id = self.id(id)?;
+        self.running.shift_remove(&id);
+        if matches!(result, TestResult::Failed(_)) {
+          fail_fast_tracker.add_failure();
+        }
+        TestEvent::Result(id, result, elapsed)
+      }
+      TestEvent::UncaughtError(origin, error) => {
+        fail_fast_tracker.add_failure();
+        TestEvent::UncaughtError(origin, error)
+      }
+      TestEvent::StepRegister(mut description) => {
+        description.parent_id = self.id(description.parent_id)?;
+        description.root_id = self.id(description.root_id)?;
+        description.id = self.register(description.id);
+        TestEvent::StepRegister(description)
+      }
+      TestEvent::StepWait(id) => TestEvent::StepWait(self.id(id)?),
+      TestEvent::StepResult(id, result, elapsed) => {
+        TestEvent::StepResult(self.id(id)?, result, elapsed)
+      }
+      TestEvent::Completed => {
+        self.completed = true;
+        TestEvent::Completed
+      }
+      TestEvent::Sigint | TestEvent::ForceEndReport => {
+        bail!("Test process sent an unexpected event")
+      }
+      event @ (TestEvent::Plan(_)
+      | TestEvent::Output(_)
+      | TestEvent::Snapshots(_)) => event,
+    };
+    Ok(Some(event))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use deno_lib::args::UnstableConfig;
+
+  use super::*;
+  use crate::args::FileFlags;
+  use crate::args::PermissionFlags;
+
+  fn location() -> TestLocation {
+    TestLocation {
+      file_name: "file:///a_test.ts".to_string(),
+      line_number: 1,
+      column_number: 6,
+    }
+  }
+
+  fn event(event: TestEvent) -> ProcessMessage {
+    ProcessMessage::Event(event)
+  }
+
+  #[test]
+  fn remaps_child_ids() {
+    let next_id = AtomicUsize::new(10);
+    let mut events = ProcessEvents {
+      ids: HashMap::new(),
+      running: IndexSet::new(),
+      next_id: &next_id,
+      completed: false,
+      done: false,
+    };
+    let tracker = FailFastTracker::new(None);
+    let description = TestDescription {
+      id: 3,
+      name: "test".to_string(),
+      ignore: false,
+      only: false,
+      origin: "file:///a_test.ts".to_string(),
+      location: location(),
+      sanitize_ops: true,
+      sanitize_resources: true,
+      retries: None,
+      timeout: None,
+    };
+    let mut tests = IndexMap::new();
+    tests.insert(description.id, description);
+    let Ok(Some(TestEvent::Register(tests))) = events.map(
+      event(TestEvent::Register(Arc::new(TestDescriptions { tests }))),
+      &tracker,
+    ) else {
+      panic!("expected a register event");
+    };
+    assert_eq!(tests.tests.keys().copied().collect::<Vec<_>>(), vec![10]);
+    assert_eq!(tests.tests[&10].id, 10);
+    assert!(matches!(
+      events.map(event(TestEvent::Wait(3)), &tracker),
+      Ok(Some(TestEvent::Wait(10)))
+    ));
+    let step = TestStepDescription {
+      id: 4,
+      name: "step".to_string(),
+      origin: "file:///a_test.ts".to_string(),
+      location: location(),
+      level: 1,
+      parent_id: 3,
+      root_id: 3,
+      root_name: "test".to_string(),
+    };
+    let Ok(Some(TestEvent::StepRegister(step))) =
+      events.map(event(TestEvent::StepRegister(step)), &tracker)
+    else {
+      panic!("expected a step register event");
+    };
+    assert_eq!((step.id, step.parent_id, step.root_id), (11, 10, 10));
+    assert_eq!(events.running.iter().copied().collect::<Vec<_>>(), vec![10]);
+    assert!(matches!(
+      events.map(event(TestEvent::Result(3, TestResult::Ok, 1)), &tracker),
+      Ok(Some(TestEvent::Result(10, TestResult::Ok, 1)))
+    ));
+    assert!(events.running.is_empty());
+    assert!(matches!(
+      events.map(ProcessMessage::Done, &tracker),
+      Ok(None)
+    ));
+    assert!(events.done);
+  }
+
+  #[test]
+  fn errors_on_unknown_ids() {
+    let next_id = AtomicUsize::new(0);
+    let mut events = ProcessEvents {
+      ids: HashMap::new(),
+      running: IndexSet::new(),
+      next_id: &next_id,
+      completed: false,
+      done: false,
+    };
+    let tracker = FailFastTracker::new(None);
+    let err = events
+      .map(event(TestEvent::Result(7, TestResult::Ok, 1)), &tracker)
+      .unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Test process sent an event for the unknown id 7"
+    );
+  }
+
+  #[test]
+  fn builds_child_args_from_flags() {
+    let flags = Flags {
+      config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
+      no_lock: true,
+      seed: Some(5),
+      argv: vec!["--arg".to_string()],
+      unstable_config: UnstableConfig {
+        sloppy_imports: true,
+        detect_cjs: true,
+        features: vec!["kv".to_string()],
+        ..Default::default()
+      },
+      permissions: PermissionFlags {
+        allow_read: Some(vec![]),
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+    let test_flags = TestFlags {
+      filter: Some("foo".to_string()),
+      retry: 2,
+      files: FileFlags {
+        include: vec!["tests/".to_string()],
+        ignore: vec!["tests/fixtures".to_string()],
+      },
+      isolate: TestIsolation::Process,
+      ..Default::default()
+    };
+    assert_eq!(
+      child_args(&flags, &test_flags),
+      vec![
+        "test",
+        "--allow-read",
+        "--unstable-detect-cjs",
+        "--unstable-sloppy-imports",
+        "--unstable-kv",
+        "--seed=5",
+        "--config=deno.jsonc",
+        "--no-lock",
+        "--no-check",
+        "--filter=foo",
+        "--retry=2",
+        "--ignore=tests/fixtures",
+        "tests/",
+      ]
+    );
+  }
+}
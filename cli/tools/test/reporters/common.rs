@@ -174,6 +174,55 @@ pub(super) fn report_summary(
     }
   }
 
+  if !summary.flaky.is_empty() {
+    // note: the trailing whitespace is intentional to get a yellow background
+    writeln!(writer, "\n{}\n", colors::black_on_yellow(" FLAKY ")).unwrap();
+    for (description, failed_attempts) in &summary.flaky {
+      let inflection = if *failed_attempts == 1 {
+        "attempt"
+      } else {
+        "attempts"
+      };
+      writeln!(
+        writer,
+        "{} {}",
+        format_test_for_summary(cwd, description),
+        colors::gray(format!(
+          "(passed after {failed_attempts} failed {inflection})"
+        ))
+      )
+      .unwrap();
+    }
+  }
+
+  let obsolete_snapshots = summary
+    .snapshots
+    .iter()
+    .filter(|snapshots| !snapshots.removed && !snapshots.obsolete.is_empty())
+    .collect::<Vec<_>>();
+  if !obsolete_snapshots.is_empty() {
+    // note: the trailing whitespace is intentional to get a yellow background
+    writeln!(
+      writer,
+      "\n{}\n",
+      colors::black_on_yellow(" OBSOLETE SNAPSHOTS ")
+    )
+    .unwrap();
+    for snapshots in obsolete_snapshots {
+      writeln!(
+        writer,
+        "{}",
+        to_relative_path_or_remote_url(cwd, &snapshots.origin)
+      )
+      .unwrap();
+      for name in &snapshots.obsolete {
+        writeln!(writer, "  {}", colors::gray(name)).unwrap();
+      }
+    }
+    writeln!(writer, "\nRun with `--update-snapshots` to remove them.")
+      .unwrap();
+  }
+
   let status = if summary.has_failed() {
     colors::red("FAILED").to_string()
   } else {
@@ -212,6 +261,53 @@ pub(super) fn report_summary(
     .unwrap()
   }
 
+  if !summary.flaky.is_empty() {
+    write!(summary_result, " | {} flaky", summary.flaky.len()).unwrap();
+  }
+
+  let get_snapshots_text = |count: usize| -> String {
+    if count == 1 {
+      "1 snapshot".to_string()
+    } else {
+      format!("{count} snapshots")
+    }
+  };
+  let mut updated_snapshots = 0;
+  let mut obsolete_snapshots = 0;
+  let mut removed_snapshots = 0;
+  for snapshots in &summary.snapshots {
+    updated_snapshots += snapshots.updated;
+    if snapshots.removed {
+      removed_snapshots += snapshots.obsolete.len();
+    } else {
+      obsolete_snapshots += snapshots.obsolete.len();
+    }
+  }
+  if updated_snapshots > 0 {
+    write!(
+      summary_result,
+      " | {} updated",
+      get_snapshots_text(updated_snapshots)
+    )
+    .unwrap();
+  }
+  if obsolete_snapshots > 0 {
+    write!(
+      summary_result,
+      " | {} obsolete",
+      get_snapshots_text(obsolete_snapshots)
+    )
+    .unwrap();
+  }
+  if removed_snapshots > 0 {
+    write!(
+      summary_result,
+      " | {} removed",
+      get_snapshots_text(removed_snapshots)
+    )
+    .unwrap();
+  }
+
   if summary.measured > 0 {
     write!(summary_result, " | {} measured", summary.measured,).unwrap();
   }
@@ -220,6 +316,20 @@ pub(super) fn report_summary(
     write!(summary_result, " | {} filtered out", summary.filtered_out).unwrap()
   };
 
+  if let Some(shard) = &summary.shard {
+    let inflection = if shard.skipped_files == 1 {
+      "file"
+    } else {
+      "files"
+    };
+    write!(
+      summary_result,
+      " | shard {}/{} ({} {} skipped)",
+      shard.index, shard.count, shard.skipped_files, inflection
+    )
+    .unwrap()
+  }
+
   writeln!(
     writer,
     "\n{} | {} {}",
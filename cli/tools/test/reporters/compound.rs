@@ -19,6 +19,18 @@ impl TestReporter for CompoundTestReporter {
     }
   }
 
+  fn report_shard(&mut self, shard: &TestShardSummary) {
+    for reporter in &mut self.test_reporters {
+      reporter.report_shard(shard);
+    }
+  }
+
+  fn report_snapshots(&mut self, summary: &TestSnapshotSummary) {
+    for reporter in &mut self.test_reporters {
+      reporter.report_snapshots(summary);
+    }
+  }
+
   fn report_plan(&mut self, plan: &TestPlan) {
     for reporter in &mut self.test_reporters {
       reporter.report_plan(plan);
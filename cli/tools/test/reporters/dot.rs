@@ -54,6 +54,7 @@ impl DotTestReporter {
       TestStepResult::Ok => fmt_ok(),
       TestStepResult::Ignored => fmt_ignored(),
       TestStepResult::Failed(_failure) => fmt_failed(),
+      TestStepResult::Flaky(_failures) => fmt_flaky(),
     };
     self.print_status(status);
   }
@@ -64,6 +65,7 @@ impl DotTestReporter {
       TestResult::Ignored => fmt_ignored(),
       TestResult::Failed(_failure) => fmt_failed(),
       TestResult::Cancelled => fmt_cancelled(),
+      TestResult::Flaky(_failures) => fmt_flaky(),
     };
 
     self.print_status(status);
@@ -82,6 +84,10 @@ fn fmt_failed() -> String {
   colors::red_bold("!").to_string()
 }
 
+fn fmt_flaky() -> String {
+  colors::yellow(".").to_string()
+}
+
 fn fmt_cancelled() -> String {
   colors::gray("!").to_string()
 }
@@ -90,6 +96,14 @@ fn fmt_cancelled() -> String {
 impl TestReporter for DotTestReporter {
   fn report_register(&mut self, _description: &TestDescription) {}
 
+  fn report_shard(&mut self, shard: &TestShardSummary) {
+    self.summary.shard = Some(shard.clone());
+  }
+
+  fn report_snapshots(&mut self, summary: &TestSnapshotSummary) {
+    self.summary.snapshots.push(summary.clone());
+  }
+
   fn report_plan(&mut self, plan: &TestPlan) {
     self.summary.total += plan.total;
     self.summary.filtered_out += plan.filtered_out;
@@ -126,6 +140,13 @@ impl TestReporter for DotTestReporter {
       TestResult::Cancelled => {
         self.summary.failed += 1;
       }
+      TestResult::Flaky(failures) => {
+        self.summary.passed += 1;
+        self
+          .summary
+          .flaky
+          .push((description.into(), failures.len()));
+      }
     }
 
     self.print_test_result(result);
@@ -179,6 +200,18 @@ impl TestReporter for DotTestReporter {
           failure.clone(),
         ))
       }
+      TestStepResult::Flaky(failures) => {
+        self.summary.passed_steps += 1;
+        self.summary.flaky.push((
+          TestFailureDescription {
+            id: desc.id,
+            name: common::format_test_step_ancestry(desc, tests, test_steps),
+            origin: desc.origin.clone(),
+            location: desc.location.clone(),
+          },
+          failures.len(),
+        ))
+      }
     }
 
     self.print_test_step_result(result);
@@ -0,0 +1,396 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use console_static_text::ansi::strip_ansi_codes;
+use deno_core::serde_json;
+use serde::Serialize;
+
+use super::common;
+use super::*;
+
+/// Writes every test event as a JSON object on its own line, so that tools
+/// can follow a run while it is in progress.
+pub struct JsonTestReporter {
+  summary: TestSummary,
+  failure_format_options: TestFailureFormatOptions,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonTestFailure {
+  message: String,
+  details: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+enum JsonTestEvent<'a> {
+  #[serde(rename_all = "camelCase")]
+  Shard {
+    index: usize,
+    count: usize,
+    total_files: usize,
+    skipped_files: usize,
+  },
+  #[serde(rename_all = "camelCase")]
+  Register {
+    id: usize,
+    name: &'a str,
+    origin: &'a str,
+    location: &'a TestLocation,
+    ignore: bool,
+    only: bool,
+  },
+  #[serde(rename_all = "camelCase")]
+  Plan {
+    origin: &'a str,
+    total: usize,
+    filtered_out: usize,
+    used_only: bool,
+  },
+  Wait {
+    id: usize,
+  },
+  Slow {
+    id: usize,
+    elapsed: u64,
+  },
+  Output {
+    output: String,
+  },
+  Result {
+    id: usize,
+    status: &'static str,
+    duration: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failure: Option<JsonTestFailure>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    retries: Vec<JsonTestFailure>,
+  },
+  #[serde(rename_all = "camelCase")]
+  UncaughtError {
+    origin: &'a str,
+    error: JsonTestFailure,
+  },
+  #[serde(rename_all = "camelCase")]
+  StepRegister {
+    id: usize,
+    name: &'a str,
+    origin: &'a str,
+    location: &'a TestLocation,
+    level: usize,
+    parent_id: usize,
+    root_id: usize,
+  },
+  StepWait {
+    id: usize,
+  },
+  StepResult {
+    id: usize,
+    status: &'static str,
+    duration: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failure: Option<JsonTestFailure>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    retries: Vec<JsonTestFailure>,
+  },
+  Snapshots {
+    origin: &'a str,
+    updated: usize,
+    obsolete: &'a [String],
+    removed: bool,
+  },
+  #[serde(rename_all = "camelCase")]
+  Summary {
+    passed: usize,
+    failed: usize,
+    ignored: usize,
+    passed_steps: usize,
+    failed_steps: usize,
+    ignored_steps: usize,
+    flaky: usize,
+    filtered_out: usize,
+    duration: u64,
+  },
+  Sigint {
+    pending: Vec<usize>,
+  },
+}
+
+impl JsonTestReporter {
+  pub fn new(failure_format_options: TestFailureFormatOptions) -> Self {
+    Self {
+      summary: TestSummary::new(),
+      failure_format_options,
+    }
+  }
+
+  #[allow(clippy::print_stdout)]
+  fn write_event(&self, event: &JsonTestEvent) {
+    println!("{}", serde_json::to_string(event).unwrap());
+  }
+
+  fn format_failure(&self, failure: &TestFailure) -> JsonTestFailure {
+    JsonTestFailure {
+      message: strip_ansi_codes(&failure.overview()).into_owned(),
+      details: strip_ansi_codes(&failure.format(&self.failure_format_options))
+        .into_owned(),
+    }
+  }
+}
+
+impl TestReporter for JsonTestReporter {
+  fn report_register(&mut self, description: &TestDescription) {
+    self.write_event(&JsonTestEvent::Register {
+      id: description.id,
+      name: &description.name,
+      origin: &description.origin,
+      location: &description.location,
+      ignore: description.ignore,
+      only: description.only,
+    });
+  }
+
+  fn report_shard(&mut self, shard: &TestShardSummary) {
+    self.write_event(&JsonTestEvent::Shard {
+      index: shard.index,
+      count: shard.count,
+      total_files: shard.total_files,
+      skipped_files: shard.skipped_files,
+    });
+  }
+
+  fn report_snapshots(&mut self, summary: &TestSnapshotSummary) {
+    self.write_event(&JsonTestEvent::Snapshots {
+      origin: &summary.origin,
+      updated: summary.updated,
+      obsolete: &summary.obsolete,
+      removed: summary.removed,
+    });
+  }
+
+  fn report_plan(&mut self, plan: &TestPlan) {
+    self.summary.total += plan.total;
+    self.summary.filtered_out += plan.filtered_out;
+    self.write_event(&JsonTestEvent::Plan {
+      origin: &plan.origin,
+      total: plan.total,
+      filtered_out: plan.filtered_out,
+      used_only: plan.used_only,
+    });
+  }
+
+  fn report_wait(&mut self, description: &TestDescription) {
+    self.write_event(&JsonTestEvent::Wait { id: description.id });
+  }
+
+  fn report_slow(&mut self, description: &TestDescription, elapsed: u64) {
+    self.write_event(&JsonTestEvent::Slow {
+      id: description.id,
+      elapsed,
+    });
+  }
+
+  fn report_output(&mut self, output: &[u8]) {
+    self.write_event(&JsonTestEvent::Output {
+      output: String::from_utf8_lossy(output).into_owned(),
+    });
+  }
+
+  fn report_result(
+    &mut self,
+    description: &TestDescription,
+    result: &TestResult,
+    elapsed: u64,
+  ) {
+    let (status, failure, retries) = match result {
+      TestResult::Ok => {
+        self.summary.passed += 1;
+        ("ok", None, vec![])
+      }
+      TestResult::Ignored => {
+        self.summary.ignored += 1;
+        ("ignored", None, vec![])
+      }
+      TestResult::Failed(failure) => {
+        self.summary.failed += 1;
+        ("failed", Some(self.format_failure(failure)), vec![])
+      }
+      TestResult::Cancelled => {
+        self.summary.failed += 1;
+        ("cancelled", None, vec![])
+      }
+      TestResult::Flaky(failures) => {
+        self.summary.passed += 1;
+        self
+          .summary
+          .flaky
+          .push((description.into(), failures.len()));
+        let retries = failures.iter().map(|f| self.format_failure(f));
+        ("flaky", None, retries.collect())
+      }
+    };
+    self.write_event(&JsonTestEvent::Result {
+      id: description.id,
+      status,
+      duration: elapsed,
+      failure,
+      retries,
+    });
+  }
+
+  fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
+    self.summary.failed += 1;
+    self.write_event(&JsonTestEvent::UncaughtError {
+      origin,
+      error: JsonTestFailure {
+        message: error.exception_message.clone(),
+        details: strip_ansi_codes(&format_test_error(
+          &error,
+          &self.failure_format_options,
+        ))
+        .into_owned(),
+      },
+    });
+  }
+
+  fn report_step_register(&mut self, description: &TestStepDescription) {
+    self.write_event(&JsonTestEvent::StepRegister {
+      id: description.id,
+      name: &description.name,
+      origin: &description.origin,
+      location: &description.location,
+      level: description.level,
+      parent_id: description.parent_id,
+      root_id: description.root_id,
+    });
+  }
+
+  fn report_step_wait(&mut self, description: &TestStepDescription) {
+    self.write_event(&JsonTestEvent::StepWait { id: description.id });
+  }
+
+  fn report_step_result(
+    &mut self,
+    desc: &TestStepDescription,
+    result: &TestStepResult,
+    elapsed: u64,
+    tests: &IndexMap<usize, TestDescription>,
+    test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    let (status, failure, retries) = match result {
+      TestStepResult::Ok => {
+        self.summary.passed_steps += 1;
+        ("ok", None, vec![])
+      }
+      TestStepResult::Ignored => {
+        self.summary.ignored_steps += 1;
+        ("ignored", None, vec![])
+      }
+      TestStepResult::Failed(failure) => {
+        self.summary.failed_steps += 1;
+        ("failed", Some(self.format_failure(failure)), vec![])
+      }
+      TestStepResult::Flaky(failures) => {
+        self.summary.passed_steps += 1;
+        self.summary.flaky.push((
+          TestFailureDescription {
+            id: desc.id,
+            name: common::format_test_step_ancestry(desc, tests, test_steps),
+            origin: desc.origin.clone(),
+            location: desc.location.clone(),
+          },
+          failures.len(),
+        ));
+        let retries = failures.iter().map(|f| self.format_failure(f));
+        ("flaky", None, retries.collect())
+      }
+    };
+    self.write_event(&JsonTestEvent::StepResult {
+      id: desc.id,
+      status,
+      duration: elapsed,
+      failure,
+      retries,
+    });
+  }
+
+  fn report_summary(
+    &mut self,
+    elapsed: &Duration,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    self.write_event(&JsonTestEvent::Summary {
+      passed: self.summary.passed,
+      failed: self.summary.failed,
+      ignored: self.summary.ignored,
+      passed_steps: self.summary.passed_steps,
+      failed_steps: self.summary.failed_steps,
+      ignored_steps: self.summary.ignored_steps,
+      flaky: self.summary.flaky.len(),
+      filtered_out: self.summary.filtered_out,
+      duration: elapsed.as_millis() as u64,
+    });
+  }
+
+  fn report_sigint(
+    &mut self,
+    tests_pending: &HashSet<usize>,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    let mut pending = tests_pending.iter().copied().collect::<Vec<_>>();
+    pending.sort();
+    self.write_event(&JsonTestEvent::Sigint { pending });
+  }
+
+  fn report_completed(&mut self) {}
+
+  fn flush_report(
+    &mut self,
+    _elapsed: &Duration,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) -> anyhow::Result<()> {
+    std::io::stdout().flush()?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn serializes_events_as_tagged_objects() {
+    let location = TestLocation {
+      file_name: "file:///a_test.ts".to_string(),
+      line_number: 1,
+      column_number: 6,
+    };
+    assert_eq!(
+      serde_json::to_string(&JsonTestEvent::StepRegister {
+        id: 2,
+        name: "step",
+        origin: "file:///a_test.ts",
+        location: &location,
+        level: 1,
+        parent_id: 1,
+        root_id: 1,
+      })
+      .unwrap(),
+      r#"{"type":"stepRegister","id":2,"name":"step","origin":"file:///a_test.ts","location":{"fileName":"file:///a_test.ts","lineNumber":1,"columnNumber":6},"level":1,"parentId":1,"rootId":1}"#
+    );
+    assert_eq!(
+      serde_json::to_string(&JsonTestEvent::Result {
+        id: 1,
+        status: "ok",
+        duration: 5,
+        failure: None,
+        retries: vec![],
+      })
+      .unwrap(),
+      r#"{"type":"result","id":1,"status":"ok","duration":5}"#
+    );
+  }
+}
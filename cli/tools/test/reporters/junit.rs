@@ -56,6 +56,9 @@ impl JunitTestReporter {
         description: None,
         reruns: vec![],
       },
+      TestResult::Flaky(failures) => {
+        Self::convert_flaky_status(failures, failure_format_options)
+      }
     }
   }
 
@@ -77,8 +80,30 @@ impl JunitTestReporter {
           reruns: vec![],
         }
       }
+      TestStepResult::Flaky(failures) => {
+        Self::convert_flaky_status(failures, failure_format_options)
+      }
     }
   }
+
+  /// Reported as a success with one `<flakyFailure>` per failed attempt.
+  fn convert_flaky_status(
+    failures: &[TestFailure],
+    failure_format_options: &TestFailureFormatOptions,
+  ) -> quick_junit::TestCaseStatus {
+    let flaky_runs = failures
+      .iter()
+      .map(|failure| {
+        let mut rerun =
+          quick_junit::TestRerun::new(quick_junit::NonSuccessKind::Failure);
+        rerun.set_message(failure.overview());
+        rerun
+          .set_description(failure.format(failure_format_options).into_owned());
+        rerun
+      })
+      .collect();
+    quick_junit::TestCaseStatus::Success { flaky_runs }
+  }
 }
 
 impl TestReporter for JunitTestReporter {
@@ -104,6 +129,10 @@ impl TestReporter for JunitTestReporter {
     self.test_name_tree.add_node(description.clone().into());
   }
 
+  fn report_shard(&mut self, _shard: &TestShardSummary) {}
+
+  fn report_snapshots(&mut self, _summary: &TestSnapshotSummary) {}
+
   fn report_plan(&mut self, _plan: &TestPlan) {}
 
   fn report_slow(&mut self, _description: &TestDescription, _elapsed: u64) {}
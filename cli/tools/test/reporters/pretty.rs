@@ -123,6 +123,7 @@ impl PrettyTestReporter {
       TestStepResult::Ok => colors::green("ok").to_string(),
       TestStepResult::Ignored => colors::yellow("ignored").to_string(),
       TestStepResult::Failed(failure) => failure.format_label(),
+      TestStepResult::Flaky(_) => colors::yellow("flaky").to_string(),
     };
     write!(&mut self.writer, " {}", status).unwrap();
     if let TestStepResult::Failed(failure) = result {
@@ -130,6 +131,10 @@ impl PrettyTestReporter {
         write!(&mut self.writer, " ({})", inline_summary).unwrap()
       }
     }
+    if let TestStepResult::Flaky(failures) = result {
+      write!(&mut self.writer, " ({})", format_flaky_summary(failures))
+        .unwrap();
+    }
     if !matches!(result, TestStepResult::Failed(TestFailure::Incomplete)) {
       write!(
         &mut self.writer,
@@ -166,8 +171,23 @@ impl PrettyTestReporter {
   }
 }
 
+fn format_flaky_summary(failures: &[TestFailure]) -> String {
+  if failures.len() == 1 {
+    "passed after 1 failed attempt".to_string()
+  } else {
+    format!("passed after {} failed attempts", failures.len())
+  }
+}
+
 impl TestReporter for PrettyTestReporter {
   fn report_register(&mut self, _description: &TestDescription) {}
+  fn report_shard(&mut self, shard: &TestShardSummary) {
+    self.summary.shard = Some(shard.clone());
+  }
+
+  fn report_snapshots(&mut self, summary: &TestSnapshotSummary) {
+    self.summary.snapshots.push(summary.clone());
+  }
   fn report_plan(&mut self, plan: &TestPlan) {
     self.write_output_end();
     self.summary.total += plan.total;
@@ -266,6 +286,13 @@ impl TestReporter for PrettyTestReporter {
       TestResult::Cancelled => {
         self.summary.failed += 1;
       }
+      TestResult::Flaky(failures) => {
+        self.summary.passed += 1;
+        self
+          .summary
+          .flaky
+          .push((description.into(), failures.len()));
+      }
     }
 
     if self.parallel {
@@ -282,6 +309,7 @@ impl TestReporter for PrettyTestReporter {
       TestResult::Ignored => colors::yellow("ignored").to_string(),
       TestResult::Failed(failure) => failure.format_label(),
       TestResult::Cancelled => colors::gray("cancelled").to_string(),
+      TestResult::Flaky(_) => colors::yellow("flaky").to_string(),
     };
     write!(&mut self.writer, " {}", status).unwrap();
     if let TestResult::Failed(failure) = result {
@@ -289,6 +317,10 @@ impl TestReporter for PrettyTestReporter {
         write!(&mut self.writer, " ({})", inline_summary).unwrap();
       }
     }
+    if let TestResult::Flaky(failures) = result {
+      write!(&mut self.writer, " ({})", format_flaky_summary(failures))
+        .unwrap();
+    }
     writeln!(
       &mut self.writer,
       " {}",
@@ -355,6 +387,18 @@ impl TestReporter for PrettyTestReporter {
           failure.clone(),
         ))
       }
+      TestStepResult::Flaky(failures) => {
+        self.summary.passed_steps += 1;
+        self.summary.flaky.push((
+          TestFailureDescription {
+            id: desc.id,
+            name: common::format_test_step_ancestry(desc, tests, test_steps),
+            origin: desc.origin.clone(),
+            location: desc.location.clone(),
+          },
+          failures.len(),
+        ))
+      }
     }
 
     if self.parallel {
@@ -21,6 +21,8 @@ pub struct TapTestReporter {
   step_n: usize,
   step_results: HashMap<usize, Vec<(TestStepDescription, TestStepResult)>>,
   failure_format_options: TestFailureFormatOptions,
+  shard: Option<TestShardSummary>,
+  snapshots: Vec<TestSnapshotSummary>,
 }
 
 #[allow(clippy::print_stdout)]
@@ -39,6 +41,8 @@ impl TapTestReporter {
       step_n: 0,
       step_results: HashMap::new(),
       failure_format_options,
+      shard: None,
+      snapshots: Vec::new(),
     }
   }
 
@@ -104,6 +108,7 @@ impl TapTestReporter {
       TestStepResult::Ok => ("ok", ""),
       TestStepResult::Ignored => ("ok", " # SKIP"),
       TestStepResult::Failed(_failure) => ("not ok", ""),
+      TestStepResult::Flaky(_failures) => ("ok", " # flaky"),
     };
     self.step_n += 1;
     Self::print_line(4, status, self.step_n, &desc.name, directive);
@@ -125,6 +130,14 @@ impl TapTestReporter {
 impl TestReporter for TapTestReporter {
   fn report_register(&mut self, _description: &TestDescription) {}
 
+  fn report_shard(&mut self, shard: &TestShardSummary) {
+    self.shard = Some(shard.clone());
+  }
+
+  fn report_snapshots(&mut self, summary: &TestSnapshotSummary) {
+    self.snapshots.push(summary.clone());
+  }
+
   fn report_plan(&mut self, plan: &TestPlan) {
     if !self.header {
       println!("{}", VERSION_HEADER);
@@ -173,6 +186,7 @@ impl TestReporter for TapTestReporter {
       TestResult::Ignored => ("ok", " # SKIP"),
       TestResult::Failed(_failure) => ("not ok", ""),
       TestResult::Cancelled => ("not ok", ""),
+      TestResult::Flaky(_failures) => ("ok", " # flaky"),
     };
     self.n += 1;
     Self::print_line(0, status, self.n, &description.name, directive);
@@ -228,6 +242,26 @@ impl TestReporter for TapTestReporter {
     _test_steps: &IndexMap<usize, TestStepDescription>,
   ) {
     println!("1..{}", self.planned);
+    if let Some(shard) = &self.shard {
+      println!(
+        "# shard {}/{}, {} of {} files skipped",
+        shard.index, shard.count, shard.skipped_files, shard.total_files
+      );
+    }
+    for snapshots in &self.snapshots {
+      let origin = to_relative_path_or_remote_url(&self.cwd, &snapshots.origin);
+      if snapshots.updated > 0 {
+        println!("# {} snapshots updated in {}", snapshots.updated, origin);
+      }
+      for name in &snapshots.obsolete {
+        let status = if snapshots.removed {
+          "removed"
+        } else {
+          "obsolete"
+        };
+        println!("# {} snapshot in {}: {}", status, origin, name);
+      }
+    }
   }
 
   fn report_sigint(
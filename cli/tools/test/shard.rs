@@ -0,0 +1,206 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::url::Url;
+use deno_core::ModuleSpecifier;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::fmt::to_relative_path_or_remote_url;
+use crate::args::TestShardFlag;
+
+/// Describes the shard that was selected for a test run.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestShardSummary {
+  pub index: usize,
+  pub count: usize,
+  pub total_files: usize,
+  pub skipped_files: usize,
+}
+
+/// Durations in milliseconds of test modules from previous runs, keyed by
+/// the module path relative to the current working directory.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TestShardTimings(BTreeMap<String, u64>);
+
+impl TestShardTimings {
+  /// Reads the timings file at `path`. A file that doesn't exist yet is
+  /// treated as empty so that the first run can produce it.
+  pub fn read(path: &Path) -> Result<Self, AnyError> {
+    let text = match std::fs::read_to_string(path) {
+      Ok(text) => text,
+      Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+        return Ok(Self::default());
+      }
+      Err(err) => {
+        return Err(err).with_context(|| {
+          format!("Failed reading shard timings '{}'", path.display())
+        });
+      }
+    };
+    serde_json::from_str(&text).with_context(|| {
+      format!("Failed parsing shard timings '{}'", path.display())
+    })
+  }
+
+  pub fn write(&self, path: &Path) -> Result<(), AnyError> {
+    let text = serde_json::to_string_pretty(self)?;
+    std::fs::write(path, text).with_context(|| {
+      format!("Failed writing shard timings '{}'", path.display())
+    })
+  }
+
+  pub fn insert(&mut self, cwd: &Url, specifier: &Url, duration: Duration) {
+    self.0.insert(
+      to_relative_path_or_remote_url(cwd, specifier.as_str()),
+      duration.as_millis() as u64,
+    );
+  }
+
+  fn get(&self, cwd: &Url, specifier: &Url) -> Option<u64> {
+    self
+      .0
+      .get(&to_relative_path_or_remote_url(cwd, specifier.as_str()))
+      .copied()
+  }
+
+  fn average(&self) -> Option<u64> {
+    if self.0.is_empty() {
+      None
+    } else {
+      Some(self.0.values().sum::<u64>() / self.0.len() as u64)
+    }
+  }
+}
+
+/// Partitions the specifiers into `shard.count` buckets and returns the ones
+/// in the bucket selected by `shard.index`, preserving their input order.
+///
+/// Specifiers are assigned greedily, longest first, to the bucket with the
+/// smallest accumulated duration. Modules without recorded timings are
+/// weighted with the average of the known timings, so without any timing
+/// data this degrades to an even split by file count. The assignment only
+/// depends on the set of specifiers and the timings, so every CI machine
+/// computes the same partitions.
+pub fn select_shard(
+  specifiers: Vec<ModuleSpecifier>,
+  shard: TestShardFlag,
+  timings: &TestShardTimings,
+  cwd: &Url,
+) -> (Vec<ModuleSpecifier>, TestShardSummary) {
+  let count = shard.count.get();
+  let selected_bucket = shard.index.get() - 1;
+  let default_weight = timings.average().unwrap_or(1).max(1);
+
+  let mut weighted = specifiers
+    .iter()
+    .map(|specifier| {
+      let weight = timings.get(cwd, specifier).unwrap_or(default_weight).max(1);
+      (specifier, weight)
+    })
+    .collect::<Vec<_>>();
+  weighted.sort_by(|(a, a_weight), (b, b_weight)| {
+    b_weight.cmp(a_weight).then_with(|| a.cmp(b))
+  });
+
+  let mut loads = vec![0u64; count];
+  let mut in_selected_bucket = HashSet::new();
+  for (specifier, weight) in weighted {
+    let bucket = (0..count).min_by_key(|i| (loads[*i], *i)).unwrap();
+    loads[bucket] += weight;
+    if bucket == selected_bucket {
+      in_selected_bucket.insert(specifier.clone());
+    }
+  }
+
+  let total_files = specifiers.len();
+  let selected = specifiers
+    .into_iter()
+    .filter(|specifier| in_selected_bucket.contains(specifier))
+    .collect::<Vec<_>>();
+  let summary = TestShardSummary {
+    index: shard.index.get(),
+    count,
+    total_files,
+    skipped_files: total_files - selected.len(),
+  };
+  (selected, summary)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::num::NonZeroUsize;
+
+  use super::*;
+
+  fn shard(index: usize, count: usize) -> TestShardFlag {
+    TestShardFlag {
+      index: NonZeroUsize::new(index).unwrap(),
+      count: NonZeroUsize::new(count).unwrap(),
+    }
+  }
+
+  fn specifiers(names: &[&str]) -> Vec<ModuleSpecifier> {
+    names
+      .iter()
+      .map(|n| Url::parse(&format!("file:///project/{n}")).unwrap())
+      .collect()
+  }
+
+  #[test]
+  fn select_shard_by_file_count() {
+    let cwd = Url::parse("file:///project/").unwrap();
+    let all = specifiers(&["a_test.ts", "b_test.ts", "c_test.ts", "d_test.ts"]);
+    let timings = TestShardTimings::default();
+    let mut seen = Vec::new();
+    for index in 1..=3 {
+      let (selected, summary) =
+        select_shard(all.clone(), shard(index, 3), &timings, &cwd);
+      assert_eq!(summary.total_files, 4);
+      assert_eq!(summary.skipped_files, 4 - selected.len());
+      assert!(!selected.is_empty());
+      seen.extend(selected);
+    }
+    seen.sort();
+    assert_eq!(seen, all);
+  }
+
+  #[test]
+  fn select_shard_is_independent_of_input_order() {
+    let cwd = Url::parse("file:///project/").unwrap();
+    let all = specifiers(&["a_test.ts", "b_test.ts", "c_test.ts"]);
+    let mut reversed = all.clone();
+    reversed.reverse();
+    let timings = TestShardTimings::default();
+    let (mut a, _) = select_shard(all, shard(2, 2), &timings, &cwd);
+    let (mut b, _) = select_shard(reversed, shard(2, 2), &timings, &cwd);
+    a.sort();
+    b.sort();
+    assert_eq!(a, b);
+  }
+
+  #[test]
+  fn select_shard_balances_by_timings() {
+    let cwd = Url::parse("file:///project/").unwrap();
+    let all = specifiers(&["a_test.ts", "b_test.ts", "c_test.ts"]);
+    let mut timings = TestShardTimings::default();
+    timings.insert(&cwd, &all[0], Duration::from_millis(100));
+    timings.insert(&cwd, &all[1], Duration::from_millis(50));
+    timings.insert(&cwd, &all[2], Duration::from_millis(40));
+    let (selected, summary) =
+      select_shard(all.clone(), shard(1, 2), &timings, &cwd);
+    assert_eq!(selected, vec![all[0].clone()]);
+    assert_eq!(summary.skipped_files, 2);
+    let (selected, _) = select_shard(all.clone(), shard(2, 2), &timings, &cwd);
+    assert_eq!(selected, vec![all[1].clone(), all[2].clone()]);
+  }
+}
@@ -0,0 +1,323 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use deno_core::ModuleSpecifier;
+use deno_error::JsErrorBox;
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde::Serialize;
+
+const SNAPSHOT_DIR_NAME: &str = "__snapshots__";
+const SNAPSHOT_FILE_HEADER: &str = "export const snapshot = {};\n";
+
+/// A snapshot assertion that didn't match the stored snapshot, or that had
+/// no stored snapshot at all.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestSnapshotMismatch {
+  pub name: String,
+  pub expected: Option<String>,
+  pub actual: String,
+}
+
+/// What happened to the snapshot file of a test module.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestSnapshotSummary {
+  pub origin: String,
+  pub updated: usize,
+  /// Snapshots in the file that no assertion used.
+  pub obsolete: Vec<String>,
+  /// Whether the obsolete snapshots were removed from the file.
+  pub removed: bool,
+}
+
+/// Snapshot state of the test module running in a worker, kept in the op
+/// state. The snapshot file is only read once the first assertion runs.
+pub struct TestSnapshots {
+  path: Option<PathBuf>,
+  update: bool,
+  snapshots: Option<IndexMap<String, String>>,
+  used: HashSet<String>,
+  updated: usize,
+  ran_all_tests: bool,
+}
+
+impl TestSnapshots {
+  pub fn new(specifier: &ModuleSpecifier, update: bool) -> Self {
+    Self {
+      path: snapshot_path(specifier),
+      update,
+      snapshots: None,
+      used: HashSet::new(),
+      updated: 0,
+      ran_all_tests: true,
+    }
+  }
+
+  /// Obsolete snapshots can only be told apart from the snapshots of tests
+  /// that didn't run when every test of the module ran and passed.
+  pub fn mark_incomplete(&mut self) {
+    self.ran_all_tests = false;
+  }
+
+  pub fn assert(
+    &mut self,
+    name: String,
+    actual: String,
+  ) -> Result<Option<TestSnapshotMismatch>, JsErrorBox> {
+    if self.path.is_none() {
+      return Err(JsErrorBox::generic(
+        "Snapshots are only supported for local test modules.",
+      ));
+    }
+    let update = self.update;
+    let snapshots = self.load()?;
+    let expected = snapshots.get(&name);
+    if expected == Some(&actual) {
+      self.used.insert(name);
+      return Ok(None);
+    }
+    if update {
+      snapshots.insert(name.clone(), actual);
+      self.updated += 1;
+      self.used.insert(name);
+      return Ok(None);
+    }
+    let expected = expected.cloned();
+    self.used.insert(name.clone());
+    Ok(Some(TestSnapshotMismatch {
+      name,
+      expected,
+      actual,
+    }))
+  }
+
+  /// Writes the snapshot file when running with `--update-snapshots` and
+  /// reports updated and obsolete snapshots.
+  pub fn finish(
+    mut self,
+    origin: &ModuleSpecifier,
+  ) -> Result<Option<TestSnapshotSummary>, JsErrorBox> {
+    let Some(path) = self.path.clone() else {
+      return Ok(None);
+    };
+    let ran_all_tests = self.ran_all_tests;
+    let update = self.update;
+    let updated = self.updated;
+    let used = std::mem::take(&mut self.used);
+    let snapshots = self.load()?;
+    let obsolete = if ran_all_tests {
+      snapshots
+        .keys()
+        .filter(|name| !used.contains(*name))
+        .cloned()
+        .collect::<Vec<_>>()
+    } else {
+      Vec::new()
+    };
+    let removed = update && !obsolete.is_empty();
+    if removed {
+      snapshots.retain(|name, _| used.contains(name));
+    }
+    if updated == 0 && !removed {
+      return Ok((!obsolete.is_empty()).then(|| TestSnapshotSummary {
+        origin: origin.to_string(),
+        updated: 0,
+        obsolete,
+        removed,
+      }));
+    }
+
+    let result = if snapshots.is_empty() {
+      std::fs::remove_file(&path)
+    } else {
+      std::fs::create_dir_all(path.parent().unwrap())
+        .and_then(|()| std::fs::write(&path, print_snapshot_file(snapshots)))
+    };
+    result.map_err(|err| {
+      JsErrorBox::generic(format!(
+        "Failed writing snapshot file '{}': {err}",
+        path.display()
+      ))
+    })?;
+    Ok(Some(TestSnapshotSummary {
+      origin: origin.to_string(),
+      updated,
+      obsolete,
+      removed,
+    }))
+  }
+
+  fn load(&mut self) -> Result<&mut IndexMap<String, String>, JsErrorBox> {
+    if self.snapshots.is_none() {
+      let path = self.path.as_ref().unwrap();
+      let snapshots = match std::fs::read_to_string(path) {
+        Ok(text) => parse_snapshot_file(&text).map_err(|err| {
+          JsErrorBox::generic(format!(
+            "Failed parsing snapshot file '{}': {err}",
+            path.display()
+          ))
+        })?,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+          IndexMap::new()
+        }
+        Err(err) => {
+          return Err(JsErrorBox::generic(format!(
+            "Failed reading snapshot file '{}': {err}",
+            path.display()
+          )));
+        }
+      };
+      self.snapshots = Some(snapshots);
+    }
+    Ok(self.snapshots.as_mut().unwrap())
+  }
+}
+
+/// `dir/__snapshots__/mod_test.ts.snap` for `dir/mod_test.ts`.
+fn snapshot_path(specifier: &ModuleSpecifier) -> Option<PathBuf> {
+  let path = deno_path_util::url_to_file_path(specifier).ok()?;
+  let file_name = path.file_name()?.to_string_lossy().into_owned();
+  Some(
+    path
+      .parent()?
+      .join(SNAPSHOT_DIR_NAME)
+      .join(format!("{file_name}.snap")),
+  )
+}
+
+/// Snapshot files are JavaScript modules that export the snapshots as
+/// template literals, one assignment per snapshot.
+fn print_snapshot_file(snapshots: &IndexMap<String, String>) -> String {
+  let mut text = SNAPSHOT_FILE_HEADER.to_string();
+  for (name, value) in snapshots {
+    let value = if value.contains('\n') {
+      format!("\n{value}\n")
+    } else {
+      value.clone()
+    };
+    text.push_str(&format!(
+      "\nsnapshot[`{}`] = `{}`;\n",
+      escape_template_literal(name),
+      escape_template_literal(&value)
+    ));
+  }
+  text
+}
+
+fn parse_snapshot_file(text: &str) -> Result<IndexMap<String, String>, String> {
+  let text = text.replace("\r\n", "\n");
+  let mut rest = text
+    .trim_start()
+    .strip_prefix(SNAPSHOT_FILE_HEADER.trim_end())
+    .ok_or_else(|| "missing snapshot export".to_string())?;
+  let mut snapshots = IndexMap::new();
+  loop {
+    rest = rest.trim_start();
+    if rest.is_empty() {
+      return Ok(snapshots);
+    }
+    rest = rest
+      .strip_prefix("snapshot[`")
+      .ok_or_else(|| "expected a snapshot assignment".to_string())?;
+    let (name, after_name) = read_template_literal(rest)?;
+    rest = after_name
+      .strip_prefix("] = `")
+      .ok_or_else(|| format!("expected a value for snapshot `{name}`"))?;
+    let (value, after_value) = read_template_literal(rest)?;
+    rest = after_value.strip_prefix(';').unwrap_or(after_value);
+    let value = if value.contains('\n') {
+      let value = value.strip_prefix('\n').unwrap_or(&value);
+      value.strip_suffix('\n').unwrap_or(value).to_string()
+    } else {
+      value
+    };
+    snapshots.insert(name, value);
+  }
+}
+
+/// Reads up to the closing backtick of a template literal whose opening
+/// backtick was already consumed.
+fn read_template_literal(text: &str) -> Result<(String, &str), String> {
+  let mut value = String::new();
+  let mut chars = text.char_indices();
+  while let Some((i, c)) = chars.next() {
+    match c {
+      '\\' => match chars.next() {
+        Some((_, c)) => value.push(c),
+        None => break,
+      },
+      '`' => return Ok((value, &text[i + 1..])),
+      c => value.push(c),
+    }
+  }
+  Err("unterminated template literal".to_string())
+}
+
+fn escape_template_literal(text: &str) -> String {
+  text
+    .replace('\\', "\\\\")
+    .replace('`', "\\`")
+    .replace("${", "\\${")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn snapshot_file_roundtrip() {
+    let mut snapshots = IndexMap::new();
+    snapshots.insert("single 1".to_string(), "\"a`b\"".to_string());
+    snapshots.insert(
+      "multi ... step 1".to_string(),
+      "{\n  a: \"${x}\\n\",\n}".to_string(),
+    );
+    let text = print_snapshot_file(&snapshots);
+    assert_eq!(
+      text,
+      r#"export const snapshot = {};
+
+snapshot[`single 1`] = `"a\`b"`;
+
+snapshot[`multi ... step 1`] = `
+{
+  a: "\${x}\\n",
+}
+`;
+"#
+    );
+    assert_eq!(parse_snapshot_file(&text).unwrap(), snapshots);
+    assert_eq!(
+      parse_snapshot_file(&text.replace('\n', "\r\n")).unwrap(),
+      snapshots
+    );
+  }
+
+  #[test]
+  fn snapshot_file_invalid() {
+    assert!(parse_snapshot_file("snapshot[`a`] = `b`;").is_err());
+    assert!(parse_snapshot_file(
+      "export const snapshot = {};\nsnapshot[`a`] = `b"
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn snapshot_path_next_to_module() {
+    let specifier = if cfg!(windows) {
+      ModuleSpecifier::parse("file:///C:/project/mod_test.ts").unwrap()
+    } else {
+      ModuleSpecifier::parse("file:///project/mod_test.ts").unwrap()
+    };
+    let path = snapshot_path(&specifier).unwrap();
+    assert!(path.ends_with("project/__snapshots__/mod_test.ts.snap"));
+    assert_eq!(
+      snapshot_path(&ModuleSpecifier::parse("https://deno.land/x.ts").unwrap()),
+      None
+    );
+  }
+}
@@ -0,0 +1,238 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::ffi::c_void;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::time::Instant;
+
+use deno_core::parking_lot::Condvar;
+use deno_core::parking_lot::Mutex;
+use deno_core::v8;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::Notify;
+
+/// Frames beyond this are left out of the stack of a timed out test.
+const STACK_FRAME_LIMIT: usize = 20;
+
+/// A test or test step that didn't finish within its timeout.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestTimedOut {
+  /// The id and full name of the step whose timeout expired, when it wasn't
+  /// the timeout of the test itself.
+  pub step_id: Option<usize>,
+  pub step: Option<String>,
+  pub timeout: u64,
+  /// The JS stack at the moment the test was terminated. `None` when no JS
+  /// was running, for example when the test awaited a promise that never
+  /// settles.
+  pub stack: Option<String>,
+}
+
+/// Enforces the timeouts of the tests running in a worker, kept in the op
+/// state. The deadlines are watched from a separate thread, so that a test
+/// stuck in a synchronous loop can still be terminated.
+pub struct TestTimeouts {
+  shared: Arc<Shared>,
+  thread: Option<JoinHandle<()>>,
+}
+
+struct Shared {
+  isolate: v8::IsolateHandle,
+  state: Mutex<State>,
+  condvar: Condvar,
+  expired: Notify,
+}
+
+#[derive(Default)]
+struct State {
+  next_id: usize,
+  deadlines: Vec<Deadline>,
+  /// Bumped after every test, so that a termination requested for a test
+  /// that already finished doesn't hit the next one.
+  generation: u64,
+  timed_out: Option<TestTimedOut>,
+  terminated: bool,
+  stopped: bool,
+}
+
+struct Deadline {
+  id: usize,
+  at: Instant,
+  timeout: Duration,
+  step: Option<(usize, String)>,
+}
+
+impl TestTimeouts {
+  pub fn new(isolate: v8::IsolateHandle) -> Self {
+    Self {
+      shared: Arc::new(Shared {
+        isolate,
+        state: Mutex::new(State::default()),
+        condvar: Condvar::new(),
+        expired: Notify::new(),
+      }),
+      thread: None,
+    }
+  }
+
+  /// Starts the timeout of the running test, or of one of its steps when
+  /// `step` (id and full name) is set. Returns an id to pass to `end()`.
+  pub fn start(
+    &mut self,
+    timeout: Duration,
+    step: Option<(usize, String)>,
+  ) -> usize {
+    if self.thread.is_none() {
+      let shared = self.shared.clone();
+      self.thread = Some(std::thread::spawn(move || watch(shared)));
+    }
+    let mut state = self.shared.state.lock();
+    let id = state.next_id;
+    state.next_id += 1;
+    state.deadlines.push(Deadline {
+      id,
+      at: Instant::now() + timeout,
+      timeout,
+      step,
+    });
+    self.shared.condvar.notify_one();
+    id
+  }
+
+  pub fn end(&self, id: usize) {
+    let mut state = self.shared.state.lock();
+    state.deadlines.retain(|deadline| deadline.id != id);
+  }
+
+  /// Resolves once a timeout expired. Used to stop waiting for a test that
+  /// hangs without running any JS.
+  pub fn expired(&self) -> impl std::future::Future<Output = ()> + 'static {
+    let shared = self.shared.clone();
+    async move {
+      loop {
+        shared.expired.notified().await;
+        if shared.state.lock().timed_out.is_some() {
+          return;
+        }
+      }
+    }
+  }
+
+  /// Clears the timeouts of the test that just finished and lifts the
+  /// termination of the isolate if it timed out.
+  pub fn finish(&self, isolate: &mut v8::Isolate) -> Option<TestTimedOut> {
+    let mut state = self.shared.state.lock();
+    state.generation += 1;
+    state.deadlines.clear();
+    if std::mem::take(&mut state.terminated) {
+      isolate.cancel_terminate_execution();
+    }
+    state.timed_out.take()
+  }
+}
+
+impl Drop for TestTimeouts {
+  fn drop(&mut self) {
+    self.shared.state.lock().stopped = true;
+    self.shared.condvar.notify_one();
+    if let Some(thread) = self.thread.take() {
+      _ = thread.join();
+    }
+  }
+}
+
+fn watch(shared: Arc<Shared>) {
+  let mut state = shared.state.lock();
+  loop {
+    if state.stopped {
+      return;
+    }
+    let Some(deadline) = state.deadlines.iter().min_by_key(|d| d.at) else {
+      shared.condvar.wait(&mut state);
+      continue;
+    };
+    if Instant::now() < deadline.at {
+      let at = deadline.at;
+      shared.condvar.wait_until(&mut state, at);
+      continue;
+    }
+    let timed_out = TestTimedOut {
+      step_id: deadline.step.as_ref().map(|(id, _)| *id),
+      step: deadline.step.as_ref().map(|(_, name)| name.clone()),
+      timeout: deadline.timeout.as_millis() as u64,
+      stack: None,
+    };
+    state.timed_out = Some(timed_out);
+    state.deadlines.clear();
+    let data = Box::into_raw(Box::new((shared.clone(), state.generation)));
+    // If JS is running, the interrupt captures its stack and terminates it.
+    // Otherwise the test is stopped by `expired()`.
+    if !shared
+      .isolate
+      .request_interrupt(terminate_with_stack, data as *mut c_void)
+    {
+      // SAFETY: the interrupt wasn't queued, so `data` is still owned here.
+      drop(unsafe { Box::from_raw(data) });
+      // The isolate is gone, so there is nothing left to time out.
+      return;
+    }
+    shared.expired.notify_one();
+  }
+}
+
+extern "C" fn terminate_with_stack(
+  isolate: &mut v8::Isolate,
+  data: *mut c_void,
+) {
+  // SAFETY: `data` was created with `Box::into_raw()` in `watch()` and
+  // interrupts run exactly once.
+  let (shared, generation) =
+    *unsafe { Box::from_raw(data as *mut (Arc<Shared>, u64)) };
+  let mut state = shared.state.lock();
+  if state.generation != generation || state.terminated {
+    return;
+  }
+  let stack = {
+    // SAFETY: interrupts run on the isolate's thread while it executes JS.
+    let scope = &mut unsafe { v8::CallbackScope::new(&mut *isolate) };
+    let scope = &mut v8::HandleScope::new(scope);
+    let context = scope.get_current_context();
+    let scope = &mut v8::ContextScope::new(scope, context);
+    format_current_stack(scope)
+  };
+  if let Some(timed_out) = &mut state.timed_out {
+    timed_out.stack = stack;
+  }
+  state.terminated = true;
+  isolate.terminate_execution();
+}
+
+fn format_current_stack(scope: &mut v8::HandleScope) -> Option<String> {
+  let stack = v8::StackTrace::current_stack_trace(scope, STACK_FRAME_LIMIT)?;
+  let mut lines = Vec::new();
+  for i in 0..stack.get_frame_count() {
+    let Some(frame) = stack.get_frame(scope, i) else {
+      continue;
+    };
+    let location = format!(
+      "{}:{}:{}",
+      frame
+        .get_script_name_or_source_url(scope)
+        .map(|name| name.to_rust_string_lossy(scope))
+        .unwrap_or_else(|| "<anonymous>".to_string()),
+      frame.get_line_number(),
+      frame.get_column()
+    );
+    let line = match frame.get_function_name(scope) {
+      Some(name) if name.length() > 0 => {
+        format!("    at {} ({location})", name.to_rust_string_lossy(scope))
+      }
+      _ => format!("    at {location}"),
+    };
+    lines.push(line);
+  }
+  (!lines.is_empty()).then(|| lines.join("\n"))
+}
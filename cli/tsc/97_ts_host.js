@@ -471,7 +471,7 @@ const hostImpl = {
     specifier = normalizedToOriginalMap.get(specifier) ?? specifier;
 
     let sourceFile = SOURCE_FILE_CACHE.get(specifier);
-    if (sourceFile) {
+    if (sourceFile && specifier.startsWith(ASSETS_URL_PREFIX)) {
       return sourceFile;
     }
 
@@ -485,6 +485,11 @@ const hostImpl = {
       data != null,
       `"data" is unexpectedly null for "${specifier}".`,
     );
+    // an isolate that is kept between checks only parses the modules that
+    // changed since the previous check
+    if (sourceFile && sourceFile.version === version) {
+      return sourceFile;
+    }
 
     sourceFile = ts.createSourceFile(
       specifier,
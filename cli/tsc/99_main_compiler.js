@@ -18,6 +18,7 @@ import {
   filterMapDiagnostic,
   fromTypeScriptDiagnostics,
   host,
+  IS_NODE_SOURCE_FILE_CACHE,
   setLogDebug,
 } from "./97_ts_host.js";
 import { serverMainLoop } from "./98_lsp.js";
@@ -135,6 +136,9 @@ function exec({ config, debug: debugFlag, rootNames, localOnly }) {
   debug(">>> exec start", { rootNames });
   debug(config);
 
+  // the isolate might be reused by `deno check --watch`
+  IS_NODE_SOURCE_FILE_CACHE.clear();
+
   rootNames.forEach(checkNormalizedPath);
 
   const { options, errors: configFileParsingDiagnostics } = ts
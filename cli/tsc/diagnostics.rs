@@ -12,6 +12,8 @@ use deno_core::sourcemap::SourceMap;
 use deno_graph::ModuleGraph;
 use deno_terminal::colors;
 
+use crate::util::sarif;
+
 const MAX_SOURCE_LINE_LENGTH: usize = 150;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -192,6 +194,105 @@ impl Diagnostic {
     !matches!(self.code, TS6133 | TS4114)
   }
 
+  /// Whether both diagnostics report the same problem, ignoring where it
+  /// is in the file, so that moved code doesn't count as a change.
+  pub fn is_same_problem(&self, other: &Diagnostic) -> bool {
+    self.code == other.code
+      && self.file_name == other.file_name
+      && self.message_text == other.message_text
+      && self.message_chain == other.message_chain
+      && self.source_line == other.source_line
+  }
+
+  /// Formats the diagnostic on a single line, without its source line.
+  pub fn to_compact_string(&self) -> String {
+    let message = self.message();
+    let mut text = format!(
+      "TS{}: {}",
+      self.code,
+      message.lines().next().unwrap_or_default().trim()
+    );
+    if let (Some(file_name), Some(start)) = (
+      self.file_name.as_ref(),
+      self.original_source_start.as_ref().or(self.start.as_ref()),
+    ) {
+      text.push_str(&format!(
+        " at {}:{}:{}",
+        file_name,
+        start.line + 1,
+        start.character + 1
+      ));
+    }
+    text
+  }
+
+  fn message(&self) -> String {
+    match &self.message_chain {
+      Some(message_chain) => message_chain.format_message(0),
+      None => self.message_text.clone().unwrap_or_default(),
+    }
+  }
+
+  /// The location of the diagnostic in the original source. The end is left
+  /// out when the start was mapped from a fast checked module, because only
+  /// the start is mapped.
+  fn sarif_location(&self, run: &sarif::Run) -> Option<sarif::Location> {
+    let file_name = self.file_name.as_ref()?;
+    let region = match (&self.original_source_start, &self.start) {
+      (Some(start), _) => Some(sarif::Region::from_indexes(
+        (start.line as usize, start.character as usize),
+        None,
+      )),
+      (None, Some(start)) => Some(sarif::Region::from_indexes(
+        (start.line as usize, start.character as usize),
+        self
+          .end
+          .as_ref()
+          .map(|end| (end.line as usize, end.character as usize)),
+      )),
+      (None, None) => None,
+    };
+    Some(sarif::Location {
+      physical_location: sarif::PhysicalLocation {
+        artifact_location: run.artifact_location(file_name),
+        region,
+      },
+      message: None,
+    })
+  }
+
+  fn to_sarif_result(&self, run: &mut sarif::Run) -> sarif::SarifResult {
+    let rule_id = format!("TS{}", self.code);
+    let rule_index =
+      run.rule_index(&rule_id, sarif::ReportingDescriptor::default);
+    let level = match self.category {
+      DiagnosticCategory::Error => sarif::Level::Error,
+      DiagnosticCategory::Warning => sarif::Level::Warning,
+      DiagnosticCategory::Suggestion | DiagnosticCategory::Message => {
+        sarif::Level::Note
+      }
+    };
+    let related_locations = self
+      .related_information
+      .iter()
+      .flatten()
+      .filter_map(|related| {
+        let mut location = related.sarif_location(run)?;
+        location.message = Some(sarif::Message::new(related.message()));
+        Some(location)
+      })
+      .collect();
+    sarif::SarifResult {
+      rule_id,
+      rule_index,
+      level,
+      message: sarif::Message::new(self.message()),
+      locations: self.sarif_location(run).into_iter().collect(),
+      related_locations,
+      fixes: Vec::new(),
+    }
+  }
+
   fn fmt_category_and_code(&self, f: &mut fmt::Formatter) -> fmt::Result {
     let category = match self.category {
       DiagnosticCategory::Error => "ERROR",
@@ -364,6 +465,25 @@ impl Diagnostics {
     !self.0.is_empty()
   }
 
+  pub fn into_vec(self) -> Vec<Diagnostic> {
+    self.0
+  }
+
+  pub fn has_error(&self) -> bool {
+    self
+      .0
+      .iter()
+      .any(|d| d.category == DiagnosticCategory::Error)
+  }
+
+  /// Adds the diagnostics to the results of a SARIF run.
+  pub fn add_to_sarif_run(&self, run: &mut sarif::Run) {
+    for diagnostic in &self.0 {
+      let result = diagnostic.to_sarif_result(run);
+      run.results.push(result);
+    }
+  }
+
   /// Modifies all the diagnostics to have their display positions
   /// modified to point at the original source.
   pub fn apply_fast_check_source_maps(&mut self, graph: &ModuleGraph) {
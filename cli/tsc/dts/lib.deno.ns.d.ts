@@ -703,6 +703,21 @@ declare namespace Deno {
      * will be set here. */
     parent?: TestContext;
 
+    /** Assert that `value` matches the snapshot stored for this assertion.
+     *
+     * The value is serialized with {@linkcode Deno.inspect} and compared with
+     * the snapshot in `__snapshots__/<test file>.snap` next to the test
+     * module. Run `deno test --update-snapshots` to create or update the
+     * snapshots.
+     *
+     * ```ts
+     * Deno.test("snapshot", (t) => {
+     *   t.assertSnapshot({ hello: "world" });
+     * });
+     * ```
+     */
+    assertSnapshot(value: unknown): void;
+
     /** Run a sub step of the parent test or step. Returns a promise
      * that resolves to a boolean signifying if the step completed successfully.
      *
@@ -809,6 +824,15 @@ declare namespace Deno {
      *
      * Defaults to the parent test or step's value. */
     sanitizeExit?: boolean;
+    /** Number of times to re-run the step if it fails. A step that passes
+     * after failing is reported as flaky.
+     *
+     * @default {0} */
+    retries?: number;
+    /** Number of milliseconds the step may run for. A step that takes longer
+     * is terminated and fails, together with the test it belongs to, even if
+     * it is stuck in synchronous code. */
+    timeout?: number;
   }
 
   /** @category Testing */
@@ -852,6 +876,17 @@ declare namespace Deno {
      *
      * @default {"inherit"} */
     permissions?: PermissionOptions;
+    /** Number of times to re-run the test if it fails. A test that passes
+     * after failing is reported as flaky.
+     *
+     * Defaults to the value of the `--retry` flag. */
+    retries?: number;
+    /** Number of milliseconds the test may run for. A test that takes longer
+     * is terminated and fails, even if it is stuck in synchronous code. The
+     * remaining tests of the module still run.
+     *
+     * Defaults to the value of the `--timeout` flag. */
+    timeout?: number;
   }
 
   /** Register a test which will be run when `deno test` is used on the command
@@ -1496,6 +1496,12 @@ declare namespace Deno {
     export interface Plugin {
       name: string;
       rules: Record<string, Rule>;
+      /**
+       * Whether the diagnostics of the plugin only depend on the linted
+       * file, so that files without diagnostics are skipped while they
+       * and the plugin are unchanged.
+       */
+      cacheable?: boolean;
     }
 
     /**
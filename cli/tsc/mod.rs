@@ -640,6 +640,24 @@ impl State {
     }
   }
 
+  fn from_request(
+    request: Request,
+    root_map: HashMap<String, ModuleSpecifier>,
+    remapped_specifiers: HashMap<String, ModuleSpecifier>,
+  ) -> Self {
+    State::new(
+      request.graph,
+      request.hash_data,
+      request.maybe_npm,
+      request.maybe_tsbuildinfo,
+      root_map,
+      remapped_specifiers,
+      std::env::current_dir()
+        .context("Unable to get CWD")
+        .unwrap(),
+    )
+  }
+
   pub fn maybe_remapped_specifier(
     &self,
     specifier: &str,
@@ -1355,16 +1373,10 @@ deno_core::extension!(deno_cli_tsc,
     remapped_specifiers: HashMap<String, Url>,
   },
   state = |state, options| {
-    state.put(State::new(
-      options.request.graph,
-      options.request.hash_data,
-      options.request.maybe_npm,
-      options.request.maybe_tsbuildinfo,
+    state.put(State::from_request(
+      options.request,
       options.root_map,
       options.remapped_specifiers,
-      std::env::current_dir()
-        .context("Unable to get CWD")
-        .unwrap(),
     ));
   },
   customizer = |ext: &mut deno_core::Extension| {
@@ -1452,6 +1464,30 @@ impl deno_core::ExtCodeCache for TscExtCodeCache {
 pub fn exec(
   request: Request,
   code_cache: Option<Arc<dyn deno_runtime::code_cache::CodeCache>>,
+) -> Result<Response, ExecError> {
+  exec_with_runtime(&mut None, request, code_cache)
+}
+
+/// Executes the request in the isolate of `maybe_runtime`, creating it when
+/// there is none. The isolate is kept for the next request, which doesn't
+/// parse the lib files and the unchanged modules again. It is discarded
+/// when the request fails.
+pub fn exec_with_runtime(
+  maybe_runtime: &mut Option<JsRuntime>,
+  request: Request,
+  code_cache: Option<Arc<dyn deno_runtime::code_cache::CodeCache>>,
+) -> Result<Response, ExecError> {
+  let result = exec_inner(maybe_runtime, request, code_cache);
+  if result.is_err() {
+    *maybe_runtime = None;
+  }
+  result
+}
+
+fn exec_inner(
+  maybe_runtime: &mut Option<JsRuntime>,
+  request: Request,
+  code_cache: Option<Arc<dyn deno_runtime::code_cache::CodeCache>>,
 ) -> Result<Response, ExecError> {
   // tsc cannot handle root specifiers that don't have one of the "acceptable"
   // extensions.  Therefore, we have to check the root modules against their
@@ -1487,23 +1523,36 @@ pub fn exec(
   });
   let exec_source = format!("globalThis.exec({request_value})");
 
-  let mut extensions =
-    deno_runtime::snapshot_info::get_extensions_in_snapshot();
-  extensions.push(deno_cli_tsc::init_ops_and_esm(
-    request,
-    root_map,
-    remapped_specifiers,
-  ));
-  let extension_code_cache = code_cache.map(|cache| {
-    Rc::new(TscExtCodeCache::new(cache)) as Rc<dyn deno_core::ExtCodeCache>
-  });
-  let mut runtime = JsRuntime::new(RuntimeOptions {
-    extensions,
-    create_params: create_isolate_create_params(),
-    startup_snapshot: deno_snapshots::CLI_SNAPSHOT,
-    extension_code_cache,
-    ..Default::default()
-  });
+  let runtime = match maybe_runtime.take() {
+    Some(runtime) => {
+      runtime.op_state().borrow_mut().put(State::from_request(
+        request,
+        root_map,
+        remapped_specifiers,
+      ));
+      runtime
+    }
+    None => {
+      let mut extensions =
+        deno_runtime::snapshot_info::get_extensions_in_snapshot();
+      extensions.push(deno_cli_tsc::init_ops_and_esm(
+        request,
+        root_map,
+        remapped_specifiers,
+      ));
+      let extension_code_cache = code_cache.map(|cache| {
+        Rc::new(TscExtCodeCache::new(cache)) as Rc<dyn deno_core::ExtCodeCache>
+      });
+      JsRuntime::new(RuntimeOptions {
+        extensions,
+        create_params: create_isolate_create_params(),
+        startup_snapshot: deno_snapshots::CLI_SNAPSHOT,
+        extension_code_cache,
+        ..Default::default()
+      })
+    }
+  };
+  let runtime = maybe_runtime.insert(runtime);
 
   runtime
     .execute_script(located_script_name!(), exec_source)
@@ -1,6 +1,7 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
 use std::fmt::Write as _;
+use std::ops::Range;
 
 use dissimilar::diff as difference;
 use dissimilar::Chunk;
@@ -147,6 +148,171 @@ impl DiffBuilder {
   }
 }
 
+/// Lines of unchanged text shown around the changes of a unified diff.
+const UNIFIED_CONTEXT_LINES: usize = 3;
+
+/// Lines of the original text that were replaced by lines of the edited
+/// text, as 0-based line indexes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineChange {
+  pub orig: Range<usize>,
+  pub edit: Range<usize>,
+}
+
+/// Splits a text into lines that keep their line ending.
+pub fn split_lines(text: &str) -> Vec<&str> {
+  text.split_inclusive('\n').collect()
+}
+
+/// Diffs two texts line by line.
+pub fn line_changes(orig_text: &str, edit_text: &str) -> Vec<LineChange> {
+  let orig_lines = split_lines(orig_text);
+  let edit_lines = split_lines(edit_text);
+
+  // diff the lines by mapping each distinct line to a char
+  let mut line_chars = std::collections::HashMap::new();
+  let mut to_chars = |lines: &[&str]| {
+    lines
+      .iter()
+      .map(|line| {
+        let next_index = line_chars.len() as u32;
+        *line_chars.entry(line.to_string()).or_insert_with(|| {
+          // skip the surrogate range, which isn't valid for chars
+          let code = if next_index < 0xD800 {
+            next_index
+          } else {
+            next_index + 0x800
+          };
+          char::from_u32(code).unwrap()
+        })
+      })
+      .collect::<String>()
+  };
+  let orig_chars = to_chars(&orig_lines);
+  let edit_chars = to_chars(&edit_lines);
+
+  let mut changes: Vec<LineChange> = Vec::new();
+  let mut orig_line = 0;
+  let mut edit_line = 0;
+  let mut last_was_change = false;
+  for chunk in difference(&orig_chars, &edit_chars) {
+    let (orig_count, edit_count) = match chunk {
+      Chunk::Equal(s) => {
+        let count = s.chars().count();
+        orig_line += count;
+        edit_line += count;
+        last_was_change = false;
+        continue;
+      }
+      Chunk::Delete(s) => (s.chars().count(), 0),
+      Chunk::Insert(s) => (0, s.chars().count()),
+    };
+    if !last_was_change {
+      changes.push(LineChange {
+        orig: orig_line..orig_line,
+        edit: edit_line..edit_line,
+      });
+    }
+    orig_line += orig_count;
+    edit_line += edit_count;
+    let change = changes.last_mut().unwrap();
+    change.orig.end = orig_line;
+    change.edit.end = edit_line;
+    last_was_change = true;
+  }
+  changes
+}
+
+/// Creates a unified diff of a file, which `git apply` and `patch -p1`
+/// accept.
+pub fn unified_diff(
+  file_name: &str,
+  orig_text: &str,
+  edit_text: &str,
+) -> String {
+  let orig_lines = split_lines(orig_text);
+  let edit_lines = split_lines(edit_text);
+  let changes = line_changes(orig_text, edit_text);
+  if changes.is_empty() {
+    return String::new();
+  }
+
+  let mut output = String::new();
+  writeln!(output, "--- a/{file_name}").unwrap();
+  writeln!(output, "+++ b/{file_name}").unwrap();
+
+  // group the changes whose context lines touch into hunks
+  let mut hunks: Vec<&[LineChange]> = Vec::new();
+  let mut hunk_start = 0;
+  for i in 1..=changes.len() {
+    if i == changes.len()
+      || changes[i].orig.start - changes[i - 1].orig.end
+        > UNIFIED_CONTEXT_LINES * 2
+    {
+      hunks.push(&changes[hunk_start..i]);
+      hunk_start = i;
+    }
+  }
+
+  for hunk in hunks {
+    let first = hunk.first().unwrap();
+    let last = hunk.last().unwrap();
+    let leading = first.orig.start.min(UNIFIED_CONTEXT_LINES);
+    let trailing =
+      (orig_lines.len() - last.orig.end).min(UNIFIED_CONTEXT_LINES);
+    let orig_range = first.orig.start - leading..last.orig.end + trailing;
+    let edit_range = first.edit.start - leading..last.edit.end + trailing;
+    writeln!(
+      output,
+      "@@ -{} +{} @@",
+      hunk_range(&orig_range),
+      hunk_range(&edit_range)
+    )
+    .unwrap();
+
+    let mut orig_line = orig_range.start;
+    for change in hunk {
+      for line in &orig_lines[orig_line..change.orig.start] {
+        write_hunk_line(&mut output, ' ', line);
+      }
+      for line in &orig_lines[change.orig.clone()] {
+        write_hunk_line(&mut output, '-', line);
+      }
+      for line in &edit_lines[change.edit.clone()] {
+        write_hunk_line(&mut output, '+', line);
+      }
+      orig_line = change.orig.end;
+    }
+    for line in &orig_lines[orig_line..orig_range.end] {
+      write_hunk_line(&mut output, ' ', line);
+    }
+  }
+  output
+}
+
+fn hunk_range(range: &Range<usize>) -> String {
+  let len = range.len();
+  // an empty range refers to the line before it
+  let start = if len == 0 {
+    range.start
+  } else {
+    range.start + 1
+  };
+  if len == 1 {
+    start.to_string()
+  } else {
+    format!("{start},{len}")
+  }
+}
+
+fn write_hunk_line(output: &mut String, prefix: char, line: &str) {
+  output.push(prefix);
+  output.push_str(line);
+  if !line.ends_with('\n') {
+    output.push_str("\n\\ No newline at end of file\n");
+  }
+}
+
 fn fmt_add() -> String {
   colors::green_bold("+").to_string()
 }
@@ -221,6 +387,55 @@ mod tests {
     run_test("test\n", "test\r\n", " | Text differed by line endings.\n");
   }
 
+  #[test]
+  fn test_line_changes() {
+    assert_eq!(
+      line_changes("a\nb\nc\nd\n", "a\nB\nc\nd\ne\n"),
+      vec![
+        LineChange {
+          orig: 1..2,
+          edit: 1..2,
+        },
+        LineChange {
+          orig: 4..4,
+          edit: 4..5,
+        },
+      ]
+    );
+    assert_eq!(line_changes("a\n", "a\n"), vec![]);
+  }
+
+  #[test]
+  fn test_unified_diff() {
+    let orig_text = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12";
+    let edit_text = "one\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n";
+    assert_eq!(
+      unified_diff("mod.ts", orig_text, edit_text),
+      concat!(
+        "--- a/mod.ts\n",
+        "+++ b/mod.ts\n",
+        "@@ -1,4 +1,4 @@\n",
+        "-1\n",
+        "+one\n",
+        " 2\n",
+        " 3\n",
+        " 4\n",
+        "@@ -9,4 +9,4 @@\n",
+        " 9\n",
+        " 10\n",
+        " 11\n",
+        "-12\n",
+        "\\ No newline at end of file\n",
+        "+12\n",
+      ),
+    );
+    assert_eq!(
+      unified_diff("mod.ts", "", "a\n"),
+      "--- a/mod.ts\n+++ b/mod.ts\n@@ -0,0 +1 @@\n+a\n",
+    );
+    assert_eq!(unified_diff("mod.ts", "a\n", "a\n"), "");
+  }
+
   fn run_test(diff_text1: &str, diff_text2: &str, expected_output: &str) {
     assert_eq!(
       test_util::strip_ansi_codes(&diff(diff_text1, diff_text2,)),
@@ -0,0 +1,309 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+
+use crate::util::fs::canonicalize_path;
+
+/// Runs `git` with the provided arguments in `cwd` and returns its stdout.
+fn run_git(cwd: &Path, args: &[&str]) -> Result<String, AnyError> {
+  let output = Command::new("git")
+    .args(args)
+    .current_dir(cwd)
+    .output()
+    .context("Failed to run git. Is it installed and in the PATH?")?;
+  if !output.status.success() {
+    bail!(
+      "`git {}` failed: {}",
+      args.join(" "),
+      String::from_utf8_lossy(&output.stderr).trim()
+    );
+  }
+  String::from_utf8(output.stdout)
+    .with_context(|| format!("`git {}` returned invalid UTF-8", args.join(" ")))
+}
+
+/// Runs `git` like `run_git`, writing `stdin` to its standard input.
+fn run_git_with_stdin(
+  cwd: &Path,
+  args: &[&str],
+  stdin: &[u8],
+) -> Result<String, AnyError> {
+  let mut child = Command::new("git")
+    .args(args)
+    .current_dir(cwd)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .context("Failed to run git. Is it installed and in the PATH?")?;
+  child
+    .stdin
+    .take()
+    .unwrap()
+    .write_all(stdin)
+    .with_context(|| format!("Failed to write to `git {}`", args.join(" ")))?;
+  let output = child.wait_with_output()?;
+  if !output.status.success() {
+    bail!(
+      "`git {}` failed: {}",
+      args.join(" "),
+      String::from_utf8_lossy(&output.stderr).trim()
+    );
+  }
+  String::from_utf8(output.stdout)
+    .with_context(|| format!("`git {}` returned invalid UTF-8", args.join(" ")))
+}
+
+/// Splits the output of a git command that was run with `-z`, which leaves
+/// the paths unquoted and separates them with NUL characters.
+fn nul_separated(text: &str) -> impl Iterator<Item = &str> {
+  text.split('\0').filter(|path| !path.is_empty())
+}
+
+/// Gets the root directory of the git repository containing `cwd`.
+pub fn repo_root(cwd: &Path) -> Result<PathBuf, AnyError> {
+  let output = run_git(cwd, &["rev-parse", "--show-toplevel"])?;
+  Ok(PathBuf::from(output.trim()))
+}
+
+/// Gets the path of `cwd` relative to the root of its git repository, which
+/// is empty at the root.
+pub fn repo_prefix(cwd: &Path) -> Result<PathBuf, AnyError> {
+  let output = run_git(cwd, &["rev-parse", "--show-prefix"])?;
+  Ok(PathBuf::from(output.trim()))
+}
+
+/// Gets the absolute paths of the files that differ between `git_ref` and
+/// the working tree, including untracked files. Deleted files are included.
+///
+/// The refs that users pass come after `--end-of-options`, so that one
+/// starting with a dash isn't taken as an option of git.
+pub fn changed_files_since(
+  cwd: &Path,
+  git_ref: &str,
+) -> Result<Vec<PathBuf>, AnyError> {
+  let root = repo_root(cwd)?;
+  let diff = run_git(
+    &root,
+    &[
+      "diff",
+      "--name-only",
+      "-z",
+      "--no-renames",
+      "--end-of-options",
+      git_ref,
+      "--",
+    ],
+  )?;
+  let mut paths = nul_separated(&diff)
+    .map(|path| root.join(path))
+    .collect::<Vec<_>>();
+  paths.extend(untracked_files(&root)?);
+  Ok(paths)
+}
+
+/// Gets the absolute paths of the untracked files that aren't ignored.
+pub fn untracked_files(cwd: &Path) -> Result<Vec<PathBuf>, AnyError> {
+  let root = repo_root(cwd)?;
+  let untracked =
+    run_git(&root, &["ls-files", "-z", "--others", "--exclude-standard"])?;
+  Ok(
+    nul_separated(&untracked)
+      .map(|path| root.join(path))
+      .collect(),
+  )
+}
+
+/// Gets the unified diff, without context lines, between `git_ref` and the
+/// working tree along with the repository root that its paths are relative
+/// to. Untracked files aren't part of it.
+pub fn diff_since(
+  cwd: &Path,
+  git_ref: &str,
+) -> Result<(PathBuf, String), AnyError> {
+  let root = repo_root(cwd)?;
+  let diff = run_git(
+    &root,
+    &[
+      "diff",
+      "--unified=0",
+      "--no-renames",
+      "--no-color",
+      "--no-ext-diff",
+      "--src-prefix=a/",
+      "--dst-prefix=b/",
+      "--end-of-options",
+      git_ref,
+      "--",
+    ],
+  )?;
+  Ok((root, diff))
+}
+
+/// Gets the contents of the file at `path` as of `git_ref`, or `None` if the
+/// file didn't exist at that point.
+pub fn file_at_ref(
+  cwd: &Path,
+  git_ref: &str,
+  path: &Path,
+) -> Result<Option<String>, AnyError> {
+  let root = repo_root(cwd)?;
+  let Ok(relative_path) = path.strip_prefix(&root) else {
+    return Ok(None);
+  };
+  let relative_path = relative_path.to_string_lossy().replace('\\', "/");
+  // check the existence first so that other failures aren't swallowed
+  let exists = run_git(
+    &root,
+    &[
+      "ls-tree",
+      "--name-only",
+      "--end-of-options",
+      git_ref,
+      "--",
+      &relative_path,
+    ],
+  )?;
+  if exists.trim().is_empty() {
+    return Ok(None);
+  }
+  run_git(
+    &root,
+    &[
+      "show",
+      "--end-of-options",
+      &format!("{git_ref}:{relative_path}"),
+    ],
+  )
+  .map(Some)
+}
+
+/// Gets the absolute paths of the files whose changes are staged, leaving
+/// out deleted files.
+pub fn staged_files(cwd: &Path) -> Result<Vec<PathBuf>, AnyError> {
+  let root = repo_root(cwd)?;
+  let diff = run_git(
+    &root,
+    &[
+      "diff",
+      "--cached",
+      "--name-only",
+      "-z",
+      "--no-renames",
+      "--diff-filter=d",
+      "--",
+    ],
+  )?;
+  Ok(nul_separated(&diff).map(|path| root.join(path)).collect())
+}
+
+/// The files that are staged, or that changed since a git ref, for
+/// narrowing the files that a subcommand collects.
+#[derive(Debug, Default)]
+pub struct ChangedPaths(HashSet<PathBuf>);
+
+impl ChangedPaths {
+  /// Gets the staged files when `staged` is set, otherwise the files changed
+  /// since `changed_since`. `None` when neither is provided.
+  pub fn resolve(
+    cwd: &Path,
+    staged: bool,
+    changed_since: Option<&str>,
+  ) -> Result<Option<Self>, AnyError> {
+    let paths = if staged {
+      staged_files(cwd)?
+    } else if let Some(git_ref) = changed_since {
+      changed_files_since(cwd, git_ref)?
+    } else {
+      return Ok(None);
+    };
+    Ok(Some(Self(
+      paths
+        .iter()
+        .filter_map(|path| canonicalize_path(path).ok())
+        .collect(),
+    )))
+  }
+
+  pub fn contains(&self, path: &Path) -> bool {
+    canonicalize_path(path)
+      .map(|path| self.0.contains(&path))
+      .unwrap_or(false)
+  }
+}
+
+/// A file of the git index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+  /// The file mode, like `100644`.
+  pub mode: String,
+  /// The path relative to the repository root, with forward slashes.
+  pub path: String,
+  pub text: String,
+}
+
+/// Gets the staged contents of the file at `path`, or `None` if the file
+/// isn't in the index.
+pub fn read_index_entry(
+  cwd: &Path,
+  path: &Path,
+) -> Result<Option<IndexEntry>, AnyError> {
+  // canonicalize both, because the paths of the collected files can go
+  // through symlinks unlike the root that git reports
+  let root = canonicalize_path(&repo_root(cwd)?)?;
+  let path = canonicalize_path(path)?;
+  let Ok(relative_path) = path.strip_prefix(&root) else {
+    return Ok(None);
+  };
+  let relative_path = relative_path.to_string_lossy().replace('\\', "/");
+  let stage = run_git(&root, &["ls-files", "--stage", "--", &relative_path])?;
+  // <mode> <object> <stage>\t<path>
+  let Some((mode, object)) = stage
+    .lines()
+    .next()
+    .and_then(|line| line.split('\t').next())
+    .and_then(|info| {
+      let mut parts = info.split(' ');
+      Some((parts.next()?.to_string(), parts.next()?.to_string()))
+    })
+  else {
+    return Ok(None);
+  };
+  let text = run_git(&root, &["cat-file", "blob", &object])?;
+  Ok(Some(IndexEntry {
+    mode,
+    path: relative_path,
+    text,
+  }))
+}
+
+/// Replaces the staged contents of a file, keeping its mode.
+pub fn write_index_entry(
+  cwd: &Path,
+  entry: &IndexEntry,
+) -> Result<(), AnyError> {
+  let root = repo_root(cwd)?;
+  let object = run_git_with_stdin(
+    &root,
+    &["hash-object", "-w", "--stdin"],
+    entry.text.as_bytes(),
+  )?;
+  run_git(
+    &root,
+    &[
+      "update-index",
+      "--cacheinfo",
+      &format!("{},{},{}", entry.mode, object.trim(), entry.path),
+    ],
+  )?;
+  Ok(())
+}
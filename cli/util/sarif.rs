@@ -0,0 +1,397 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! A subset of the SARIF 2.1.0 format, which code scanning services read to
+//! show diagnostics as annotations.
+//! See https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use deno_core::url::Url;
+use serde::Serialize;
+
+const SCHEMA_URL: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// Locations are made relative to this base, so that they resolve against
+/// the checkout of a repository.
+const SRCROOT: &str = "%SRCROOT%";
+
+#[derive(Debug, Serialize)]
+pub struct SarifLog<'a> {
+  #[serde(rename = "$schema")]
+  schema: &'static str,
+  version: &'static str,
+  runs: Vec<&'a Run>,
+}
+
+impl<'a> SarifLog<'a> {
+  pub fn new(run: &'a Run) -> Self {
+    Self {
+      schema: SCHEMA_URL,
+      version: SARIF_VERSION,
+      runs: vec![run],
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Run {
+  tool: Tool,
+  #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+  original_uri_base_ids: BTreeMap<&'static str, ArtifactLocation>,
+  column_kind: ColumnKind,
+  pub results: Vec<SarifResult>,
+  pub invocations: Vec<Invocation>,
+  #[serde(skip)]
+  root: Option<Url>,
+}
+
+impl Run {
+  /// Creates a run of `tool` whose locations are relative to `root_dir`.
+  pub fn new(
+    tool_name: &str,
+    information_uri: &str,
+    column_kind: ColumnKind,
+    root_dir: &Path,
+  ) -> Self {
+    let root = Url::from_directory_path(root_dir).ok();
+    let mut original_uri_base_ids = BTreeMap::new();
+    if let Some(root) = &root {
+      original_uri_base_ids.insert(
+        SRCROOT,
+        ArtifactLocation {
+          uri: root.to_string(),
+          uri_base_id: None,
+        },
+      );
+    }
+    Self {
+      tool: Tool {
+        driver: ToolComponent {
+          name: tool_name.to_string(),
+          version: deno_lib::version::DENO_VERSION_INFO.deno.to_string(),
+          information_uri: information_uri.to_string(),
+          rules: Vec::new(),
+        },
+      },
+      original_uri_base_ids,
+      column_kind,
+      results: Vec::new(),
+      invocations: Vec::new(),
+      root,
+    }
+  }
+
+  /// The index of the rule with `id`, added with `describe` the first time.
+  pub fn rule_index(
+    &mut self,
+    id: &str,
+    describe: impl FnOnce() -> ReportingDescriptor,
+  ) -> usize {
+    let rules = &mut self.tool.driver.rules;
+    match rules.iter().position(|rule| rule.id == id) {
+      Some(index) => index,
+      None => {
+        rules.push(ReportingDescriptor {
+          id: id.to_string(),
+          ..describe()
+        });
+        rules.len() - 1
+      }
+    }
+  }
+
+  /// Makes the location of a file relative to the root when it's within it.
+  pub fn artifact_location(&self, specifier: &str) -> ArtifactLocation {
+    let relative = self.root.as_ref().and_then(|root| {
+      let url = Url::parse(specifier).ok()?;
+      let relative = root.make_relative(&url)?;
+      (url.scheme() == "file" && !relative.starts_with("../"))
+        .then_some(relative)
+    });
+    match relative {
+      Some(relative) => ArtifactLocation {
+        uri: relative,
+        uri_base_id: Some(SRCROOT.to_string()),
+      },
+      None => ArtifactLocation {
+        uri: specifier.to_string(),
+        uri_base_id: None,
+      },
+    }
+  }
+
+  /// Sorts the results by location, for a deterministic output.
+  pub fn sort_results(&mut self) {
+    self.results.sort_by(|a, b| {
+      let key = |result: &SarifResult| {
+        result.locations.first().map(|location| {
+          let location = &location.physical_location;
+          (
+            location.artifact_location.uri.clone(),
+            location
+              .region
+              .as_ref()
+              .map(|r| (r.start_line, r.start_column)),
+          )
+        })
+      };
+      key(a).cmp(&key(b))
+    });
+  }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ColumnKind {
+  Utf16CodeUnits,
+  UnicodeCodePoints,
+}
+
+#[derive(Debug, Serialize)]
+struct Tool {
+  driver: ToolComponent,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolComponent {
+  name: String,
+  version: String,
+  information_uri: String,
+  rules: Vec<ReportingDescriptor>,
+}
+
+/// The metadata of a rule.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportingDescriptor {
+  pub id: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub help_uri: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub properties: Option<PropertyBag>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PropertyBag {
+  pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Level {
+  Error,
+  Warning,
+  Note,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+  pub rule_id: String,
+  pub rule_index: usize,
+  pub level: Level,
+  pub message: Message,
+  pub locations: Vec<Location>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub related_locations: Vec<Location>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub fixes: Vec<Fix>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Message {
+  pub text: String,
+}
+
+impl Message {
+  pub fn new(text: impl Into<String>) -> Self {
+    Self { text: text.into() }
+  }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+  pub physical_location: PhysicalLocation,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub message: Option<Message>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+  pub artifact_location: ArtifactLocation,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub region: Option<Region>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactLocation {
+  pub uri: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub uri_base_id: Option<String>,
+}
+
+/// A range of a file, with 1-based lines and columns.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+  pub start_line: usize,
+  pub start_column: usize,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub end_line: Option<usize>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub end_column: Option<usize>,
+}
+
+impl Region {
+  /// Converts 0-based line and column indexes.
+  pub fn from_indexes(
+    start: (usize, usize),
+    end: Option<(usize, usize)>,
+  ) -> Self {
+    Self {
+      start_line: start.0 + 1,
+      start_column: start.1 + 1,
+      end_line: end.map(|end| end.0 + 1),
+      end_column: end.map(|end| end.1 + 1),
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fix {
+  pub description: Message,
+  pub artifact_changes: Vec<ArtifactChange>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactChange {
+  pub artifact_location: ArtifactLocation,
+  pub replacements: Vec<Replacement>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Replacement {
+  pub deleted_region: Region,
+  pub inserted_content: ArtifactContent,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArtifactContent {
+  pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Invocation {
+  pub execution_successful: bool,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub tool_execution_notifications: Vec<Notification>,
+}
+
+/// A problem of the tool itself, like a file that failed to be processed.
+#[derive(Debug, Serialize)]
+pub struct Notification {
+  pub level: Level,
+  pub message: Message,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub locations: Vec<Location>,
+}
+
+#[cfg(test)]
+mod tests {
+  use deno_core::serde_json;
+  use deno_core::serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn serializes_run() {
+    let root_dir = if cfg!(windows) {
+      Path::new("C:\\project")
+    } else {
+      Path::new("/project")
+    };
+    let root_url = Url::from_directory_path(root_dir).unwrap();
+    let mut run = Run::new(
+      "deno lint",
+      "https://docs.deno.com/go/lint",
+      ColumnKind::UnicodeCodePoints,
+      root_dir,
+    );
+    let rule_index = run.rule_index("no-var", || ReportingDescriptor {
+      help_uri: Some("https://docs.deno.com/lint/rules/no-var".to_string()),
+      ..Default::default()
+    });
+    assert_eq!(run.rule_index("no-var", || unreachable!()), rule_index);
+    let location =
+      run.artifact_location(root_url.join("src/mod.ts").unwrap().as_str());
+    run.results.push(SarifResult {
+      rule_id: "no-var".to_string(),
+      rule_index,
+      level: Level::Error,
+      message: Message::new("`var` keyword is not allowed."),
+      locations: vec![Location {
+        physical_location: PhysicalLocation {
+          artifact_location: location,
+          region: Some(Region::from_indexes((0, 0), Some((0, 3)))),
+        },
+        message: None,
+      }],
+      related_locations: Vec::new(),
+      fixes: Vec::new(),
+    });
+    assert_eq!(
+      run.artifact_location("https://deno.land/x/mod.ts").uri,
+      "https://deno.land/x/mod.ts"
+    );
+
+    let value = serde_json::to_value(SarifLog::new(&run)).unwrap();
+    let run = &value["runs"][0];
+    assert_eq!(value["version"], "2.1.0");
+    assert_eq!(
+      run["originalUriBaseIds"]["%SRCROOT%"]["uri"],
+      root_url.as_str()
+    );
+    assert_eq!(run["columnKind"], "unicodeCodePoints");
+    assert_eq!(
+      run["tool"]["driver"]["rules"],
+      json!([{
+        "id": "no-var",
+        "helpUri": "https://docs.deno.com/lint/rules/no-var",
+      }])
+    );
+    assert_eq!(
+      run["results"][0],
+      json!({
+        "ruleId": "no-var",
+        "ruleIndex": 0,
+        "level": "error",
+        "message": { "text": "`var` keyword is not allowed." },
+        "locations": [{
+          "physicalLocation": {
+            "artifactLocation": {
+              "uri": "src/mod.ts",
+              "uriBaseId": "%SRCROOT%",
+            },
+            "region": {
+              "startLine": 1,
+              "startColumn": 1,
+              "endLine": 1,
+              "endColumn": 4,
+            },
+          },
+        }],
+      })
+    );
+  }
+}
@@ -39,6 +39,15 @@ pub trait HmrRunner: Send + Sync {
 pub trait CoverageCollector: Send + Sync {
   async fn start_collecting(&mut self) -> Result<(), CoreError>;
   async fn stop_collecting(&mut self) -> Result<(), CoreError>;
+  /// Writes the coverage collected since the start or the previous call,
+  /// attributed to the test named `test` of the module `origin`, or to the
+  /// module itself when `test` is `None`. Later coverage, up to the next
+  /// call, is attributed to the module.
+  async fn take_test_coverage(
+    &mut self,
+    origin: &str,
+    test: Option<&str>,
+  ) -> Result<(), CoreError>;
 }
 
 pub type CreateHmrRunnerCb = Box<
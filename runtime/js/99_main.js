@@ -523,6 +523,7 @@ function shimTemporalDurationToLocaleString() {
 const NOT_IMPORTED_OPS = [
   // Related to `Deno.bench()` API
   "op_bench_now",
+  "op_bench_heap_used",
   "op_dispatch_bench_event",
   "op_register_bench",
   "op_bench_get_origin",
@@ -540,7 +541,9 @@ const NOT_IMPORTED_OPS = [
   "op_is_cancelled",
 
   // Related to `Deno.test()` API
+  "op_test_assert_snapshot",
   "op_test_event_step_result_failed",
+  "op_test_event_step_result_flaky",
   "op_test_event_step_result_ignored",
   "op_test_event_step_result_ok",
   "op_test_event_step_wait",
@@ -552,6 +555,8 @@ const NOT_IMPORTED_OPS = [
   "op_register_test_step",
   "op_register_test",
   "op_test_get_origin",
+  "op_test_step_timeout_end",
+  "op_test_step_timeout_start",
   "op_pledge_test_permissions",
 
   // TODO(bartlomieju): used in various integration tests - figure out a way
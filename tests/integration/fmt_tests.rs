@@ -442,3 +442,133 @@ fn opt_out_top_level_exclude_via_fmt_unexclude() {
   assert_contains!(output, "excluded.ts");
   assert_not_contains!(output, "actually_excluded.ts");
 }
+
+#[test]
+fn fmt_staged() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir().path();
+  let git = |args: &[&str]| {
+    let output = std::process::Command::new("git")
+      .args(args)
+      .current_dir(temp_dir)
+      .output()
+      .unwrap();
+    assert!(output.status.success(), "git {args:?} failed");
+    String::from_utf8(output.stdout).unwrap()
+  };
+  git(&["init", "--quiet"]);
+  temp_dir.join("staged.ts").write("const a   = 1;\n");
+  git(&["add", "staged.ts"]);
+  // partially staged, because this line isn't staged
+  temp_dir
+    .join("staged.ts")
+    .write("const a   = 1;\nconst b   = 2;\n");
+  temp_dir.join("unstaged.ts").write("const c   = 3;\n");
+
+  let output = context.new_command().args("fmt --check --staged").run();
+  output.assert_exit_code(1);
+  assert_contains!(
+    output.combined_output(),
+    "Found 1 not formatted file in 1 file"
+  );
+
+  let output = context.new_command().args("fmt --staged").run();
+  output.assert_exit_code(0);
+  assert_contains!(output.combined_output(), "Checked 1 file");
+  assert_eq!(git(&["show", ":staged.ts"]), "const a = 1;\n");
+  assert_eq!(
+    temp_dir.join("staged.ts").read_to_string(),
+    "const a = 1;\nconst b = 2;\n"
+  );
+  assert_eq!(
+    temp_dir.join("unstaged.ts").read_to_string(),
+    "const c   = 3;\n"
+  );
+
+  let output = context.new_command().args("fmt --check --staged").run();
+  output.assert_exit_code(0);
+
+  let output = context.new_command().args("lint --staged").run();
+  output.assert_exit_code(1);
+  let output = output.combined_output();
+  assert_contains!(output, "staged.ts");
+  assert_not_contains!(output, "unstaged.ts");
+}
+
+#[test]
+fn fmt_check_output_diff() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir().path();
+  let git = |args: &[&str]| {
+    let output = std::process::Command::new("git")
+      .args(args)
+      .current_dir(temp_dir)
+      .output()
+      .unwrap();
+    assert!(output.status.success(), "git {args:?} failed");
+  };
+  git(&["init", "--quiet"]);
+  temp_dir
+    .join("a.ts")
+    .write("const a   = 1\n\nfunction b(){return 2}");
+  temp_dir.join("sub/b.json").write("{\"b\":   [1,2]}\n");
+  temp_dir.join("formatted.ts").write("const c = 3;\n");
+
+  let output = context
+    .new_command()
+    .args("fmt --check --output=diff")
+    .split_output()
+    .run();
+  output.assert_exit_code(1);
+  assert_contains!(output.stderr(), "Found 2 not formatted files in 3 files");
+  let patch = output.stdout();
+  assert_contains!(patch, "--- a/a.ts\n+++ b/a.ts\n");
+  assert_contains!(patch, "--- a/sub/b.json\n+++ b/sub/b.json\n");
+  assert_not_contains!(patch, "formatted.ts");
+
+  temp_dir.join("fmt.patch").write(patch);
+  git(&["apply", "fmt.patch"]);
+  temp_dir.join("fmt.patch").remove_file();
+  let output = context.new_command().args("fmt --check").run();
+  output.assert_exit_code(0);
+  assert_contains!(output.combined_output(), "Checked 3 files");
+}
+
+#[test]
+fn fmt_check_output_diff_subdir() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir().path();
+  let git = |args: &[&str], cwd: &PathRef| {
+    let output = std::process::Command::new("git")
+      .args(args)
+      .current_dir(cwd)
+      .output()
+      .unwrap();
+    assert!(output.status.success(), "git {args:?} failed");
+  };
+  git(&["init", "--quiet"], temp_dir);
+  let sub_dir = temp_dir.join("sub");
+  sub_dir.join("b.json").write("{\"b\":   [1,2]}\n");
+
+  // the paths are relative to the repository root, not to the cwd
+  let output = context
+    .new_command()
+    .current_dir(&sub_dir)
+    .args("fmt --check --output=diff")
+    .split_output()
+    .run();
+  output.assert_exit_code(1);
+  let patch = output.stdout();
+  assert_contains!(patch, "--- a/sub/b.json\n+++ b/sub/b.json\n");
+
+  sub_dir.join("fmt.patch").write(patch);
+  git(&["apply", "fmt.patch"], &sub_dir);
+  sub_dir.join("fmt.patch").remove_file();
+  let output = context
+    .new_command()
+    .current_dir(&sub_dir)
+    .args("fmt --check")
+    .run();
+  output.assert_exit_code(0);
+  assert_contains!(output.combined_output(), "Checked 1 file");
+}
@@ -343,6 +343,78 @@ async fn lint_all_files_on_each_change_test() {
   drop(t);
 }
 
+#[flaky_test(tokio)]
+async fn check_watch_test() {
+  let t = TempDir::new();
+  let a_file = t.path().join("a.ts");
+  let b_file = t.path().join("b.ts");
+  let c_file = t.path().join("c.ts");
+  a_file.write("import { b } from \"./b.ts\";\nexport const a: number = b;\n");
+  b_file.write("export const b: string = \"b\";\n");
+  c_file.write("export const c: number = \"c\";\n");
+
+  let mut child = util::deno_cmd()
+    .current_dir(t.path())
+    .arg("check")
+    .arg("--watch")
+    .arg("a.ts")
+    .arg("c.ts")
+    .env("NO_COLOR", "1")
+    .piped_output()
+    .spawn()
+    .unwrap();
+  let (_stdout_lines, mut stderr_lines) = child_lines(&mut child);
+
+  wait_contains(
+    "Found 2 problems (2 new, 0 unchanged, 0 fixed).",
+    &mut stderr_lines,
+  )
+  .await;
+  wait_contains("Check failed", &mut stderr_lines).await;
+
+  // only a.ts depends on b.ts, so c.ts isn't checked again
+  b_file.write("export const b: number = 1;\n");
+  assert_contains!(
+    wait_contains("Check file:///", &mut stderr_lines).await,
+    "a.ts"
+  );
+  assert_contains!(next_line(&mut stderr_lines).await.unwrap(), "Fixed TS2322");
+  wait_contains(
+    "Found 1 problem (0 new, 1 unchanged, 1 fixed).",
+    &mut stderr_lines,
+  )
+  .await;
+  wait_contains("Check failed", &mut stderr_lines).await;
+
+  c_file.write("export const c: number = 1;\n");
+  assert_contains!(
+    wait_contains("Check file:///", &mut stderr_lines).await,
+    "c.ts"
+  );
+  assert_contains!(next_line(&mut stderr_lines).await.unwrap(), "Fixed TS2322");
+  wait_contains("Check finished", &mut stderr_lines).await;
+
+  // the modules that are kept between the checks are updated on change
+  b_file.write("export const b: string = \"b\";\n");
+  wait_contains(
+    "Found 1 problem (1 new, 0 unchanged, 0 fixed).",
+    &mut stderr_lines,
+  )
+  .await;
+  wait_contains("Check failed", &mut stderr_lines).await;
+
+  let d_file = t.path().join("d.ts");
+  d_file.write("export const d: number = 1;\n");
+  a_file.write("import { d } from \"./d.ts\";\nexport const a: number = d;\n");
+  assert_contains!(
+    wait_contains("Check file:///", &mut stderr_lines).await,
+    "a.ts"
+  );
+  assert_contains!(next_line(&mut stderr_lines).await.unwrap(), "Fixed TS2322");
+  wait_contains("Check finished", &mut stderr_lines).await;
+  check_alive_then_kill(child);
+}
+
 #[flaky_test(tokio)]
 async fn fmt_watch_test() {
   let fmt_testdata_path = util::testdata_path().join("fmt");
@@ -0,0 +1,7 @@
+Deno.bench("noop", () => {});
+Deno.bench("sum, with a comma", { group: "math" }, () => {
+  let sum = 0;
+  for (let i = 0; i < 10; i++) {
+    sum += i;
+  }
+});
@@ -0,0 +1,4 @@
+Deno.bench("noop", () => {});
+Deno.bench("throws", () => {
+  throw new Error("boom");
+});
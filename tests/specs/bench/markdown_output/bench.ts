@@ -0,0 +1,2 @@
+Deno.bench("noop", () => {});
+Deno.bench("a | b", { group: "pipes" }, () => {});
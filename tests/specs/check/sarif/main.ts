@@ -0,0 +1,2 @@
+const value: string = 1;
+console.log(value);
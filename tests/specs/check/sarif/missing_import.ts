@@ -0,0 +1,3 @@
+import { value } from "./does_not_exist.ts";
+
+console.log(value);
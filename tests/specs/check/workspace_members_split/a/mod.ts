@@ -0,0 +1,4 @@
+export function a(value) {
+  const n: number = "a";
+  return [value, n];
+}
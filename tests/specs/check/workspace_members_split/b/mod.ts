@@ -0,0 +1,3 @@
+export function b(value) {
+  return value;
+}
@@ -0,0 +1,13 @@
+const final = JSON.parse(
+  Deno.readTextFileSync("istanbul/coverage-final.json"),
+);
+for (const [path, file] of Object.entries<any>(final)) {
+  console.log(path.endsWith("source.ts"), path === file.path);
+  for (const [id, fn] of Object.entries<any>(file.fnMap)) {
+    console.log(fn.name, fn.loc.start.line, file.f[id]);
+  }
+}
+const summary = JSON.parse(
+  Deno.readTextFileSync("istanbul/coverage-summary.json"),
+);
+console.log(summary.total.functions);
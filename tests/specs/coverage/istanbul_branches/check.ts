@@ -0,0 +1,17 @@
+const final = JSON.parse(
+  Deno.readTextFileSync("istanbul/coverage-final.json"),
+);
+for (const file of Object.values<any>(final)) {
+  for (const [id, branch] of Object.entries<any>(file.branchMap)) {
+    console.log(
+      branch.type,
+      branch.line,
+      branch.locations.length,
+      JSON.stringify(file.b[id]),
+    );
+  }
+}
+const summary = JSON.parse(
+  Deno.readTextFileSync("istanbul/coverage-summary.json"),
+);
+console.log(summary.total.branches);
@@ -0,0 +1,7 @@
+import { sign } from "./source.ts";
+
+Deno.test("sign()", () => {
+  sign(1);
+  sign(2);
+  sign(0);
+});
@@ -0,0 +1 @@
+const d = 4;
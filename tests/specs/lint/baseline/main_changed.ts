@@ -0,0 +1,3 @@
+var a = 1;
+var c = 3;
+export { a, c };
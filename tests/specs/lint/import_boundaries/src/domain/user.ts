@@ -0,0 +1,6 @@
+import { z } from "npm:zod@^3";
+import { router } from "../http/server.ts";
+import express from "express";
+
+export const User = z.object({ name: z.string() });
+export { express, router };
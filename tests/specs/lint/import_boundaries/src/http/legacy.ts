@@ -0,0 +1,3 @@
+import db from "database";
+
+export const client = db;
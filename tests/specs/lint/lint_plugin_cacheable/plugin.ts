@@ -0,0 +1,14 @@
+import { prefix } from "./helper.ts";
+
+export default {
+  name: "test-plugin",
+  cacheable: true,
+  rules: {
+    "my-rule": {
+      create(ctx) {
+        console.log(`${prefix}: ${ctx.id}`);
+        return {};
+      },
+    },
+  },
+};
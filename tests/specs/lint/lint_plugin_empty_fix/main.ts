@@ -0,0 +1,2 @@
+const value = "unfixed";
+console.log(value);
@@ -0,0 +1,24 @@
+export default {
+  name: "test-plugin",
+  rules: {
+    "my-rule": {
+      create(context) {
+        return {
+          VariableDeclarator(node) {
+            if (
+              node.init?.type === "Literal" && node.init.value === "unfixed"
+            ) {
+              context.report({
+                node: node.init!,
+                message: "should be fixed",
+                fix() {
+                  return [];
+                },
+              });
+            }
+          },
+        };
+      },
+    },
+  },
+} satisfies Deno.lint.Plugin;
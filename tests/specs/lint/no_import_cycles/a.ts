@@ -0,0 +1,7 @@
+import { b } from "./b.ts";
+
+export const a: number = 1;
+
+export function getB(): number {
+  return b;
+}
@@ -0,0 +1,4 @@
+import { a } from "./a.ts";
+
+export const value: number = a;
+export type { Shape } from "./shapes.ts";
@@ -0,0 +1,5 @@
+import type { Kind } from "./types.ts";
+
+export interface Shape {
+  kind: Kind;
+}
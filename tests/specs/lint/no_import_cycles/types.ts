@@ -0,0 +1,4 @@
+import type { Shape } from "./shapes.ts";
+
+export type Kind = "circle" | "square";
+export type Shapes = Shape[];
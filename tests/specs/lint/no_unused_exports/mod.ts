@@ -0,0 +1,7 @@
+import { add } from "./math.ts";
+
+export function sum(values: number[]): number {
+  return values.reduce(add, 0);
+}
+
+export * from "./types.ts";
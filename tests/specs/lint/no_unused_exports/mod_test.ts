@@ -0,0 +1,8 @@
+import { sum } from "./mod.ts";
+// doesn't count as a use of `sub`
+import { sub } from "./math.ts";
+
+Deno.test("sum", () => {
+  if (sum([1, 2]) !== 3) throw new Error();
+  if (sub(2, 1) !== 1) throw new Error();
+});
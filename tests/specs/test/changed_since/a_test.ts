@@ -0,0 +1,7 @@
+import { value } from "./util.ts";
+
+Deno.test("a", () => {
+  if (typeof value !== "number") {
+    throw new Error("value should be a number");
+  }
+});
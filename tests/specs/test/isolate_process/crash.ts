@@ -0,0 +1,5 @@
+Deno.test("exits the process", { sanitizeExit: false }, () => {
+  Deno.exit(3);
+});
+
+Deno.test("never runs", () => {});
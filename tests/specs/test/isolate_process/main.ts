@@ -0,0 +1,8 @@
+// runs in another process, so the crash of ./crash.ts doesn't affect it
+Deno.test("passes", () => {
+  console.log("output");
+});
+
+Deno.test("fails", () => {
+  throw new Error("boom");
+});
@@ -0,0 +1,15 @@
+// the child process gets the permissions, filter and script arguments of
+// the parent
+Deno.test("passes", () => {
+  if (Deno.args.join(" ") !== "arg") {
+    throw new Error(`unexpected args: ${Deno.args}`);
+  }
+  const status = Deno.permissions.querySync({ name: "env", variable: "FOO" });
+  if (status.state !== "granted") {
+    throw new Error(`unexpected permission state: ${status.state}`);
+  }
+});
+
+Deno.test("filtered out", () => {
+  throw new Error("should not run");
+});
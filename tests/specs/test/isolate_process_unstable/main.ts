@@ -0,0 +1,7 @@
+import { value } from "./value";
+
+Deno.test("resolves sloppy imports", () => {
+  if (value !== 1) {
+    throw new Error(`unexpected value: ${value}`);
+  }
+});
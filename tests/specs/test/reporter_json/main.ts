@@ -0,0 +1,4 @@
+Deno.test("passes", async (t) => {
+  console.log("hello");
+  await t.step("step", () => {});
+});
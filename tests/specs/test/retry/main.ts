@@ -0,0 +1,21 @@
+let testAttempts = 0;
+Deno.test("flaky test", () => {
+  testAttempts++;
+  if (testAttempts < 2) {
+    throw new Error("first attempt fails");
+  }
+});
+
+Deno.test("flaky step", async (t) => {
+  let stepAttempts = 0;
+  await t.step({
+    name: "step",
+    retries: 1,
+    fn() {
+      stepAttempts++;
+      if (stepAttempts < 2) {
+        throw new Error("first attempt fails");
+      }
+    },
+  });
+});
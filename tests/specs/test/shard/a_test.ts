@@ -0,0 +1,3 @@
+Deno.test("a", () => {
+  Deno.writeTextFileSync("ran.txt", "a_test.ts\n", { append: true });
+});
@@ -0,0 +1,3 @@
+Deno.test("b", () => {
+  Deno.writeTextFileSync("ran.txt", "b_test.ts\n", { append: true });
+});
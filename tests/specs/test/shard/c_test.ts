@@ -0,0 +1,3 @@
+Deno.test("c", () => {
+  Deno.writeTextFileSync("ran.txt", "c_test.ts\n", { append: true });
+});
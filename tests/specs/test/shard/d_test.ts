@@ -0,0 +1,3 @@
+Deno.test("d", () => {
+  Deno.writeTextFileSync("ran.txt", "d_test.ts\n", { append: true });
+});
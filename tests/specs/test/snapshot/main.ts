@@ -0,0 +1,3 @@
+Deno.test("snapshot", (t) => {
+  t.assertSnapshot(42);
+});
@@ -0,0 +1,23 @@
+Deno.test({ name: "sync loop", timeout: 100 }, () => {
+  while (true) {
+    // spin
+  }
+});
+
+Deno.test({ name: "slow promise", timeout: 100 }, async () => {
+  await new Promise((resolve) => setTimeout(resolve, 60_000));
+});
+
+Deno.test("slow step", async (t) => {
+  await t.step({
+    name: "step",
+    timeout: 100,
+    fn: () => {
+      while (true) {
+        // spin
+      }
+    },
+  });
+});
+
+Deno.test("still runs", () => {});
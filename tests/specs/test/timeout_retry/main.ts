@@ -0,0 +1,17 @@
+let attempts = 0;
+Deno.test({ name: "retried", retries: 1 }, async (t) => {
+  attempts++;
+  // the step of the first attempt is never reported, because the attempt
+  // times out and is retried
+  await t.step({
+    name: "step",
+    timeout: 100,
+    fn: () => {
+      while (attempts === 1) {
+        // spin
+      }
+    },
+  });
+});
+
+Deno.test("still runs", () => {});